@@ -4,7 +4,7 @@ use crate::{
     scopes::{Scope, ScopeState},
     Element,
 };
-use std::panic::AssertUnwindSafe;
+use std::{any::TypeId, marker::PhantomData, panic::AssertUnwindSafe};
 
 /// A trait that essentially allows VComponentProps to be used generically
 ///
@@ -13,6 +13,8 @@ use std::panic::AssertUnwindSafe;
 /// This should not be implemented outside this module
 pub(crate) unsafe trait AnyProps<'a> {
     fn props_ptr(&self) -> *const ();
+    fn props_type_id(&self) -> TypeId;
+    fn render_fn(&self) -> *const ();
     fn render(&'a self, bump: &'a ScopeState) -> RenderReturn<'a>;
     unsafe fn memoize(&self, other: &dyn AnyProps) -> bool;
 }
@@ -42,6 +44,14 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
         &self.props as *const _ as *const ()
     }
 
+    fn props_type_id(&self) -> TypeId {
+        erased_type_id::<P>()
+    }
+
+    fn render_fn(&self) -> *const () {
+        self.render_fn as *const ()
+    }
+
     // Safety:
     // this will downcast the other ptr as our swallowed type!
     // you *must* make this check *before* calling this method
@@ -74,3 +84,29 @@ unsafe impl<'a, P> AnyProps<'a> for VProps<'a, P> {
         }
     }
 }
+
+/// Get the [`TypeId`] of a type that may borrow data. Lifetimes are erased, so `Props<'a>` and `Props<'static>` have the
+/// same id.
+fn erased_type_id<T>() -> TypeId {
+    trait NonStaticAny {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static;
+    }
+
+    impl<T> NonStaticAny for PhantomData<T> {
+        fn get_type_id(&self) -> TypeId
+        where
+            Self: 'static,
+        {
+            TypeId::of::<T>()
+        }
+    }
+
+    let phantom = PhantomData::<T>;
+    let phantom: &dyn NonStaticAny = &phantom;
+    // Safety:
+    // the TypeId of a type doesn't depend on its lifetimes and PhantomData holds no data that could be borrowed
+    let phantom: &(dyn NonStaticAny + 'static) = unsafe { std::mem::transmute(phantom) };
+    phantom.get_type_id()
+}
@@ -0,0 +1,230 @@
+use crate::innerlude::{BorrowedAttributeValue, VComponent};
+use crate::mutations::Mutation::*;
+use crate::nodes::{DynamicNode, TemplateNode, VNode};
+use crate::virtual_dom::VirtualDom;
+use crate::{AttributeValue, ElementId, Mutations, RenderReturn, ScopeId};
+use rustc_hash::FxHashSet;
+
+impl<'b> VirtualDom {
+    /// Write every edit required to recreate the current state of the dom from scratch, without running any components.
+    ///
+    /// Unlike [`VirtualDom::rebuild`], this keeps all of the state stored in components and reuses the [`ElementId`]s
+    /// that are already assigned. This lets a renderer that lost its copy of the dom, like a liveview client that
+    /// reconnected after reloading the page, catch up with a VirtualDom that is already running.
+    ///
+    /// Every template that is currently mounted is included in the mutations, even if it was sent before.
+    ///
+    /// The mutations item expects the RealDom's stack to be the root of the application. If the VirtualDom was never
+    /// rebuilt, this returns no mutations.
+    pub fn replay(&mut self) -> Mutations {
+        let root = match self
+            .get_scope(ScopeId::ROOT)
+            .and_then(|s| s.try_root_node())
+        {
+            Some(root) => unsafe { root.extend_lifetime_ref() },
+            None => return std::mem::take(&mut self.mutations),
+        };
+
+        let mut templates = FxHashSet::default();
+        match root {
+            RenderReturn::Ready(node) => {
+                let m = self.replay_node(node, &mut templates);
+                self.mutations.push(AppendChildren {
+                    id: ElementId(0),
+                    m,
+                });
+            }
+            RenderReturn::Aborted(placeholder) => {
+                let id = placeholder.id.get().unwrap();
+                self.mutations.push(CreatePlaceholder { id });
+            }
+        }
+
+        std::mem::take(&mut self.mutations)
+    }
+
+    /// Write the edits to recreate a node and return the number of nodes it pushes on the stack
+    fn replay_node(
+        &mut self,
+        node: &'b VNode<'b>,
+        templates: &mut FxHashSet<&'static str>,
+    ) -> usize {
+        let template = node.template.get();
+        if templates.insert(template.name) && !template.is_completely_dynamic() {
+            self.mutations.templates.push(template);
+        }
+
+        template
+            .roots
+            .iter()
+            .enumerate()
+            .map(|(root_idx, root)| match root {
+                TemplateNode::Dynamic { id } | TemplateNode::DynamicText { id } => {
+                    self.replay_dynamic_root(&node.dynamic_nodes[*id], templates)
+                }
+                TemplateNode::Element { .. } => {
+                    let id = node.root_ids.borrow()[root_idx];
+                    self.mutations.push(LoadTemplate {
+                        name: template.name,
+                        index: root_idx,
+                        id,
+                    });
+                    self.replay_attrs_on_root(node, root_idx as u8, id);
+                    self.replay_placeholders(node, root_idx as u8, templates);
+                    1
+                }
+                TemplateNode::Text { .. } => {
+                    let id = node.root_ids.borrow()[root_idx];
+                    self.mutations.push(LoadTemplate {
+                        name: template.name,
+                        index: root_idx,
+                        id,
+                    });
+                    1
+                }
+            })
+            .sum()
+    }
+
+    fn replay_dynamic_root(
+        &mut self,
+        node: &'b DynamicNode<'b>,
+        templates: &mut FxHashSet<&'static str>,
+    ) -> usize {
+        match node {
+            DynamicNode::Component(component) => self.replay_component(component, templates),
+            DynamicNode::Fragment(nodes) => nodes
+                .iter()
+                .map(|node| self.replay_node(node, templates))
+                .sum(),
+            DynamicNode::Placeholder(placeholder) => {
+                let id = placeholder.id.get().unwrap();
+                self.mutations.push(CreatePlaceholder { id });
+                1
+            }
+            DynamicNode::Text(text) => {
+                // Safety: we promise not to re-alias this text later on after committing it to the mutation
+                let value = unsafe { std::mem::transmute(text.value) };
+                self.mutations.push(CreateTextNode {
+                    value,
+                    id: text.id.get().unwrap(),
+                });
+                1
+            }
+        }
+    }
+
+    fn replay_component(
+        &mut self,
+        component: &'b VComponent<'b>,
+        templates: &mut FxHashSet<&'static str>,
+    ) -> usize {
+        let scope = component.scope.get().unwrap();
+        match unsafe {
+            self.get_scope(scope)
+                .unwrap()
+                .root_node()
+                .extend_lifetime_ref()
+        } {
+            RenderReturn::Ready(node) => self.replay_node(node, templates),
+            RenderReturn::Aborted(placeholder) => {
+                let id = placeholder.id.get().unwrap();
+                self.mutations.push(CreatePlaceholder { id });
+                1
+            }
+        }
+    }
+
+    /// Write the dynamic attributes under a root in the same order they were created in
+    fn replay_attrs_on_root(&mut self, node: &'b VNode<'b>, root_idx: u8, root: ElementId) {
+        let attr_paths = node.template.get().attr_paths;
+        let mut attrs: Vec<_> = (0..attr_paths.len())
+            .filter(|idx| attr_paths[*idx].first() == Some(&root_idx))
+            .collect();
+        attrs.sort_by_key(|idx| attr_paths[*idx]);
+
+        let mut last_path = None;
+        for idx in attrs {
+            let path = attr_paths[idx];
+            let attribute = &node.dynamic_attrs[idx];
+
+            // Attributes on the root use the id of the root, deeper elements need to be assigned their id again
+            let id = if path.len() == 1 {
+                root
+            } else {
+                let id = attribute.mounted_element();
+                if last_path != Some(path) {
+                    self.mutations.push(AssignId {
+                        path: &path[1..],
+                        id,
+                    });
+                }
+                id
+            };
+            last_path = Some(path);
+
+            // Safety: we promise not to re-alias this text later on after committing it to the mutation
+            let name: &str = unsafe { std::mem::transmute(attribute.name) };
+            match &attribute.value {
+                // all listeners start with "on"
+                AttributeValue::Listener(_) => self.mutations.push(NewEventListener {
+                    name: &name[2..],
+                    id,
+                }),
+                _ => {
+                    // Safety: we promise not to re-alias this text later on after committing it to the mutation
+                    let value: BorrowedAttributeValue<'b> = (&attribute.value).into();
+                    let value = unsafe { std::mem::transmute(value) };
+                    self.mutations.push(SetAttribute {
+                        name,
+                        value,
+                        ns: attribute.namespace,
+                        id,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Write the dynamic nodes under a root, last to first so the paths of earlier nodes stay valid
+    fn replay_placeholders(
+        &mut self,
+        node: &'b VNode<'b>,
+        root_idx: u8,
+        templates: &mut FxHashSet<&'static str>,
+    ) {
+        let node_paths = node.template.get().node_paths;
+        let mut dynamic_nodes: Vec<_> = (0..node_paths.len())
+            .filter(|idx| node_paths[*idx].len() > 1 && node_paths[*idx][0] == root_idx)
+            .collect();
+        dynamic_nodes.sort_by_key(|idx| node_paths[*idx]);
+
+        for idx in dynamic_nodes.into_iter().rev() {
+            // The path is one shorter because the top node is the root
+            let path = &node_paths[idx][1..];
+            match &node.dynamic_nodes[idx] {
+                DynamicNode::Text(text) => {
+                    // Safety: we promise not to re-alias this text later on after committing it to the mutation
+                    let value = unsafe { std::mem::transmute(text.value) };
+                    self.mutations.push(HydrateText {
+                        path,
+                        value,
+                        id: text.id.get().unwrap(),
+                    });
+                }
+                DynamicNode::Placeholder(placeholder) => {
+                    self.mutations.push(AssignId {
+                        path,
+                        id: placeholder.id.get().unwrap(),
+                    });
+                }
+                dynamic => {
+                    let m = self.replay_dynamic_root(dynamic, templates);
+                    if m > 0 {
+                        self.mutations.push(ReplacePlaceholder { m, path });
+                    }
+                }
+            }
+        }
+    }
+}
@@ -12,7 +12,7 @@ use crate::{
 };
 use bumpalo::{boxed::Box as BumpBox, Bump};
 use std::{
-    any::Any,
+    any::{Any, TypeId},
     cell::{Cell, Ref, RefCell, UnsafeCell},
     fmt::{Arguments, Debug},
     future::Future,
@@ -131,6 +131,27 @@ impl<'src> ScopeState {
         self.context().name
     }
 
+    /// Get the props of this component if it was created from the given component function
+    ///
+    /// Returns `None` if this scope renders a different component, or if the props of the component borrow data.
+    pub fn props_of<P: Properties + 'static>(
+        &self,
+        component: fn(Scope<P>) -> Element,
+    ) -> Option<&P> {
+        let props = self.props.as_ref()?;
+        // Function pointers are not unique (identical functions may be merged), so the address only tells components
+        // apart. The type of the props is checked separately before the cast.
+        if props.render_fn() != component as *const () {
+            return None;
+        }
+        // Borrowed props have the same TypeId as their 'static version, so they are never handed out
+        if !P::IS_STATIC || props.props_type_id() != TypeId::of::<P>() {
+            return None;
+        }
+        // safety: the props are of type P and don't borrow from the parent
+        Some(unsafe { &*(props.props_ptr() as *const P) })
+    }
+
     /// Get the current render since the inception of this component
     ///
     /// This can be used as a helpful diagnostic when debugging hooks/renders, etc
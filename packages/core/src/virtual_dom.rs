@@ -590,6 +590,11 @@ impl VirtualDom {
         }
     }
 
+    /// Check if the virtual dom has any scopes that are suspended and waiting on async work to finish
+    pub fn has_suspended_work(&self) -> bool {
+        !self.suspended_scopes.is_empty()
+    }
+
     /// Render the virtual dom, waiting for all suspense to be finished
     ///
     /// The mutations will be thrown out, so it's best to use this method for things like SSR that have async content
@@ -0,0 +1,66 @@
+#![allow(non_snake_case)]
+
+//! Replaying a VirtualDom writes the same edits as creating it, without running any components
+
+use dioxus::core::Mutation::*;
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+
+fn app(cx: Scope) -> Element {
+    let count = use_state(cx, || 0);
+
+    cx.render(rsx! {
+        div { class: "app-{count}",
+            h1 { onclick: move |_| count += 1, "count: {count}" }
+            if **count > 0 {
+                rsx! { span { "clicked" } }
+            }
+            Child { name: "nested" }
+            (0..2).map(|i| rsx! { p { key: "{i}", "{i}" } })
+        }
+        "trailing text"
+    })
+}
+
+#[inline_props]
+fn Child(cx: Scope, name: &'static str) -> Element {
+    cx.render(rsx! { b { title: "{name}", "child" } })
+}
+
+#[test]
+fn replay_matches_rebuild() {
+    let mut dom = VirtualDom::new(app);
+    let rebuild = dom.rebuild().santize().edits;
+
+    let replay = dom.replay().santize();
+    assert_eq!(replay.edits, rebuild);
+    assert!(!replay.templates.is_empty());
+}
+
+#[test]
+fn replay_keeps_state() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    dom.handle_event(
+        "click",
+        std::rc::Rc::new(MouseData::default()),
+        ElementId(2),
+        true,
+    );
+    _ = dom.render_immediate();
+
+    let replay = dom.replay().santize();
+    assert!(replay.edits.contains(&SetAttribute {
+        name: "class",
+        value: dioxus_core::BorrowedAttributeValue::Text("app-1"),
+        id: ElementId(1),
+        ns: None,
+    }));
+    assert!(replay
+        .edits
+        .contains(&AppendChildren { id: ElementId(0), m: 2 }));
+
+    // Nothing changed, so replaying again writes the same edits
+    assert_eq!(dom.replay().santize().edits, replay.edits);
+}
@@ -1,6 +1,8 @@
 use std::borrow::Cow;
 use std::path::PathBuf;
 
+use dioxus_interpreter_js::binary_protocol::EditEncoding;
+
 use wry::application::window::Icon;
 use wry::{
     application::window::{Window, WindowBuilder},
@@ -36,6 +38,7 @@ pub struct Config {
     pub(crate) root_name: String,
     pub(crate) background_color: Option<(u8, u8, u8, u8)>,
     pub(crate) last_window_close_behaviour: WindowCloseBehaviour,
+    pub(crate) edit_encoding: EditEncoding,
 }
 
 type DropHandler = Box<dyn Fn(&Window, FileDropEvent) -> bool>;
@@ -65,6 +68,7 @@ impl Config {
             root_name: "main".to_string(),
             background_color: None,
             last_window_close_behaviour: WindowCloseBehaviour::LastWindowExitsApp,
+            edit_encoding: EditEncoding::default(),
         }
     }
 
@@ -166,6 +170,14 @@ impl Config {
         self.background_color = Some(color);
         self
     }
+
+    /// Set the format edits are sent to the WebView in. Edits are sent in the binary format by default.
+    ///
+    /// JSON edits are slower to send, but can be useful when debugging the interpreter.
+    pub fn with_edit_encoding(mut self, encoding: EditEncoding) -> Self {
+        self.edit_encoding = encoding;
+        self
+    }
 }
 
 impl Default for Config {
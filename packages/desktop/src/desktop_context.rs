@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::rc::Weak;
 
@@ -67,6 +68,8 @@ pub struct DesktopService {
 
     pub(crate) shortcut_manager: ShortcutRegistry,
 
+    pub(crate) data_dir: Option<PathBuf>,
+
     #[cfg(target_os = "ios")]
     pub(crate) views: Rc<RefCell<Vec<*mut objc::runtime::Object>>>,
 }
@@ -91,6 +94,7 @@ impl DesktopService {
         webviews: WebviewQueue,
         event_handlers: WindowEventHandlers,
         shortcut_manager: ShortcutRegistry,
+        data_dir: Option<PathBuf>,
     ) -> Self {
         Self {
             webview: Rc::new(webview),
@@ -100,11 +104,17 @@ impl DesktopService {
             pending_windows: webviews,
             event_handlers,
             shortcut_manager,
+            data_dir,
             #[cfg(target_os = "ios")]
             views: Default::default(),
         }
     }
 
+    /// Get the directory where data is stored, if one was set with [`crate::Config::with_data_directory`]
+    pub fn data_directory(&self) -> Option<&Path> {
+        self.data_dir.as_deref()
+    }
+
     /// Create a new window using the props and window builder
     ///
     /// Returns the webview handle for the new window.
@@ -29,6 +29,8 @@ use desktop_context::{EventData, UserWindowEvent, WebviewQueue, WindowEventHandl
 use dioxus_core::*;
 use dioxus_html::MountedData;
 use dioxus_html::{native_bind::NativeFileEngine, FormData, HtmlEvent};
+pub use dioxus_interpreter_js::binary_protocol::EditEncoding;
+use dioxus_interpreter_js::binary_protocol::MutationEncoder;
 use element::DesktopElement;
 use eval::init_eval;
 use futures_util::{pin_mut, FutureExt};
@@ -299,7 +301,11 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
 
                     view.dom.handle_event(&name, as_any, element, bubbles);
 
-                    send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+                    send_edits(
+                        view.dom.render_immediate(),
+                        &view.desktop_context.webview,
+                        view.edit_encoder.as_mut(),
+                    );
                 }
 
                 // When the webview sends a query, we need to send it to the query manager which handles dispatching the data to the correct pending query
@@ -322,7 +328,17 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
 
                 EventData::Ipc(msg) if msg.method() == "initialize" => {
                     let view = webviews.get_mut(&event.1).unwrap();
-                    send_edits(view.dom.rebuild(), &view.desktop_context.webview);
+
+                    // The page was (re)loaded, so the interpreter has a new decoder without any interned strings
+                    if let Some(encoder) = &mut view.edit_encoder {
+                        encoder.reset();
+                    }
+
+                    send_edits(
+                        view.dom.rebuild(),
+                        &view.desktop_context.webview,
+                        view.edit_encoder.as_mut(),
+                    );
                 }
 
                 EventData::Ipc(msg) if msg.method() == "browser_open" => {
@@ -360,7 +376,11 @@ pub fn launch_with_props<P: 'static>(root: Component<P>, props: P, cfg: Config)
                             view.dom.handle_event(event_name, data, id, event_bubbles);
                         }
 
-                        send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+                        send_edits(
+                            view.dom.render_immediate(),
+                            &view.desktop_context.webview,
+                            view.edit_encoder.as_mut(),
+                        );
                     }
                 }
 
@@ -381,6 +401,10 @@ fn create_new_window(
     event_handlers: &WindowEventHandlers,
     shortcut_manager: ShortcutRegistry,
 ) -> WebviewHandler {
+    let edit_encoder = match cfg.edit_encoding {
+        EditEncoding::Json => None,
+        EditEncoding::Binary => Some(MutationEncoder::new()),
+    };
     let (webview, web_context) = webview::build(&mut cfg, event_loop, proxy.clone());
     let desktop_context = Rc::from(DesktopService::new(
         webview,
@@ -389,6 +413,7 @@ fn create_new_window(
         queue.clone(),
         event_handlers.clone(),
         shortcut_manager,
+        cfg.data_dir.clone(),
     ));
 
     let cx = dom.base_scope();
@@ -402,6 +427,7 @@ fn create_new_window(
         waker: waker::tao_waker(proxy, desktop_context.webview.window().id()),
         desktop_context,
         dom,
+        edit_encoder,
         _web_context: web_context,
     }
 }
@@ -411,6 +437,9 @@ struct WebviewHandler {
     desktop_context: DesktopContext,
     waker: Waker,
 
+    // None if edits are sent as JSON
+    edit_encoder: Option<MutationEncoder>,
+
     // Wry assumes the webcontext is alive for the lifetime of the webview.
     // We need to keep the webcontext alive, otherwise the webview will crash
     _web_context: WebContext,
@@ -435,16 +464,31 @@ fn poll_vdom(view: &mut WebviewHandler) {
             }
         }
 
-        send_edits(view.dom.render_immediate(), &view.desktop_context.webview);
+        send_edits(
+            view.dom.render_immediate(),
+            &view.desktop_context.webview,
+            view.edit_encoder.as_mut(),
+        );
     }
 }
 
 /// Send a list of mutations to the webview
-fn send_edits(edits: Mutations, webview: &WebView) {
-    let serialized = serde_json::to_string(&edits).unwrap();
+///
+/// Binary edits are base64 encoded because scripts are the only way to send data to the webview synchronously
+fn send_edits(edits: Mutations, webview: &WebView, encoder: Option<&mut MutationEncoder>) {
+    let script = match encoder {
+        Some(encoder) => {
+            use base64::Engine;
+            let encoded = base64::engine::general_purpose::STANDARD.encode(encoder.encode(&edits));
+            format!("window.interpreter.handleBinaryEdits(Uint8Array.from(atob(\"{encoded}\"), (c) => c.charCodeAt(0)))")
+        }
+        None => {
+            let serialized = serde_json::to_string(&edits).unwrap();
+            format!("window.interpreter.handleEdits({serialized})")
+        }
+    };
 
-    // todo: use SSE and binary data to send the edits with lower overhead
-    _ = webview.evaluate_script(&format!("window.interpreter.handleEdits({serialized})"));
+    _ = webview.evaluate_script(&script);
 }
 
 /// Different hide implementations per platform
@@ -1,11 +1,12 @@
 use crate::{AtomId, AtomRoot, Readable, Writable};
+use std::rc::Rc;
 
 pub struct Atom<T>(pub fn(AtomBuilder) -> T);
 pub struct AtomBuilder;
 
-impl<V> Readable<V> for &'static Atom<V> {
-    fn read(&self, _root: AtomRoot) -> Option<V> {
-        todo!()
+impl<V: 'static> Readable<V> for &'static Atom<V> {
+    fn read(&self, root: &AtomRoot) -> Option<Rc<V>> {
+        root.get(self.unique_id())
     }
     fn init(&self) -> V {
         self.0(AtomBuilder)
@@ -15,9 +16,9 @@ impl<V> Readable<V> for &'static Atom<V> {
     }
 }
 
-impl<V> Writable<V> for &'static Atom<V> {
-    fn write(&self, _root: AtomRoot, _value: V) {
-        todo!()
+impl<V: 'static> Writable<V> for &'static Atom<V> {
+    fn write(&self, root: &AtomRoot, value: V) {
+        root.set(self.unique_id(), value)
     }
 }
 
@@ -42,3 +43,15 @@ fn atom_is_unique_2() {
     static TEST_ATOM_2: Atom<Vec<String>> = Atom(|_| Vec::new());
     assert_ne!((&TEST_ATOM_1).unique_id(), (&TEST_ATOM_2).unique_id());
 }
+
+#[test]
+fn atom_reads_and_writes() {
+    static TEST_ATOM: Atom<u32> = Atom(|_| 0);
+    let root = AtomRoot::new(std::sync::Arc::new(|_| {}));
+
+    assert_eq!((&TEST_ATOM).read(&root), None);
+    assert_eq!(*root.read(&TEST_ATOM), 0);
+
+    (&TEST_ATOM).write(&root, 10);
+    assert_eq!((&TEST_ATOM).read(&root), Some(Rc::new(10)));
+}
@@ -1,12 +1,13 @@
 use crate::{AtomId, AtomRoot, Readable, Writable};
 use im_rc::HashMap as ImMap;
+use std::rc::Rc;
 
 pub struct AtomFamilyBuilder;
 pub struct AtomFamily<K, V>(pub fn(AtomFamilyBuilder) -> ImMap<K, V>);
 
-impl<K, V> Readable<ImMap<K, V>> for &'static AtomFamily<K, V> {
-    fn read(&self, _root: AtomRoot) -> Option<ImMap<K, V>> {
-        todo!()
+impl<K: 'static, V: 'static> Readable<ImMap<K, V>> for &'static AtomFamily<K, V> {
+    fn read(&self, root: &AtomRoot) -> Option<Rc<ImMap<K, V>>> {
+        root.get(self.unique_id())
     }
 
     fn init(&self) -> ImMap<K, V> {
@@ -18,8 +19,8 @@ impl<K, V> Readable<ImMap<K, V>> for &'static AtomFamily<K, V> {
     }
 }
 
-impl<K, V> Writable<ImMap<K, V>> for &'static AtomFamily<K, V> {
-    fn write(&self, _root: AtomRoot, _value: ImMap<K, V>) {
-        todo!()
+impl<K: 'static, V: 'static> Writable<ImMap<K, V>> for &'static AtomFamily<K, V> {
+    fn write(&self, root: &AtomRoot, value: ImMap<K, V>) {
+        root.set(self.unique_id(), value)
     }
 }
@@ -1,12 +1,12 @@
 use crate::{AtomId, AtomRoot, Readable};
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc};
 
 pub struct AtomRefBuilder;
 pub struct AtomRef<T>(pub fn(AtomRefBuilder) -> T);
 
-impl<V> Readable<RefCell<V>> for &'static AtomRef<V> {
-    fn read(&self, _root: AtomRoot) -> Option<RefCell<V>> {
-        todo!()
+impl<V: 'static> Readable<RefCell<V>> for &'static AtomRef<V> {
+    fn read(&self, root: &AtomRoot) -> Option<Rc<RefCell<V>>> {
+        root.get(self.unique_id())
     }
 
     fn init(&self) -> RefCell<V> {
@@ -1 +1,219 @@
+use crate::{AtomId, AtomRoot, Readable};
+use std::{future::Future, pin::Pin, rc::Rc, sync::Arc};
 
+/// A value derived from other atoms
+///
+/// Selectors remember every atom they read. They are recomputed when one of those atoms changes, and only re-render
+/// their subscribers if the derived value is different.
+///
+/// ```rust, ignore
+/// static COUNT: Atom<u32> = Atom(|_| 0);
+/// static DOUBLED: Selector<u32> = Selector(|s| s.get(&COUNT) * 2);
+///
+/// fn Doubled(cx: Scope) -> Element {
+///     let doubled = use_read(cx, &DOUBLED);
+///     cx.render(rsx! { "{doubled}" })
+/// }
+/// ```
+pub struct Selector<T>(pub fn(SelectorBuilder) -> T);
+
+/// Reads atoms inside of a selector, tracking them as dependencies of the selector
+pub struct SelectorBuilder<'a> {
+    root: &'a AtomRoot,
+}
+
+impl<'a> SelectorBuilder<'a> {
+    pub(crate) fn new(root: &'a AtomRoot) -> Self {
+        Self { root }
+    }
+
+    /// Get the current value of an atom or selector
+    pub fn get<V: Clone + 'static>(&self, atom: impl Readable<V>) -> V {
+        self.get_rc(atom).as_ref().clone()
+    }
+
+    /// Get the current value of an atom or selector in its RC container
+    pub fn get_rc<V: 'static>(&self, atom: impl Readable<V>) -> Rc<V> {
+        self.root.track(atom.unique_id());
+        self.root.read(atom)
+    }
+}
+
+impl<V: PartialEq + 'static> Readable<V> for &'static Selector<V> {
+    fn read(&self, root: &AtomRoot) -> Option<Rc<V>> {
+        root.get(self.unique_id())
+    }
+
+    /// Compute the selector from the initial values of the atoms it reads
+    fn init(&self) -> V {
+        (self.0)(SelectorBuilder::new(&AtomRoot::new(Arc::new(|_| {}))))
+    }
+
+    fn init_in(&self, root: &AtomRoot) -> V {
+        let selector: &'static Selector<V> = *self;
+        root.init_selector(
+            self.unique_id(),
+            Rc::new(move |root: &AtomRoot| (selector.0)(SelectorBuilder::new(root))),
+        )
+    }
+
+    fn unique_id(&self) -> AtomId {
+        *self as *const Selector<V> as *const ()
+    }
+}
+
+/// The value of an async selector
+#[derive(Debug, PartialEq)]
+pub enum Loadable<T> {
+    /// The first future of the selector has not resolved yet
+    Loading,
+    /// The value of the latest future that resolved
+    Ready(Rc<T>),
+}
+
+impl<T> Clone for Loadable<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Loading => Self::Loading,
+            Self::Ready(value) => Self::Ready(value.clone()),
+        }
+    }
+}
+
+impl<T> Loadable<T> {
+    /// Get the value if it is ready
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            Self::Loading => None,
+            Self::Ready(value) => Some(value),
+        }
+    }
+}
+
+/// A value derived from other atoms with a future
+///
+/// Only the atoms read before the future is created are tracked as dependencies. When one of them changes, a new
+/// future is started and the previous value is kept until it resolves. Read async selectors with
+/// [`crate::use_read_async`] to suspend the component until the first value is ready.
+///
+/// ```rust, ignore
+/// static USER_ID: Atom<u32> = Atom(|_| 0);
+/// static USER: AsyncSelector<String> = AsyncSelector(|s| {
+///     let id = s.get(&USER_ID);
+///     Box::pin(async move { fetch_user(id).await })
+/// });
+/// ```
+pub struct AsyncSelector<T>(pub fn(SelectorBuilder) -> Pin<Box<dyn Future<Output = T>>>);
+
+impl<T: PartialEq + 'static> Readable<Loadable<T>> for &'static AsyncSelector<T> {
+    fn read(&self, root: &AtomRoot) -> Option<Rc<Loadable<T>>> {
+        root.get(self.unique_id())
+    }
+
+    fn init(&self) -> Loadable<T> {
+        Loadable::Loading
+    }
+
+    fn init_in(&self, root: &AtomRoot) -> Loadable<T> {
+        let selector: &'static AsyncSelector<T> = *self;
+        root.init_async_selector(
+            self.unique_id(),
+            Rc::new(move |root: &AtomRoot| (selector.0)(SelectorBuilder::new(root))),
+        )
+    }
+
+    fn unique_id(&self) -> AtomId {
+        *self as *const AsyncSelector<T> as *const ()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Atom;
+    use dioxus_core::ScopeId;
+    use futures_util::FutureExt;
+    use std::cell::{Cell, RefCell};
+
+    fn root() -> (Rc<AtomRoot>, Rc<RefCell<Vec<ScopeId>>>) {
+        let updates = Rc::new(RefCell::new(Vec::new()));
+        let root = AtomRoot::new(Arc::new({
+            let updates = updates.clone();
+            move |scope| updates.borrow_mut().push(scope)
+        }));
+        (Rc::new(root), updates)
+    }
+
+    #[test]
+    fn selector_is_memoized() {
+        thread_local! {
+            static RUNS: Cell<usize> = Cell::new(0);
+        }
+        static COUNT: Atom<u32> = Atom(|_| 1);
+        static DOUBLED: Selector<u32> = Selector(|s| {
+            RUNS.with(|runs| runs.set(runs.get() + 1));
+            s.get(&COUNT) * 2
+        });
+
+        let (root, _) = root();
+        assert_eq!(*root.read(&DOUBLED), 2);
+        assert_eq!(*root.read(&DOUBLED), 2);
+        assert_eq!(RUNS.with(Cell::get), 1);
+
+        root.set((&COUNT).unique_id(), 5);
+        assert_eq!(*root.read(&DOUBLED), 10);
+        assert_eq!(RUNS.with(Cell::get), 2);
+    }
+
+    #[test]
+    fn subscribers_only_update_when_the_value_changes() {
+        static COUNT: Atom<u32> = Atom(|_| 0);
+        static IS_BIG: Selector<bool> = Selector(|s| s.get(&COUNT) > 10);
+
+        let (root, updates) = root();
+        assert!(!*root.register(&IS_BIG, ScopeId(1)));
+
+        root.set((&COUNT).unique_id(), 5);
+        assert!(updates.borrow().is_empty());
+
+        root.set((&COUNT).unique_id(), 20);
+        assert_eq!(*updates.borrow(), [ScopeId(1)]);
+        assert!(*root.read(&IS_BIG));
+    }
+
+    #[test]
+    fn selectors_track_selectors() {
+        static COUNT: Atom<u32> = Atom(|_| 1);
+        static DOUBLED: Selector<u32> = Selector(|s| s.get(&COUNT) * 2);
+        static QUADRUPLED: Selector<u32> = Selector(|s| s.get(&DOUBLED) * 2);
+
+        let (root, updates) = root();
+        assert_eq!(*root.register(&QUADRUPLED, ScopeId(1)), 4);
+
+        root.set((&COUNT).unique_id(), 2);
+        assert_eq!(*updates.borrow(), [ScopeId(1)]);
+        assert_eq!(*root.read(&QUADRUPLED), 8);
+    }
+
+    #[test]
+    fn async_selector_resolves() {
+        static COUNT: Atom<u32> = Atom(|_| 1);
+        static DOUBLED: AsyncSelector<u32> = AsyncSelector(|s| {
+            let count = s.get(&COUNT);
+            Box::pin(async move { count * 2 })
+        });
+
+        let (root, updates) = root();
+        assert_eq!(*root.register(&DOUBLED, ScopeId(1)), Loadable::Loading);
+
+        let _ = root.clone().run_selector_tasks().now_or_never();
+        assert_eq!(*root.read(&DOUBLED), Loadable::Ready(Rc::new(2)));
+        assert_eq!(*updates.borrow(), [ScopeId(1)]);
+
+        // The previous value is kept while the next future runs
+        root.set((&COUNT).unique_id(), 2);
+        assert_eq!(*root.read(&DOUBLED), Loadable::Ready(Rc::new(2)));
+        let _ = root.clone().run_selector_tasks().now_or_never();
+        assert_eq!(*root.read(&DOUBLED), Loadable::Ready(Rc::new(4)));
+    }
+}
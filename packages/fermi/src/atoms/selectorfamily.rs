@@ -1 +1,254 @@
+use crate::{AtomId, AtomRoot, Readable, SelectorBuilder};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+    rc::Rc,
+    sync::Arc,
+};
 
+/// A selector that derives a different value for every key
+///
+/// A member is removed from the root once the last component that reads it is dropped, and computed again the next
+/// time it is read.
+///
+/// ```rust, ignore
+/// static TODOS: Atom<Vec<String>> = Atom(|_| Vec::new());
+/// static TODO: SelectorFamily<usize, Option<String>> =
+///     SelectorFamily(|s, idx| s.get(&TODOS).get(*idx).cloned());
+///
+/// fn Todo(cx: Scope<TodoProps>) -> Element {
+///     let todo = use_read(cx, TODO.select(cx.props.idx));
+///     ...
+/// }
+/// ```
+pub struct SelectorFamily<K, V>(pub fn(SelectorBuilder, &K) -> V);
+
+impl<K: Hash + Eq + Clone + 'static, V> SelectorFamily<K, V> {
+    /// Select the member of the family for a key
+    pub fn select(&'static self, key: K) -> SelectorFamilyMember<K, V> {
+        let id = member_id(self.family_id(), &key);
+        SelectorFamilyMember {
+            family: self,
+            key,
+            id,
+        }
+    }
+
+    fn family_id(&'static self) -> usize {
+        self as *const Self as usize
+    }
+}
+
+/// The selector for a single key of a [`SelectorFamily`]
+pub struct SelectorFamilyMember<K: 'static, V: 'static> {
+    family: &'static SelectorFamily<K, V>,
+    key: K,
+    // The address of this allocation is the id of the member. Members that outlive the entry in `MEMBER_IDS` keep it
+    // alive, so the id is never reused for another key while it can still be read.
+    id: Rc<u8>,
+}
+
+impl<K: Clone, V> Clone for SelectorFamilyMember<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            family: self.family,
+            key: self.key.clone(),
+            id: self.id.clone(),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: PartialEq + 'static> Readable<V>
+    for SelectorFamilyMember<K, V>
+{
+    fn read(&self, root: &AtomRoot) -> Option<Rc<V>> {
+        root.get(self.unique_id())
+    }
+
+    fn init(&self) -> V {
+        (self.family.0)(
+            SelectorBuilder::new(&AtomRoot::new(Arc::new(|_| {}))),
+            &self.key,
+        )
+    }
+
+    fn init_in(&self, root: &AtomRoot) -> V {
+        let family = self.family;
+        let key = self.key.clone();
+        let value = root.init_selector(
+            self.unique_id(),
+            Rc::new(move |root: &AtomRoot| (family.0)(SelectorBuilder::new(root), &key)),
+        );
+
+        let (family_id, key, id) = (family.family_id(), self.key.clone(), self.id.clone());
+        retain_member(family_id, &key, &id);
+        root.collect_when_unused(
+            self.unique_id(),
+            Box::new(move || release_member(family_id, &key, &id)),
+        );
+
+        value
+    }
+
+    fn unique_id(&self) -> AtomId {
+        Rc::as_ptr(&self.id) as AtomId
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: PartialEq + 'static> Readable<V>
+    for &SelectorFamilyMember<K, V>
+{
+    fn read(&self, root: &AtomRoot) -> Option<Rc<V>> {
+        (*self).read(root)
+    }
+
+    fn init(&self) -> V {
+        (*self).init()
+    }
+
+    fn init_in(&self, root: &AtomRoot) -> V {
+        (*self).init_in(root)
+    }
+
+    fn unique_id(&self) -> AtomId {
+        (*self).unique_id()
+    }
+}
+
+/// The id of a member and the number of roots it is initialized in
+struct MemberId {
+    id: Rc<u8>,
+    roots: usize,
+}
+
+thread_local! {
+    // Every key of every family that is initialized in a root gets a small allocation whose address is used as its
+    // id. The entry is removed once no root uses the member anymore.
+    static MEMBER_IDS: RefCell<HashMap<usize, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+fn with_members<K: Hash + Eq + 'static, O>(
+    family: usize,
+    f: impl FnOnce(&mut HashMap<K, MemberId>) -> O,
+) -> O {
+    MEMBER_IDS.with(|ids| {
+        let mut ids = ids.borrow_mut();
+        let members = ids
+            .entry(family)
+            .or_insert_with(|| Box::new(HashMap::<K, MemberId>::new()))
+            .downcast_mut::<HashMap<K, MemberId>>()
+            .unwrap();
+        f(members)
+    })
+}
+
+/// The id of the member for a key. Members that are not initialized in any root get a fresh id that is only
+/// registered once a root initializes the member.
+fn member_id<K: Hash + Eq + 'static>(family: usize, key: &K) -> Rc<u8> {
+    with_members(family, |members: &mut HashMap<K, MemberId>| {
+        match members.get(key) {
+            Some(member) => member.id.clone(),
+            None => Rc::new(0),
+        }
+    })
+}
+
+/// Count a root that initialized the member
+fn retain_member<K: Hash + Eq + Clone + 'static>(family: usize, key: &K, id: &Rc<u8>) {
+    with_members(family, |members: &mut HashMap<K, MemberId>| {
+        match members.entry(key.clone()) {
+            Entry::Occupied(mut member) if Rc::ptr_eq(&member.get().id, id) => {
+                member.get_mut().roots += 1
+            }
+            // A newer id was handed out for the key, this member is only kept alive by the values that still hold it
+            Entry::Occupied(_) => {}
+            Entry::Vacant(member) => {
+                member.insert(MemberId {
+                    id: id.clone(),
+                    roots: 1,
+                });
+            }
+        }
+    })
+}
+
+/// Forget a root that removed the member, removing its id once no root uses it
+fn release_member<K: Hash + Eq + 'static>(family: usize, key: &K, id: &Rc<u8>) {
+    // The ids may already be gone if the root is dropped while the thread is shutting down
+    let _ = MEMBER_IDS.try_with(|_| {
+        with_members(family, |members: &mut HashMap<K, MemberId>| {
+            if let Some(member) = members.get_mut(key) {
+                if Rc::ptr_eq(&member.id, id) {
+                    member.roots -= 1;
+                    if member.roots == 0 {
+                        members.remove(key);
+                    }
+                }
+            }
+        })
+    });
+}
+
+#[cfg(test)]
+fn member_count<K: Hash + Eq + 'static>(family: usize) -> usize {
+    with_members(family, |members: &mut HashMap<K, MemberId>| members.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Atom;
+    use dioxus_core::ScopeId;
+
+    #[test]
+    fn members_are_independent() {
+        static TODOS: Atom<Vec<&str>> = Atom(|_| vec!["a", "b"]);
+        static TODO: SelectorFamily<usize, Option<&str>> =
+            SelectorFamily(|s, idx| s.get(&TODOS).get(*idx).copied());
+
+        let root = AtomRoot::new(Arc::new(|_| {}));
+        assert_eq!(*root.read(TODO.select(0)), Some("a"));
+        assert_eq!(TODO.select(0).unique_id(), TODO.select(0).unique_id());
+        assert_ne!(TODO.select(0).unique_id(), TODO.select(1).unique_id());
+
+        assert_eq!(*root.read(TODO.select(1)), Some("b"));
+        assert_eq!(*root.read(TODO.select(2)), None);
+
+        root.set((&TODOS).unique_id(), vec!["a", "b", "c"]);
+        assert_eq!(*root.read(TODO.select(2)), Some("c"));
+    }
+
+    #[test]
+    fn unused_members_are_removed() {
+        static TODOS: Atom<Vec<&str>> = Atom(|_| vec!["a", "b"]);
+        static TODO: SelectorFamily<usize, Option<&str>> =
+            SelectorFamily(|s, idx| s.get(&TODOS).get(*idx).copied());
+
+        let root = AtomRoot::new(Arc::new(|_| {}));
+        let scope = ScopeId(1);
+        let family = TODO.family_id();
+
+        // Selecting a key that is never read doesn't register it
+        drop(TODO.select(1));
+        assert_eq!(member_count::<usize>(family), 0);
+
+        let first = TODO.select(0);
+        assert_eq!(*root.register(&first, scope), Some("a"));
+        let id = first.unique_id();
+        drop(first);
+        assert_eq!(member_count::<usize>(family), 1);
+
+        // Selecting the key again while it is subscribed returns the same member
+        assert_eq!(TODO.select(0).unique_id(), id);
+
+        root.unsubscribe(id, scope);
+        assert!(root.get::<Option<&str>>(id).is_none());
+        assert_eq!(member_count::<usize>(family), 0);
+
+        // The member is computed again the next time it is read
+        root.set((&TODOS).unique_id(), vec!["c"]);
+        assert_eq!(*root.register(TODO.select(0), scope), Some("c"));
+    }
+}
@@ -1,11 +1,28 @@
-#![allow(clippy::all, unused)]
-
 use std::rc::Rc;
 
 use dioxus_core::prelude::*;
 
-use crate::{AtomRoot, Readable, Writable};
-
+use crate::{use_atom_root, AtomRoot, Readable, Writable};
+
+/// A handle to the atom root that reads and writes atoms without subscribing to them
+///
+/// This is useful in event handlers and async tasks that need the latest value of an atom, but shouldn't make the
+/// component re-render when it changes.
+///
+/// ```rust, ignore
+/// static COUNT: Atom<u32> = Atom(|_| 0);
+///
+/// fn Example(cx: Scope) -> Element {
+///     let atoms = use_atom_context(cx);
+///
+///     cx.render(rsx! {
+///         button {
+///             onclick: move |_| atoms.set(&COUNT, atoms.get(&COUNT) + 1),
+///             "Increment"
+///         }
+///     })
+/// }
+/// ```
 #[derive(Clone)]
 pub struct CallbackApi {
     root: Rc<AtomRoot>,
@@ -13,41 +30,23 @@ pub struct CallbackApi {
 
 impl CallbackApi {
     // get the current value of the atom
-    pub fn get<V>(&self, atom: impl Readable<V>) -> &V {
-        todo!()
+    pub fn get<V: Clone + 'static>(&self, atom: impl Readable<V>) -> V {
+        self.get_rc(atom).as_ref().clone()
     }
 
     // get the current value of the atom in its RC container
-    pub fn get_rc<V>(&self, atom: impl Readable<V>) -> &Rc<V> {
-        todo!()
+    pub fn get_rc<V: 'static>(&self, atom: impl Readable<V>) -> Rc<V> {
+        self.root.read(atom)
     }
 
     // set the current value of the atom
-    pub fn set<V>(&self, atom: impl Writable<V>, value: V) {
-        todo!()
+    pub fn set<V: 'static>(&self, atom: impl Writable<V>, value: V) {
+        atom.write(&self.root, value);
     }
 }
 
+/// Get a [`CallbackApi`] to read and write atoms without subscribing the component to them
 pub fn use_atom_context(cx: &ScopeState) -> &CallbackApi {
-    todo!()
-}
-
-macro_rules! use_callback {
-    (&$cx:ident, [$($cap:ident),*],  move || $body:expr) => {
-        move || {
-            $(
-                #[allow(unused_mut)]
-                let mut $cap = $cap.to_owned();
-            )*
-            $cx.spawn($body);
-        }
-    };
-}
-
-#[macro_export]
-macro_rules! to_owned {
-    ($($es:ident),+) => {$(
-        #[allow(unused_mut)]
-        let mut $es = $es.to_owned();
-    )*}
+    let root = use_atom_root(cx);
+    cx.use_hook(|| CallbackApi { root: root.clone() })
 }
@@ -6,6 +6,11 @@ use std::rc::Rc;
 pub fn use_init_atom_root(cx: &ScopeState) -> &Rc<AtomRoot> {
     cx.use_hook(|| match cx.consume_context::<Rc<AtomRoot>>() {
         Some(ctx) => ctx,
-        None => cx.provide_context(Rc::new(AtomRoot::new(cx.schedule_update_any()))),
+        None => {
+            let root = cx.provide_context(Rc::new(AtomRoot::new(cx.schedule_update_any())));
+            // async selectors run their futures on the component that owns the root
+            cx.push_future(root.clone().run_selector_tasks());
+            root
+        }
     })
 }
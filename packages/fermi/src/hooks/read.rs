@@ -1,4 +1,4 @@
-use crate::{use_atom_root, AtomId, AtomRoot, Readable};
+use crate::{use_atom_root, AtomId, AtomRoot, Loadable, Readable};
 use dioxus_core::{ScopeId, ScopeState};
 use std::rc::Rc;
 
@@ -34,3 +34,26 @@ pub fn use_read_rc<V: 'static>(cx: &ScopeState, f: impl Readable<V>) -> &Rc<V> {
     inner.value = Some(value);
     inner.value.as_ref().unwrap()
 }
+
+/// Read the value of an async selector, suspending the component until its first value is ready
+///
+/// ```rust, ignore
+/// static USER: AsyncSelector<String> = AsyncSelector(|s| {
+///     let id = s.get(&USER_ID);
+///     Box::pin(async move { fetch_user(id).await })
+/// });
+///
+/// fn Profile(cx: Scope) -> Element {
+///     let user = use_read_async(cx, &USER)?;
+///     cx.render(rsx! { "{user}" })
+/// }
+/// ```
+pub fn use_read_async<T: 'static>(cx: &ScopeState, f: impl Readable<Loadable<T>>) -> Option<&T> {
+    match use_read(cx, f) {
+        Loadable::Ready(value) => Some(value),
+        Loadable::Loading => {
+            cx.suspend();
+            None
+        }
+    }
+}
@@ -230,35 +230,42 @@ impl<T: Clone> AtomState<T> {
         self.set(new_val);
     }
 
-    /// Get a mutable handle to the value by calling `ToOwned::to_owned` on the
-    /// current value.
+    /// Get a mutable handle to the value stored in the atom root and mark all
+    /// consumers of this atom to re-render.
     ///
-    /// This is essentially cloning the underlying value and then setting it,
-    /// giving you a mutable handle in the process. This method is intended for
-    /// types that are cheaply cloneable.
+    /// The value is updated in place. It is only cloned if another handle is
+    /// still holding on to the current value, like the `AtomState` of a
+    /// component that has not re-rendered yet.
     ///
     /// # Warning
-    /// Be careful with `RefMut` since you might panic if the `RefCell` is left open!
+    /// Every atom in the root is locked while the `RefMut` is alive. Reading
+    /// or writing any atom before dropping it will panic!
     ///
     /// # Examples
     ///
     /// ```ignore
-    /// let val = use_state(cx, || 0);
+    /// let val = use_atom_state(cx, &COUNT);
     ///
     /// *val.make_mut() += 1;
     /// ```
     #[must_use]
     pub fn make_mut(&self) -> RefMut<T> {
-        todo!("make mut not support for atom values yet")
-        // let mut slot = self.value.as_ref().unwrap();
-
-        // self.needs_update();
-
-        // if Rc::strong_count(&*slot) > 0 {
-        //     *slot = Rc::new(slot.as_ref().to_owned());
-        // }
-
-        // RefMut::map(slot, |rc| Rc::get_mut(rc).expect("the hard count to be 0"))
+        self.needs_update();
+
+        RefMut::map(self.root.atoms.borrow_mut(), |atoms| {
+            let slot = atoms.get_mut(&self.id).expect("the atom to be initialized");
+
+            // Only clone the value if someone else is holding on to it
+            if Rc::get_mut(&mut slot.value).is_none() {
+                let value: &T = slot.value.downcast_ref().unwrap();
+                let value = Rc::new(value.clone());
+                slot.value = value;
+            }
+
+            Rc::get_mut(&mut slot.value)
+                .and_then(|value| value.downcast_mut())
+                .expect("the hard count to be 0")
+        })
     }
 
     /// Convert this handle to a tuple of the value and the handle itself.
@@ -6,9 +6,11 @@ pub mod prelude {
     pub use crate::*;
 }
 
+mod callback;
 mod root;
 
 pub use atoms::*;
+pub use callback::*;
 pub use hooks::*;
 pub use root::*;
 
@@ -46,9 +48,16 @@ pub mod hooks {
 /// This trait lets Dioxus abstract over Atoms, AtomFamilies, AtomRefs, and Selectors.
 /// It is not very useful for your own code, but could be used to build new Atom primitives.
 pub trait Readable<V> {
-    fn read(&self, root: AtomRoot) -> Option<V>;
+    fn read(&self, root: &AtomRoot) -> Option<std::rc::Rc<V>>;
     fn init(&self) -> V;
     fn unique_id(&self) -> AtomId;
+
+    /// Compute the value of the atom the first time it is read from a root.
+    ///
+    /// Atoms use [`Readable::init`], selectors read the atoms they depend on from the root instead.
+    fn init_in(&self, _root: &AtomRoot) -> V {
+        self.init()
+    }
 }
 
 /// All Atoms are `Writable` - they support writing their value.
@@ -56,5 +65,5 @@ pub trait Readable<V> {
 /// This trait lets Dioxus abstract over Atoms, AtomFamilies, AtomRefs, and Selectors.
 /// This trait lets Dioxus abstract over Atoms, AtomFamilies, AtomRefs, and Selectors
 pub trait Writable<V>: Readable<V> {
-    fn write(&self, root: AtomRoot, value: V);
+    fn write(&self, root: &AtomRoot, value: V);
 }
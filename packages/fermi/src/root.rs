@@ -1,15 +1,42 @@
-use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    task::{Poll, Waker},
+};
 
 use dioxus_core::ScopeId;
+use futures_util::{stream::FuturesUnordered, StreamExt};
 use im_rc::HashSet;
 
-use crate::Readable;
+use crate::{Loadable, Readable};
 
 pub type AtomId = *const ();
 
+/// A future started by an async selector. It resolves to a callback that stores the value in the root.
+type SelectorTask = Pin<Box<dyn Future<Output = Box<dyn FnOnce(&AtomRoot)>>>>;
+
+/// Recompute a selector, returning the new value if it changed
+type Recompute = Rc<dyn Fn(&AtomRoot) -> Option<Rc<dyn Any>>>;
+
 pub struct AtomRoot {
     pub atoms: RefCell<HashMap<AtomId, Slot>>,
     pub update_any: Arc<dyn Fn(ScopeId)>,
+    selectors: RefCell<HashMap<AtomId, SelectorEntry>>,
+    // The atoms and selectors each selector read the last time it was computed
+    dependencies: RefCell<HashMap<AtomId, HashSet<AtomId>>>,
+    // The selectors that read each atom or selector
+    dependents: RefCell<HashMap<AtomId, HashSet<AtomId>>>,
+    // The dependencies collected by the selectors that are currently being computed
+    tracking: RefCell<Vec<HashSet<AtomId>>>,
+    pending_tasks: RefCell<Vec<SelectorTask>>,
+    tasks_waker: RefCell<Option<Waker>>,
+    // The selectors that are removed once nothing subscribes to or depends on them, with a callback to run then
+    collectable: RefCell<HashMap<AtomId, Box<dyn FnOnce()>>>,
 }
 
 pub struct Slot {
@@ -17,21 +44,36 @@ pub struct Slot {
     pub subscribers: HashSet<ScopeId>,
 }
 
+struct SelectorEntry {
+    recompute: Recompute,
+    // Set when a dependency was changed in place, the selector is recomputed the next time it is read
+    dirty: bool,
+}
+
 impl AtomRoot {
     pub fn new(update_any: Arc<dyn Fn(ScopeId)>) -> Self {
         Self {
             update_any,
             atoms: RefCell::new(HashMap::new()),
+            selectors: Default::default(),
+            dependencies: Default::default(),
+            dependents: Default::default(),
+            tracking: Default::default(),
+            pending_tasks: Default::default(),
+            tasks_waker: Default::default(),
+            collectable: Default::default(),
         }
     }
 
     pub fn initialize<V: 'static>(&self, f: impl Readable<V>) {
         let id = f.unique_id();
         if self.atoms.borrow().get(&id).is_none() {
+            // Selectors read other atoms while they are initialized, so the atoms can't be borrowed here
+            let value = Rc::new(f.init_in(self));
             self.atoms.borrow_mut().insert(
                 id,
                 Slot {
-                    value: Rc::new(f.init()),
+                    value,
                     subscribers: HashSet::new(),
                 },
             );
@@ -39,33 +81,27 @@ impl AtomRoot {
     }
 
     pub fn register<V: 'static>(&self, f: impl Readable<V>, scope: ScopeId) -> Rc<V> {
-        let mut atoms = self.atoms.borrow_mut();
+        let id = f.unique_id();
+        let value = self.read(f);
 
-        // initialize the value if it's not already initialized
-        if let Some(slot) = atoms.get_mut(&f.unique_id()) {
+        if let Some(slot) = self.atoms.borrow_mut().get_mut(&id) {
             slot.subscribers.insert(scope);
-            slot.value.clone().downcast().unwrap()
-        } else {
-            let value = Rc::new(f.init());
-            let mut subscribers = HashSet::new();
-            subscribers.insert(scope);
-
-            atoms.insert(
-                f.unique_id(),
-                Slot {
-                    value: value.clone(),
-                    subscribers,
-                },
-            );
-            value
         }
+
+        value
     }
 
     pub fn set<V: 'static>(&self, ptr: AtomId, value: V) {
+        self.replace(ptr, Rc::new(value));
+        self.propagate(ptr);
+    }
+
+    /// Replace the value in a slot and mark its subscribers to re-render
+    fn replace(&self, ptr: AtomId, value: Rc<dyn Any>) {
         let mut atoms = self.atoms.borrow_mut();
 
         if let Some(slot) = atoms.get_mut(&ptr) {
-            slot.value = Rc::new(value);
+            slot.value = value;
             tracing::trace!("found item with subscribers {:?}", slot.subscribers);
 
             for scope in &slot.subscribers {
@@ -77,7 +113,7 @@ impl AtomRoot {
             atoms.insert(
                 ptr,
                 Slot {
-                    value: Rc::new(value),
+                    value,
                     subscribers: HashSet::new(),
                 },
             );
@@ -85,11 +121,11 @@ impl AtomRoot {
     }
 
     pub fn unsubscribe(&self, ptr: AtomId, scope: ScopeId) {
-        let mut atoms = self.atoms.borrow_mut();
-
-        if let Some(slot) = atoms.get_mut(&ptr) {
+        if let Some(slot) = self.atoms.borrow_mut().get_mut(&ptr) {
             slot.subscribers.remove(&scope);
         }
+
+        self.collect(ptr);
     }
 
     // force update of all subscribers
@@ -100,24 +136,280 @@ impl AtomRoot {
                 (self.update_any)(*scope);
             }
         }
+
+        // The value may still be changing, so selectors are only recomputed once they are read again
+        self.invalidate(ptr);
+    }
+
+    /// Get the value of an atom if it has been initialized, without subscribing to it
+    pub fn get<V: 'static>(&self, ptr: AtomId) -> Option<Rc<V>> {
+        self.refresh(ptr);
+        let atoms = self.atoms.borrow();
+        let slot = atoms.get(&ptr)?;
+        slot.value.clone().downcast().ok()
     }
 
     pub fn read<V: 'static>(&self, f: impl Readable<V>) -> Rc<V> {
-        let mut atoms = self.atoms.borrow_mut();
+        let id = f.unique_id();
+        self.refresh(id);
+
+        if let Some(slot) = self.atoms.borrow().get(&id) {
+            return slot.value.clone().downcast().unwrap();
+        }
 
         // initialize the value if it's not already initialized
-        if let Some(slot) = atoms.get_mut(&f.unique_id()) {
-            slot.value.clone().downcast().unwrap()
-        } else {
-            let value = Rc::new(f.init());
-            atoms.insert(
-                f.unique_id(),
-                Slot {
-                    value: value.clone(),
-                    subscribers: HashSet::new(),
-                },
-            );
-            value
+        let value = Rc::new(f.init_in(self));
+        self.atoms.borrow_mut().insert(
+            id,
+            Slot {
+                value: value.clone(),
+                subscribers: HashSet::new(),
+            },
+        );
+        value
+    }
+
+    /// Record that the selector that is currently being computed read this atom
+    pub(crate) fn track(&self, ptr: AtomId) {
+        if let Some(dependencies) = self.tracking.borrow_mut().last_mut() {
+            dependencies.insert(ptr);
+        }
+    }
+
+    /// Compute the first value of a selector and remember how to recompute it
+    pub(crate) fn init_selector<V: PartialEq + 'static>(
+        &self,
+        ptr: AtomId,
+        compute: Rc<dyn Fn(&AtomRoot) -> V>,
+    ) -> V {
+        let value = self.compute_tracked(ptr, &*compute);
+
+        let recompute: Recompute = Rc::new(move |root: &AtomRoot| {
+            let new = root.compute_tracked(ptr, &*compute);
+            match root.get_slot_value::<V>(ptr) {
+                Some(old) if *old == new => None,
+                _ => Some(Rc::new(new) as Rc<dyn Any>),
+            }
+        });
+        self.selectors.borrow_mut().insert(
+            ptr,
+            SelectorEntry {
+                recompute,
+                dirty: false,
+            },
+        );
+
+        value
+    }
+
+    /// Remove a selector once its last subscriber unsubscribes and no other selector depends on it, calling `release`
+    /// when it is removed
+    pub(crate) fn collect_when_unused(&self, ptr: AtomId, release: Box<dyn FnOnce()>) {
+        if let Some(previous) = self.collectable.borrow_mut().insert(ptr, release) {
+            previous();
+        }
+    }
+
+    /// Start the first future of an async selector and remember how to restart it
+    pub(crate) fn init_async_selector<T: PartialEq + 'static>(
+        &self,
+        ptr: AtomId,
+        compute: Rc<dyn Fn(&AtomRoot) -> Pin<Box<dyn Future<Output = T>>>>,
+    ) -> Loadable<T> {
+        // Only the value of the latest future is kept
+        let generation = Rc::new(Cell::new(0));
+
+        let start = Rc::new(move |root: &AtomRoot| {
+            let future = root.compute_tracked(ptr, &*compute);
+            generation.set(generation.get() + 1);
+            let started = generation.get();
+            let generation = generation.clone();
+
+            root.spawn(Box::pin(async move {
+                let value = future.await;
+                Box::new(move |root: &AtomRoot| {
+                    if generation.get() != started {
+                        return;
+                    }
+                    if let Some(old) = root.get_slot_value::<Loadable<T>>(ptr) {
+                        if matches!(&*old, Loadable::Ready(old) if **old == value) {
+                            return;
+                        }
+                    }
+                    root.replace(ptr, Rc::new(Loadable::Ready(Rc::new(value))));
+                    root.propagate(ptr);
+                }) as Box<dyn FnOnce(&AtomRoot)>
+            }));
+        });
+
+        start(self);
+
+        // The previous value is kept until the new future resolves
+        let recompute: Recompute = Rc::new(move |root: &AtomRoot| -> Option<Rc<dyn Any>> {
+            start(root);
+            None
+        });
+        self.selectors.borrow_mut().insert(
+            ptr,
+            SelectorEntry {
+                recompute,
+                dirty: false,
+            },
+        );
+
+        Loadable::Loading
+    }
+
+    /// Drive the futures started by async selectors. This never resolves.
+    ///
+    /// This is spawned by [`crate::use_init_atom_root`]. If you create the root yourself, you need to spawn it for
+    /// async selectors to resolve.
+    pub async fn run_selector_tasks(self: Rc<Self>) {
+        let mut running = FuturesUnordered::new();
+
+        std::future::poll_fn(|cx| {
+            *self.tasks_waker.borrow_mut() = Some(cx.waker().clone());
+
+            loop {
+                running.extend(self.pending_tasks.borrow_mut().drain(..));
+                match running.poll_next_unpin(cx) {
+                    Poll::Ready(Some(resolve)) => resolve(&self),
+                    _ => return Poll::<()>::Pending,
+                }
+            }
+        })
+        .await
+    }
+
+    fn spawn(&self, task: SelectorTask) {
+        self.pending_tasks.borrow_mut().push(task);
+        if let Some(waker) = self.tasks_waker.borrow_mut().take() {
+            waker.wake();
+        }
+    }
+
+    fn get_slot_value<V: 'static>(&self, ptr: AtomId) -> Option<Rc<V>> {
+        let atoms = self.atoms.borrow();
+        atoms.get(&ptr)?.value.clone().downcast().ok()
+    }
+
+    /// Run a selector, collecting every atom it reads as its new dependencies
+    fn compute_tracked<V>(&self, ptr: AtomId, compute: &dyn Fn(&AtomRoot) -> V) -> V {
+        self.tracking.borrow_mut().push(HashSet::new());
+        let value = compute(self);
+        let new = self.tracking.borrow_mut().pop().unwrap();
+
+        let old = self
+            .dependencies
+            .borrow_mut()
+            .insert(ptr, new.clone())
+            .unwrap_or_default();
+
+        let mut dependents = self.dependents.borrow_mut();
+        for dependency in old.iter().filter(|dependency| !new.contains(dependency)) {
+            if let Some(dependents) = dependents.get_mut(dependency) {
+                dependents.remove(&ptr);
+            }
+        }
+        for dependency in new {
+            dependents.entry(dependency).or_default().insert(ptr);
+        }
+
+        value
+    }
+
+    fn dependents_of(&self, ptr: AtomId) -> HashSet<AtomId> {
+        self.dependents
+            .borrow()
+            .get(&ptr)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Recompute every selector that depends on an atom that was set, re-rendering the subscribers of the selectors
+    /// whose value changed
+    fn propagate(&self, ptr: AtomId) {
+        for selector in self.dependents_of(ptr) {
+            let recompute = match self.selectors.borrow_mut().get_mut(&selector) {
+                Some(entry) => {
+                    entry.dirty = false;
+                    entry.recompute.clone()
+                }
+                None => continue,
+            };
+
+            if let Some(value) = recompute(self) {
+                self.replace(selector, value);
+                self.propagate(selector);
+            }
+        }
+    }
+
+    /// Mark every selector that depends on an atom as dirty and re-render their subscribers
+    fn invalidate(&self, ptr: AtomId) {
+        for selector in self.dependents_of(ptr) {
+            match self.selectors.borrow_mut().get_mut(&selector) {
+                Some(entry) if !entry.dirty => entry.dirty = true,
+                _ => continue,
+            }
+
+            self.force_update(selector);
+        }
+    }
+
+    /// Remove a collectable selector if nothing subscribes to it or depends on it anymore
+    fn collect(&self, ptr: AtomId) {
+        if !self.collectable.borrow().contains_key(&ptr) {
+            return;
+        }
+        let subscribed = self
+            .atoms
+            .borrow()
+            .get(&ptr)
+            .map_or(false, |slot| !slot.subscribers.is_empty());
+        if subscribed || !self.dependents_of(ptr).is_empty() {
+            return;
+        }
+
+        self.atoms.borrow_mut().remove(&ptr);
+        self.selectors.borrow_mut().remove(&ptr);
+        self.dependents.borrow_mut().remove(&ptr);
+        let dependencies = self
+            .dependencies
+            .borrow_mut()
+            .remove(&ptr)
+            .unwrap_or_default();
+        for dependency in dependencies.iter() {
+            if let Some(dependents) = self.dependents.borrow_mut().get_mut(dependency) {
+                dependents.remove(&ptr);
+            }
+        }
+
+        let release = self.collectable.borrow_mut().remove(&ptr);
+        if let Some(release) = release {
+            release();
+        }
+
+        // The selectors this selector read may have been kept alive only by it
+        for dependency in dependencies {
+            self.collect(dependency);
+        }
+    }
+
+    /// Recompute a selector if one of its dependencies was changed in place since it was last read
+    fn refresh(&self, ptr: AtomId) {
+        let recompute = match self.selectors.borrow_mut().get_mut(&ptr) {
+            Some(entry) if entry.dirty => {
+                entry.dirty = false;
+                entry.recompute.clone()
+            }
+            _ => return,
+        };
+
+        if let Some(value) = recompute(self) {
+            if let Some(slot) = self.atoms.borrow_mut().get_mut(&ptr) {
+                slot.value = value;
+            }
         }
     }
 }
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "ssr")]
 #[tokio::main]
 async fn main() {
-    pre_cache_static_routes_with_props(
+    let report = export_static_site_with_props(
         &ServeConfigBuilder::new_with_router(dioxus_fullstack::router::FullstackRouterConfig::<
             Route,
         >::default())
@@ -23,8 +23,11 @@ async fn main() {
         .incremental(IncrementalRendererConfig::default().static_dir("docs"))
         .build(),
     )
-    .await
-    .unwrap();
+    .await;
+
+    for (route, err) in &report.failed {
+        eprintln!("Failed to render {route}: {err}");
+    }
 }
 
 // Hydrate the page
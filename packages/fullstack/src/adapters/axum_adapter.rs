@@ -379,6 +379,25 @@ pub async fn render_handler<P: Clone + serde::Serialize + Send + Sync + 'static>
     let parts: Arc<RwLock<http::request::Parts>> = Arc::new(RwLock::new(parts.into()));
     let server_context = DioxusServerContext::new(parts.clone());
 
+    if cfg.streaming {
+        return match ssr_state.render_streaming(&cfg, &server_context).await {
+            Ok(rendered) => {
+                let mut response = Response::builder()
+                    .header("Content-Type", "text/html; charset=utf-8")
+                    .body(body::boxed(body::StreamBody::new(rendered.into_stream())))
+                    .unwrap();
+                // Only headers set while the shell rendered are sent, suspended components resolve after the response started
+                let headers = server_context.response_parts().unwrap().headers.clone();
+                apply_request_parts_to_response(headers, &mut response);
+                response
+            }
+            Err(e) => {
+                tracing::error!("Failed to render page: {}", e);
+                report_err(e).into_response()
+            }
+        };
+    }
+
     match ssr_state.render(url, &cfg, &server_context).await {
         Ok(rendered) => {
             let crate::render::RenderResponse { html, freshness } = rendered;
@@ -409,6 +409,26 @@ impl<P: Clone + serde::Serialize + Send + Sync + 'static> Handler for SSRHandler
         let parts: Arc<RwLock<http::request::Parts>> = Arc::new(RwLock::new(extract_parts(req)));
         let server_context = DioxusServerContext::new(parts);
 
+        if self.cfg.streaming {
+            match renderer_pool
+                .render_streaming(&self.cfg, &server_context)
+                .await
+            {
+                Ok(rendered) => {
+                    res.streaming(rendered.into_stream()).unwrap();
+
+                    // Only headers set while the shell rendered are sent, suspended components resolve after the response started
+                    let headers = server_context.response_parts().unwrap().headers.clone();
+                    apply_request_parts_to_response(headers, res);
+                }
+                Err(err) => {
+                    tracing::error!("Error rendering SSR: {}", err);
+                    res.write_body("Error rendering SSR").unwrap();
+                }
+            };
+            return;
+        }
+
         match renderer_pool
             .render(route, &self.cfg, &server_context)
             .await
@@ -213,13 +213,40 @@ pub fn render_ssr<P: Clone + serde::Serialize + Send + Sync + 'static>(
             async move {
                 let server_context = DioxusServerContext::new(parts);
 
+                if cfg.streaming {
+                    return match renderer.render_streaming(&cfg, &server_context).await {
+                        Ok(rendered) => {
+                            let mut res = Response::builder()
+                                .header("Content-Type", "text/html")
+                                .body(warp::hyper::Body::wrap_stream(rendered.into_stream()))
+                                .unwrap();
+
+                            // Only headers set while the shell rendered are sent, suspended components resolve after the response started
+                            let headers_mut = res.headers_mut();
+                            let headers = server_context.response_parts().unwrap().headers.clone();
+                            for (key, value) in headers.iter() {
+                                headers_mut.insert(key, value.clone());
+                            }
+
+                            res
+                        }
+                        Err(err) => {
+                            tracing::error!("Failed to render ssr: {}", err);
+                            Response::builder()
+                                .status(500)
+                                .body("Failed to render ssr".into())
+                                .unwrap()
+                        }
+                    };
+                }
+
                 match renderer.render(route, &cfg, &server_context).await {
                     Ok(rendered) => {
                         let crate::render::RenderResponse { html, freshness } = rendered;
 
                         let mut res = Response::builder()
                             .header("Content-Type", "text/html")
-                            .body(html)
+                            .body(warp::hyper::Body::from(html))
                             .unwrap();
 
                         let headers_mut = res.headers_mut();
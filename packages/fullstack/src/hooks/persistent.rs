@@ -0,0 +1,329 @@
+use dioxus::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::cell::{Ref, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A value that is saved under a key and restored the next time the app starts.
+///
+/// - On the web, the value is stored in `localStorage`.
+/// - On desktop, the value is stored in a file in the directory set with `dioxus_desktop::Config::with_data_directory`.
+///   If no data directory is set, the value is only kept in memory.
+/// - On the server, there is no storage. The server always renders the initial value.
+///
+/// When the client is hydrated from server rendered HTML, the first render uses the initial value so it matches the
+/// HTML from the server. The stored value is loaded right after hydration and the component rerenders with it.
+///
+/// The value is stored as JSON, so it must stay compatible with the type it was saved as. If the stored value can't be
+/// deserialized as the current type, the initial value is used instead.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_fullstack::prelude::*;
+///
+/// fn app(cx: Scope) -> Element {
+///     let count = use_persistent(cx, "count", || 0);
+///     let clicks = count.get();
+///
+///     render! {
+///         button {
+///             onclick: move |_| count.modify(|count| *count += 1),
+///             "Clicked {clicks} times"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_persistent<T: Serialize + DeserializeOwned + 'static>(
+    cx: &ScopeState,
+    key: impl ToString,
+    init: impl FnOnce() -> T,
+) -> &UsePersistent<T> {
+    cx.use_hook(|| {
+        // The first render of a hydrated page needs to match the server, so the stored value is loaded after hydration
+        let hydrating = cfg!(all(target_arch = "wasm32", feature = "web"));
+        init_persistent(
+            cx,
+            PersistentStorage::new(cx, key.to_string()),
+            init,
+            hydrating,
+        )
+    })
+}
+
+fn init_persistent<T: DeserializeOwned + 'static>(
+    cx: &ScopeState,
+    storage: PersistentStorage,
+    init: impl FnOnce() -> T,
+    hydrating: bool,
+) -> UsePersistent<T> {
+    let update = cx.schedule_update();
+
+    let stored = if hydrating { None } else { storage.load() };
+    let value = Rc::new(RefCell::new(stored.unwrap_or_else(init)));
+
+    if hydrating {
+        let storage = storage.clone();
+        let value = value.clone();
+        let update = update.clone();
+        cx.spawn(async move {
+            if let Some(stored) = storage.load() {
+                *value.borrow_mut() = stored;
+                update();
+            }
+        });
+    }
+
+    UsePersistent {
+        storage,
+        value,
+        update,
+    }
+}
+
+/// A value created by [`use_persistent`]. See its documentation for more details.
+pub struct UsePersistent<T> {
+    storage: PersistentStorage,
+    value: Rc<RefCell<T>>,
+    update: Arc<dyn Fn()>,
+}
+
+impl<T> Clone for UsePersistent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+            value: self.value.clone(),
+            update: self.update.clone(),
+        }
+    }
+}
+
+impl<T: Serialize> UsePersistent<T> {
+    /// Read the current value
+    pub fn read(&self) -> Ref<'_, T> {
+        self.value.borrow()
+    }
+
+    /// Set the value, save it and rerender the component
+    pub fn set(&self, value: T) {
+        *self.value.borrow_mut() = value;
+        self.save();
+    }
+
+    /// Modify the value in place, save it and rerender the component
+    pub fn modify(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.value.borrow_mut());
+        self.save();
+    }
+
+    /// Remove the value from the storage. The current value is kept until the app restarts.
+    pub fn clear(&self) {
+        self.storage.remove();
+    }
+
+    fn save(&self) {
+        self.storage.store(&*self.value.borrow());
+        (self.update)();
+    }
+}
+
+impl<T: Clone> UsePersistent<T> {
+    /// Get a clone of the current value
+    pub fn get(&self) -> T {
+        self.value.borrow().clone()
+    }
+}
+
+/// Where the values of [`use_persistent`] are stored on the current platform
+#[derive(Clone)]
+struct PersistentStorage {
+    key: String,
+    #[cfg(all(feature = "desktop", not(feature = "ssr"), not(target_arch = "wasm32")))]
+    path: Option<std::path::PathBuf>,
+}
+
+impl PersistentStorage {
+    #[allow(unused)]
+    fn new(cx: &ScopeState, key: String) -> Self {
+        Self {
+            #[cfg(all(feature = "desktop", not(feature = "ssr"), not(target_arch = "wasm32")))]
+            path: cx
+                .consume_context::<dioxus_desktop::DesktopContext>()
+                .and_then(|desktop| {
+                    use base64::Engine;
+                    let file = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&key);
+                    Some(desktop.data_directory()?.join("persistent").join(file))
+                }),
+            key,
+        }
+    }
+
+    // Values are stored as JSON instead of the compact format used for hydration because JSON describes its own
+    // shape, so a value that was saved as a different type fails to load instead of loading garbage
+    fn load<T: DeserializeOwned>(&self) -> Option<T> {
+        let serialized = self.load_raw()?;
+        match serde_json::from_str(&serialized) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                tracing::error!("Failed to deserialize {}: {}", self.key, err);
+                None
+            }
+        }
+    }
+
+    fn store<T: Serialize>(&self, value: &T) {
+        match serde_json::to_string(value) {
+            Ok(serialized) => self.store_raw(&serialized),
+            Err(err) => tracing::error!("Failed to serialize {}: {}", self.key, err),
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+    fn load_raw(&self) -> Option<String> {
+        Self::local_storage()?.get_item(&self.key).ok()?
+    }
+
+    #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+    fn store_raw(&self, value: &str) {
+        if let Some(storage) = Self::local_storage() {
+            if storage.set_item(&self.key, value).is_err() {
+                tracing::error!("Failed to save {} to local storage", self.key);
+            }
+        }
+    }
+
+    #[cfg(all(target_arch = "wasm32", not(feature = "ssr")))]
+    fn remove(&self) {
+        if let Some(storage) = Self::local_storage() {
+            let _ = storage.remove_item(&self.key);
+        }
+    }
+
+    #[cfg(all(feature = "desktop", not(feature = "ssr"), not(target_arch = "wasm32")))]
+    fn load_raw(&self) -> Option<String> {
+        std::fs::read_to_string(self.path.as_ref()?).ok()
+    }
+
+    #[cfg(all(feature = "desktop", not(feature = "ssr"), not(target_arch = "wasm32")))]
+    fn store_raw(&self, value: &str) {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return,
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(path, value));
+        if let Err(err) = result {
+            tracing::error!("Failed to save {} to {}: {}", self.key, path.display(), err);
+        }
+    }
+
+    #[cfg(all(feature = "desktop", not(feature = "ssr"), not(target_arch = "wasm32")))]
+    fn remove(&self) {
+        if let Some(path) = &self.path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    // The server has no storage. It always renders the initial value so the client can hydrate it.
+    #[cfg(any(
+        feature = "ssr",
+        all(not(feature = "desktop"), not(target_arch = "wasm32"))
+    ))]
+    fn load_raw(&self) -> Option<String> {
+        None
+    }
+
+    #[cfg(any(
+        feature = "ssr",
+        all(not(feature = "desktop"), not(target_arch = "wasm32"))
+    ))]
+    fn store_raw(&self, _: &str) {}
+
+    #[cfg(any(
+        feature = "ssr",
+        all(not(feature = "desktop"), not(target_arch = "wasm32"))
+    ))]
+    fn remove(&self) {}
+}
+
+#[cfg(all(
+    test,
+    feature = "desktop",
+    not(feature = "ssr"),
+    not(target_arch = "wasm32")
+))]
+fn test_storage(name: &str) -> PersistentStorage {
+    let dir = std::env::temp_dir().join(format!("dioxus-persistent-{}", std::process::id()));
+    PersistentStorage {
+        key: name.to_string(),
+        path: Some(dir.join("persistent").join(name)),
+    }
+}
+
+#[cfg(all(feature = "desktop", not(feature = "ssr"), not(target_arch = "wasm32")))]
+#[test]
+fn desktop_values_round_trip_through_the_data_directory() {
+    let storage = test_storage("round-trip");
+    storage.remove();
+    assert_eq!(storage.load_raw(), None);
+
+    storage.store_raw("raw");
+    assert_eq!(storage.load_raw().as_deref(), Some("raw"));
+
+    storage.store(&vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(
+        storage.load::<Vec<String>>(),
+        Some(vec!["a".to_string(), "b".to_string()])
+    );
+    // A value of a different type falls back to the initial value
+    assert_eq!(storage.load::<u64>(), None);
+
+    storage.remove();
+    assert_eq!(storage.load_raw(), None);
+}
+
+#[cfg(any(
+    feature = "ssr",
+    all(not(feature = "desktop"), not(target_arch = "wasm32"))
+))]
+#[test]
+fn server_does_not_store_values() {
+    let storage = PersistentStorage {
+        key: "count".to_string(),
+    };
+    storage.store(&1);
+    assert_eq!(storage.load::<i32>(), None);
+}
+
+#[cfg(all(feature = "desktop", not(feature = "ssr"), not(target_arch = "wasm32")))]
+#[tokio::test]
+async fn hydration_loads_the_stored_value_after_the_first_render() {
+    thread_local! {
+        static RENDERS: RefCell<Vec<i32>> = RefCell::new(Vec::new());
+    }
+
+    fn app(cx: Scope) -> Element {
+        let count = cx.use_hook(|| init_persistent(cx, test_storage("hydration"), || 0, true));
+        RENDERS.with(|renders| renders.borrow_mut().push(count.get()));
+        render! { "{count.get()}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    test_storage("hydration").store(&5);
+
+    // The first render uses the initial value like the server did
+    let _ = dom.rebuild();
+    RENDERS.with(|renders| assert_eq!(*renders.borrow(), [0]));
+
+    tokio::time::timeout(std::time::Duration::from_secs(1), dom.wait_for_work())
+        .await
+        .unwrap();
+    let _ = dom.render_immediate();
+    RENDERS.with(|renders| assert_eq!(*renders.borrow(), [0, 5]));
+}
@@ -0,0 +1,96 @@
+use dioxus::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::future::Future;
+
+/// A [`use_query`] that is fetched on the server and sent to the client with the HTML.
+///
+/// On the server, the component suspends until the query resolves and the key and result are serialized into the HTML.
+/// On the client, the result is loaded into the [`QueryCache`] before the query runs, so the client does not fetch the
+/// key again while it hydrates.
+///
+/// Like [`use_server_future`](crate::prelude::use_server_future), the queries are sent in the order they resolve on the
+/// server. Because the key is sent with the result, a result is always stored under the key it was fetched for.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_fullstack::prelude::*;
+///
+/// #[server]
+/// async fn get_user(id: u32) -> Result<String, ServerFnError> {
+///     Ok(format!("user {id}"))
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope, id: u32) -> Element {
+///     let user = use_server_query(cx, ("user", *id), |(_, id)| get_user(id))?;
+///
+///     match user.value().as_deref() {
+///         Some(Ok(user)) => render! { "{user}" },
+///         Some(Err(err)) => render! { "Failed to load the user: {err}" },
+///         None => render! { "Loading..." },
+///     }
+/// }
+/// ```
+pub fn use_server_query<K, T, E, F>(
+    cx: &ScopeState,
+    key: K,
+    fetcher: impl FnOnce(K) -> F,
+) -> Option<&UseQuery<T, E>>
+where
+    K: Clone + Serialize + DeserializeOwned + 'static,
+    T: Serialize + DeserializeOwned + 'static,
+    E: Serialize + DeserializeOwned + 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    // The result from the server is fresh for the first render, so the client doesn't fetch it again while hydrating
+    #[allow(unused_mut)]
+    let mut options = QueryOptions::default();
+
+    #[cfg(not(feature = "ssr"))]
+    {
+        let cache = use_query_cache(cx);
+        let hydrated = cx.use_hook(|| {
+            match crate::html_storage::deserialize::take_server_data::<(K, Result<T, E>)>() {
+                Some((key, value)) => {
+                    cache.set(key, value);
+                    true
+                }
+                None => {
+                    tracing::trace!("Failed to load the query from the server... fetching it");
+                    false
+                }
+            }
+        });
+        if std::mem::take(hydrated) {
+            options.stale_time = std::time::Duration::MAX;
+        }
+    }
+
+    #[cfg(feature = "ssr")]
+    let sent = cx.use_hook(|| false);
+
+    let query = use_query_with_options(cx, key.clone(), options, fetcher);
+
+    #[cfg(feature = "ssr")]
+    {
+        match query.value() {
+            Some(value) => {
+                if !*sent {
+                    *sent = true;
+                    if let Err(err) =
+                        crate::prelude::server_context().push_html_data(&(&key, &*value))
+                    {
+                        tracing::error!("Failed to push HTML data: {}", err);
+                    }
+                }
+            }
+            None => {
+                tracing::trace!("Suspending until the query resolves");
+                cx.suspend();
+                return None;
+            }
+        }
+    }
+
+    Some(query)
+}
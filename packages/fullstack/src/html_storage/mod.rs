@@ -19,6 +19,18 @@ impl HTMLData {
         self.data.push(serialized);
     }
 
+    /// Reserve a slot for data that is set later with [`Self::set`]. The client takes the data in
+    /// the order the slots were reserved, not the order they were set.
+    pub(crate) fn reserve(&mut self) -> usize {
+        self.data.push(Vec::new());
+        self.data.len() - 1
+    }
+
+    pub(crate) fn set<T: Serialize>(&mut self, index: usize, value: &T) {
+        let serialized = postcard::to_allocvec(value).unwrap();
+        self.data[index] = serialized;
+    }
+
     pub(crate) fn cursor(self) -> HTMLDataCursor {
         HTMLDataCursor {
             data: self.data,
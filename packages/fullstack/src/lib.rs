@@ -40,12 +40,12 @@ pub mod prelude {
     #[cfg(not(feature = "ssr"))]
     pub use crate::html_storage::deserialize::get_root_props_from_document;
     pub use crate::launch::LaunchBuilder;
-    #[cfg(all(feature = "ssr", feature = "router"))]
-    pub use crate::render::pre_cache_static_routes_with_props;
     #[cfg(feature = "ssr")]
     pub use crate::render::SSRState;
+    #[cfg(all(feature = "ssr", feature = "router"))]
+    pub use crate::render::{export_static_site_with_props, pre_cache_static_routes_with_props};
     #[cfg(feature = "router")]
-    pub use crate::router::FullstackRouterConfig;
+    pub use crate::router::{server_loader, FullstackRouterConfig};
     #[cfg(feature = "ssr")]
     pub use crate::serve_config::{ServeConfig, ServeConfigBuilder};
     #[cfg(all(feature = "ssr", feature = "axum"))]
@@ -62,5 +62,10 @@ pub mod prelude {
     pub use dioxus_ssr::incremental::IncrementalRendererConfig;
     pub use server_fn::{self, ServerFn as _, ServerFnError};
 
-    pub use hooks::{server_cached::server_cached, server_future::use_server_future};
+    pub use hooks::{
+        persistent::{use_persistent, UsePersistent},
+        server_cached::server_cached,
+        server_future::use_server_future,
+        server_query::use_server_query,
+    };
 }
@@ -156,6 +156,50 @@ impl SsrRendererPool {
     }
 }
 
+impl SsrRendererPool {
+    /// Take a plain renderer out of the pool. Streamed pages are never cached, so incremental pools create a new renderer
+    fn take_renderer(self: &Arc<Self>) -> PooledRenderer {
+        let renderer = match &**self {
+            Self::Renderer(pool) => pool.write().unwrap().pop().unwrap_or_else(pre_renderer),
+            Self::Incremental(_) => pre_renderer(),
+        };
+        PooledRenderer {
+            pool: self.clone(),
+            renderer: Some(renderer),
+        }
+    }
+}
+
+/// A renderer taken from a [`SsrRendererPool`] that is returned to the pool when it is dropped, even if rendering fails
+struct PooledRenderer {
+    pool: Arc<SsrRendererPool>,
+    renderer: Option<Renderer>,
+}
+
+impl std::ops::Deref for PooledRenderer {
+    type Target = Renderer;
+
+    fn deref(&self) -> &Self::Target {
+        self.renderer.as_ref().unwrap()
+    }
+}
+
+impl std::ops::DerefMut for PooledRenderer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.renderer.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledRenderer {
+    fn drop(&mut self) {
+        if let (SsrRendererPool::Renderer(pool), Some(renderer)) =
+            (&*self.pool, self.renderer.take())
+        {
+            pool.write().unwrap().push(renderer);
+        }
+    }
+}
+
 /// State used in server side rendering. This utilizes a pool of [`dioxus_ssr::Renderer`]s to cache static templates between renders.
 #[derive(Clone)]
 pub struct SSRState {
@@ -208,6 +252,95 @@ impl SSRState {
             Ok(RenderResponse { html, freshness })
         }
     }
+
+    /// Render the application to a stream of HTML chunks.
+    ///
+    /// This resolves as soon as the shell of the page is rendered. Suspended components are streamed to the client as they resolve.
+    /// Response headers must be read from the server context once this resolves, changes made after that are not sent.
+    pub fn render_streaming<'a, P: 'static + Clone + serde::Serialize + Send + Sync>(
+        &'a self,
+        cfg: &'a ServeConfig<P>,
+        server_context: &'a DioxusServerContext,
+    ) -> impl std::future::Future<
+        Output = Result<StreamingRenderResponse, dioxus_ssr::incremental::IncrementalRendererError>,
+    > + Send
+           + 'a {
+        async move {
+            let ServeConfig { app, props, .. } = cfg;
+            let component = *app;
+            let props = props.clone();
+
+            let wrapper = FullstackRenderer {
+                cfg: cfg.clone(),
+                server_context: server_context.clone(),
+            };
+            let server_context = Box::new(server_context.clone());
+            let mut renderer = self.renderers.take_renderer();
+
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+            spawn_blocking(move || {
+                tokio::runtime::Runtime::new()
+                    .expect("couldn't spawn runtime")
+                    .block_on(async move {
+                        let mut vdom = VirtualDom::new_with_props(component, props);
+                        let mut head = WriteBuffer { buffer: Vec::new() };
+                        if let Err(err) = wrapper.render_before_body(&mut *head) {
+                            let _ = tx.send(Err(err));
+                            return;
+                        }
+                        let mut head = match String::from_utf8(head.buffer) {
+                            Ok(head) => Some(head),
+                            Err(err) => {
+                                let _ = tx.send(Err(
+                                    dioxus_ssr::incremental::IncrementalRendererError::Other(
+                                        Box::new(err),
+                                    ),
+                                ));
+                                return;
+                            }
+                        };
+
+                        // The server context needs to stay set while streaming because suspended components may call server_context()
+                        let prev_context = SERVER_CONTEXT.with(|ctx| ctx.replace(server_context));
+                        tracing::info!("Rebuilding vdom");
+                        let _ = vdom.rebuild();
+                        let streamed = renderer
+                            .render_streaming(&mut vdom, |chunk| {
+                                // The head of the page is sent with the shell
+                                let chunk = match head.take() {
+                                    Some(head) => head + &chunk,
+                                    None => chunk,
+                                };
+                                tx.send(Ok(chunk)).map_err(|_| std::fmt::Error)
+                            })
+                            .await;
+                        tracing::info!("Suspense resolved");
+                        SERVER_CONTEXT.with(|ctx| ctx.replace(prev_context));
+
+                        if let Err(err) = streamed {
+                            let _ = tx.send(Err(err.into()));
+                            return;
+                        }
+
+                        let mut tail = WriteBuffer { buffer: Vec::new() };
+                        if let Err(err) = wrapper.render_after_body(&mut *tail) {
+                            let _ = tx.send(Err(err));
+                            return;
+                        }
+                        let _ = tx.send(String::from_utf8(tail.buffer).map_err(|err| {
+                            dioxus_ssr::incremental::IncrementalRendererError::Other(Box::new(err))
+                        }));
+                    });
+            });
+
+            let shell = rx.recv().await.unwrap_or(Err(
+                dioxus_ssr::incremental::IncrementalRendererError::RenderError(std::fmt::Error),
+            ))?;
+
+            Ok(StreamingRenderResponse { shell, rest: rx })
+        }
+    }
 }
 
 struct FullstackRenderer<P: Clone + Send + Sync + 'static> {
@@ -319,6 +452,36 @@ impl RenderResponse {
     }
 }
 
+/// A rendered response that is streamed to the client as suspended components resolve.
+#[derive(Debug)]
+pub struct StreamingRenderResponse {
+    pub(crate) shell: String,
+    pub(crate) rest: tokio::sync::mpsc::UnboundedReceiver<
+        Result<String, dioxus_ssr::incremental::IncrementalRendererError>,
+    >,
+}
+
+impl StreamingRenderResponse {
+    /// Get the HTML that was rendered before any suspended components resolved.
+    pub fn shell(&self) -> &str {
+        &self.shell
+    }
+
+    /// Convert the response into a stream of HTML chunks, starting with the shell.
+    pub fn into_stream(
+        self,
+    ) -> impl tokio_stream::Stream<
+        Item = Result<String, dioxus_ssr::incremental::IncrementalRendererError>,
+    > + Send
+           + 'static {
+        use tokio_stream::StreamExt;
+
+        tokio_stream::once(Ok(self.shell)).chain(
+            tokio_stream::wrappers::UnboundedReceiverStream::new(self.rest),
+        )
+    }
+}
+
 fn pre_renderer() -> Renderer {
     let mut renderer = Renderer::default();
     renderer.pre_render = true;
@@ -355,6 +518,28 @@ where
     dioxus_router::incremental::pre_cache_static_routes::<Rt, _>(&mut renderer, &wrapper).await
 }
 
+#[cfg(all(feature = "ssr", feature = "router"))]
+/// Renders all static routes to the static directory of the incremental renderer, replacing any cached pages
+pub async fn export_static_site_with_props<Rt>(
+    cfg: &crate::prelude::ServeConfig<crate::router::FullstackRouterConfig<Rt>>,
+) -> dioxus_router::incremental::StaticGenerationReport
+where
+    Rt: dioxus_router::prelude::Routable + Send + Sync + Serialize,
+    <Rt as std::str::FromStr>::Err: std::fmt::Display,
+{
+    let wrapper = FullstackRenderer {
+        cfg: cfg.clone(),
+        server_context: Default::default(),
+    };
+    let mut renderer = incremental_pre_renderer(
+        cfg.incremental
+            .as_ref()
+            .expect("incremental renderer config must be set to export a static site"),
+    );
+
+    dioxus_router::incremental::export_static_site::<Rt, _>(&mut renderer, &wrapper).await
+}
+
 struct WriteBuffer {
     buffer: Vec<u8>,
 }
@@ -98,3 +98,77 @@ where
         }
     }
 }
+
+/// Wrap the future of a [route loader](dioxus_router::prelude::RouteLoader) so the data it loads
+/// on the server is sent to the client with the HTML.
+///
+/// On the server, the data is serialized into the HTML once the loader is finished. When the
+/// client hydrates that page, the loaders of the first route read the data from the HTML instead
+/// of loading it again. Later navigations run the loader on the client.
+///
+/// Loaders that don't use this function run on the server and again on the client when it
+/// hydrates.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_fullstack::prelude::*;
+/// use dioxus_router::prelude::*;
+///
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/user/:id", User, loader = load_user)]
+///     User { id: usize },
+/// }
+///
+/// fn load_user(id: usize) -> impl std::future::Future<Output = String> {
+///     server_loader(async move { format!("User {id}") })
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope, id: usize) -> Element {
+///     let name = use_loader_data::<String>(cx)?;
+///     render! { h1 { "{name}" } }
+/// }
+/// ```
+pub fn server_loader<T, F>(loader: F) -> impl std::future::Future<Output = T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + 'static,
+    F: std::future::Future<Output = T> + 'static,
+{
+    // The slot is reserved when the loader is created, so the client takes the data in the same
+    // order even if the loaders finish in a different order on the server
+    #[cfg(feature = "ssr")]
+    let slot = {
+        let context = crate::prelude::server_context();
+        let index = context.reserve_html_data().map_err(|err| {
+            tracing::error!("Failed to reserve HTML data: {}", err);
+        });
+        (context, index)
+    };
+
+    #[cfg(not(feature = "ssr"))]
+    let hydrated = match dioxus_router::loader::is_initial_load() {
+        true => crate::html_storage::deserialize::take_server_data::<T>(),
+        false => None,
+    };
+
+    async move {
+        #[cfg(feature = "ssr")]
+        {
+            let data = loader.await;
+            if let (context, Ok(index)) = slot {
+                if let Err(err) = context.set_html_data(index, &data) {
+                    tracing::error!("Failed to push HTML data: {}", err);
+                }
+            }
+            data
+        }
+        #[cfg(not(feature = "ssr"))]
+        {
+            match hydrated {
+                Some(data) => data,
+                None => loader.await,
+            }
+        }
+    }
+}
@@ -19,6 +19,7 @@ pub struct ServeConfigBuilder<P: Clone> {
     pub(crate) assets_path: Option<&'static str>,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) streaming: bool,
 }
 
 /// A template for incremental rendering that does nothing.
@@ -63,6 +64,7 @@ impl<P: Clone> ServeConfigBuilder<P> {
             index_path: None,
             assets_path: None,
             incremental: None,
+            streaming: false,
         }
     }
 
@@ -72,6 +74,15 @@ impl<P: Clone> ServeConfigBuilder<P> {
         self
     }
 
+    /// Stream the page to the client as suspended components resolve instead of waiting for the whole page to render (defaults to false)
+    ///
+    /// Streamed pages are never cached by the incremental renderer. The status and headers are sent with the shell of the
+    /// page, so headers or a status set through the server context by suspended components are dropped.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
     /// Set the path of the index.html file to be served. (defaults to {assets_path}/index.html)
     pub fn index_path(mut self, index_path: &'static str) -> Self {
         self.index_path = Some(index_path);
@@ -109,6 +120,7 @@ impl<P: Clone> ServeConfigBuilder<P> {
             index,
             assets_path,
             incremental: self.incremental,
+            streaming: self.streaming,
         }
     }
 }
@@ -153,6 +165,7 @@ pub struct ServeConfig<P: Clone> {
     pub(crate) assets_path: &'static str,
     pub(crate) incremental:
         Option<std::sync::Arc<dioxus_ssr::incremental::IncrementalRendererConfig>>,
+    pub(crate) streaming: bool,
 }
 
 impl<P: Clone> From<ServeConfigBuilder<P>> for ServeConfig<P> {
@@ -115,6 +115,24 @@ mod server_fn_impl {
             })
         }
 
+        /// Reserve a slot in the html data store that is filled later with [`Self::set_html_data`]
+        pub(crate) fn reserve_html_data(
+            &self,
+        ) -> Result<usize, PoisonError<RwLockWriteGuard<'_, HTMLData>>> {
+            self.html_data.write().map(|mut map| map.reserve())
+        }
+
+        /// Fill a slot reserved with [`Self::reserve_html_data`]
+        pub(crate) fn set_html_data<T: serde::Serialize>(
+            &self,
+            index: usize,
+            value: &T,
+        ) -> Result<(), PoisonError<RwLockWriteGuard<'_, HTMLData>>> {
+            self.html_data.write().map(|mut map| {
+                map.set(index, value);
+            })
+        }
+
         /// Get the html data store
         pub(crate) fn html_data(&self) -> LockResult<RwLockReadGuard<'_, HTMLData>> {
             self.html_data.read()
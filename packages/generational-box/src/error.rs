@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt::Display;
+
+#[cfg(feature = "debug_borrows")]
+use std::panic::Location;
+
+/// An error that can occur when trying to borrow a value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowError {
+    /// The value was dropped.
+    Dropped(ValueDroppedError),
+    /// The value was already borrowed mutably.
+    AlreadyBorrowedMut(AlreadyBorrowedMutError),
+}
+
+impl Display for BorrowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BorrowError::Dropped(error) => Display::fmt(error, f),
+            BorrowError::AlreadyBorrowedMut(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for BorrowError {}
+
+/// An error that can occur when trying to borrow a value mutably.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BorrowMutError {
+    /// The value was dropped.
+    Dropped(ValueDroppedError),
+    /// The value was already borrowed.
+    AlreadyBorrowed(AlreadyBorrowedError),
+    /// The value was already borrowed mutably.
+    AlreadyBorrowedMut(AlreadyBorrowedMutError),
+}
+
+impl Display for BorrowMutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BorrowMutError::Dropped(error) => Display::fmt(error, f),
+            BorrowMutError::AlreadyBorrowed(error) => Display::fmt(error, f),
+            BorrowMutError::AlreadyBorrowedMut(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for BorrowMutError {}
+
+/// An error that can occur when trying to use a value that has been dropped.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ValueDroppedError {
+    #[cfg(feature = "debug_borrows")]
+    pub(crate) created_at: &'static Location<'static>,
+    #[cfg(feature = "debug_borrows")]
+    pub(crate) dropped_at: Option<&'static Location<'static>>,
+}
+
+impl ValueDroppedError {
+    /// The location the value was created at. This is only available with the `debug_borrows` feature.
+    #[cfg(feature = "debug_borrows")]
+    pub fn created_at(&self) -> &'static Location<'static> {
+        self.created_at
+    }
+
+    /// The location of the [`crate::Owner`] that dropped the value, where it was created with [`crate::Store::owner`].
+    /// Owners are dropped by drop glue that has no location of its own, so this points to the owner instead. This is
+    /// only available with the `debug_borrows` feature.
+    #[cfg(feature = "debug_borrows")]
+    pub fn dropped_at(&self) -> Option<&'static Location<'static>> {
+        self.dropped_at
+    }
+}
+
+impl Display for ValueDroppedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to borrow because the value was dropped.")?;
+        #[cfg(feature = "debug_borrows")]
+        match self.dropped_at {
+            Some(dropped_at) => f.write_fmt(format_args!(
+                " The value was created at {} and dropped when its owner created at {} was dropped.",
+                self.created_at, dropped_at
+            ))?,
+            None => f.write_fmt(format_args!(
+                " The value was created at {} and dropped when its owner was dropped.",
+                self.created_at
+            ))?,
+        }
+        Ok(())
+    }
+}
+
+impl Error for ValueDroppedError {}
+
+/// An error that can occur when trying to borrow a value that has already been borrowed mutably.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AlreadyBorrowedMutError {
+    #[cfg(feature = "debug_borrows")]
+    pub(crate) borrowed_mut_at: Option<&'static Location<'static>>,
+}
+
+impl AlreadyBorrowedMutError {
+    /// The location of the mutable borrow that is still active. This is only available with the `debug_borrows` feature.
+    #[cfg(feature = "debug_borrows")]
+    pub fn borrowed_mut_at(&self) -> Option<&'static Location<'static>> {
+        self.borrowed_mut_at
+    }
+}
+
+impl Display for AlreadyBorrowedMutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to borrow because the value was already borrowed mutably.")?;
+        #[cfg(feature = "debug_borrows")]
+        if let Some(location) = self.borrowed_mut_at {
+            f.write_fmt(format_args!(
+                " The value was borrowed mutably at {}",
+                location
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for AlreadyBorrowedMutError {}
+
+/// An error that can occur when trying to borrow a value mutably that has already been borrowed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlreadyBorrowedError {
+    #[cfg(feature = "debug_borrows")]
+    pub(crate) borrowed_at: Vec<&'static Location<'static>>,
+}
+
+impl AlreadyBorrowedError {
+    /// The locations of the borrows that are still active. This is only available with the `debug_borrows` feature.
+    #[cfg(feature = "debug_borrows")]
+    pub fn borrowed_at(&self) -> &[&'static Location<'static>] {
+        &self.borrowed_at
+    }
+}
+
+impl Display for AlreadyBorrowedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to borrow mutably because the value was already borrowed.")?;
+        #[cfg(feature = "debug_borrows")]
+        if !self.borrowed_at.is_empty() {
+            f.write_str(" The value is borrowed at:")?;
+            for location in &self.borrowed_at {
+                f.write_fmt(format_args!("\n - {}", location))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Error for AlreadyBorrowedError {}
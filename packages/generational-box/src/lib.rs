@@ -1,14 +1,25 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+use parking_lot::{
+    MappedRwLockReadGuard, MappedRwLockWriteGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
 use std::{
-    cell::{Cell, Ref, RefCell, RefMut},
+    any::Any,
+    cell::{Ref, RefCell, RefMut},
     fmt::Debug,
     marker::PhantomData,
+    ops::{Deref, DerefMut},
+    panic::Location,
     rc::Rc,
+    sync::atomic::{AtomicU32, Ordering},
 };
 
-use bumpalo::Bump;
+mod error;
+pub use error::*;
+mod references;
+use references::MemoryLocationBorrowInfo;
+pub use references::{BorrowLocation, GenerationalRef, GenerationalRefMut};
 
 /// # Example
 ///
@@ -29,12 +40,12 @@ fn reused() {
     let first_ptr;
     {
         let owner = store.owner();
-        first_ptr = owner.insert(1).raw.data.as_ptr();
+        first_ptr = owner.insert(1).raw.data_ptr();
         drop(owner);
     }
     {
         let owner = store.owner();
-        let second_ptr = owner.insert(1234).raw.data.as_ptr();
+        let second_ptr = owner.insert(1234).raw.data_ptr();
         assert_eq!(first_ptr, second_ptr);
         drop(owner);
     }
@@ -53,7 +64,10 @@ fn leaking_is_ok() {
         // don't drop the owner
         std::mem::forget(owner);
     }
-    assert_eq!(key.try_read().as_deref(), Some(&"hello world".to_string()));
+    assert_eq!(
+        key.try_read().ok().as_deref(),
+        Some(&"hello world".to_string())
+    );
 }
 
 #[test]
@@ -68,7 +82,7 @@ fn drops() {
         key = owner.insert(data);
         // drop the owner
     }
-    assert!(key.try_read().is_none());
+    assert!(key.try_read().is_err());
 }
 
 #[test]
@@ -148,34 +162,161 @@ fn fuzz() {
     }
 }
 
+#[test]
+fn sync_works() {
+    let store = Store::<SyncStorage>::new();
+    let owner = store.owner();
+    let key = owner.insert(1);
+
+    std::thread::spawn(move || {
+        *key.write() += 1;
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*key.read(), 2);
+}
+
+#[test]
+fn sync_drops() {
+    let store = Store::<SyncStorage>::new();
+    let key;
+    {
+        let owner = store.owner();
+        key = owner.insert(String::from("hello world"));
+    }
+    let valid = std::thread::spawn(move || key.try_read().is_ok())
+        .join()
+        .unwrap();
+    assert!(!valid);
+}
+
+#[test]
+fn borrow_errors() {
+    let store = Store::default();
+    let owner = store.owner();
+    let key = owner.insert(1);
+
+    let read = key.read();
+    assert!(matches!(
+        key.try_write(),
+        Err(BorrowMutError::AlreadyBorrowed(_))
+    ));
+    drop(read);
+
+    let write = key.write();
+    assert!(matches!(
+        key.try_read(),
+        Err(BorrowError::AlreadyBorrowedMut(_))
+    ));
+    assert!(matches!(
+        key.try_write(),
+        Err(BorrowMutError::AlreadyBorrowedMut(_))
+    ));
+    drop(write);
+
+    drop(owner);
+    assert!(matches!(key.try_read(), Err(BorrowError::Dropped(_))));
+}
+
+#[test]
+fn sync_try_borrow_does_not_block() {
+    let store = Store::<SyncStorage>::new();
+    let owner = store.owner();
+    let key = owner.insert(1);
+
+    let write = key.write();
+    assert!(matches!(
+        key.try_read(),
+        Err(BorrowError::AlreadyBorrowedMut(_))
+    ));
+    assert!(matches!(
+        key.try_write(),
+        Err(BorrowMutError::AlreadyBorrowedMut(_))
+    ));
+    drop(write);
+
+    let read = key.read();
+    assert!(matches!(
+        key.try_write(),
+        Err(BorrowMutError::AlreadyBorrowed(_))
+    ));
+    assert_eq!(*key.try_read().unwrap(), 1);
+    drop(read);
+}
+
+#[cfg(feature = "debug_borrows")]
+#[test]
+fn borrow_errors_report_locations() {
+    let store = Store::default();
+    let (owner, owner_created_at) = (store.owner(), line!());
+    let (key, created_at) = (owner.insert(1), line!());
+
+    let (write, borrowed_mut_at) = (key.write(), line!());
+    match key.try_read() {
+        Err(BorrowError::AlreadyBorrowedMut(error)) => {
+            assert_eq!(error.borrowed_mut_at().unwrap().line(), borrowed_mut_at)
+        }
+        _ => panic!("the value should be borrowed mutably"),
+    }
+    drop(write);
+
+    let (read, borrowed_at) = (key.read(), line!());
+    match key.try_write() {
+        Err(BorrowMutError::AlreadyBorrowed(error)) => {
+            let lines: Vec<_> = error.borrowed_at().iter().map(|at| at.line()).collect();
+            assert_eq!(lines, [borrowed_at]);
+        }
+        _ => panic!("the value should be borrowed"),
+    }
+    drop(read);
+
+    drop(owner);
+    match key.try_read() {
+        Err(BorrowError::Dropped(error)) => {
+            assert_eq!(error.created_at().line(), created_at);
+            assert_eq!(
+                error.dropped_at().map(|at| at.line()),
+                Some(owner_created_at)
+            );
+        }
+        _ => panic!("the value should be dropped"),
+    }
+}
+
 /// The core Copy state type. The generational box will be dropped when the [Owner] is dropped.
-pub struct GenerationalBox<T> {
-    raw: MemoryLocation,
+///
+/// The storage decides which threads can access the value. Boxes in [`UnsyncStorage`] can only be used on the thread
+/// they were created on, boxes in [`SyncStorage`] can be sent to and shared between threads.
+pub struct GenerationalBox<T, S: 'static = UnsyncStorage> {
+    raw: MemoryLocation<S>,
     #[cfg(any(debug_assertions, feature = "check_generation"))]
     generation: u32,
+    #[cfg(feature = "debug_borrows")]
+    created_at: &'static Location<'static>,
     _marker: PhantomData<T>,
 }
 
-impl<T: 'static> Debug for GenerationalBox<T> {
+impl<T: 'static, S: AnyStorage> Debug for GenerationalBox<T, S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         #[cfg(any(debug_assertions, feature = "check_generation"))]
         f.write_fmt(format_args!(
             "{:?}@{:?}",
-            self.raw.data.as_ptr(),
+            self.raw.data_ptr(),
             self.generation
         ))?;
         #[cfg(not(any(debug_assertions, feature = "check_generation")))]
-        f.write_fmt(format_args!("{:?}", self.raw.data.as_ptr()))?;
+        f.write_fmt(format_args!("{:?}", self.raw.data_ptr()))?;
         Ok(())
     }
 }
 
-impl<T: 'static> GenerationalBox<T> {
+impl<T: 'static, S: Storage<T>> GenerationalBox<T, S> {
     #[inline(always)]
     fn validate(&self) -> bool {
         #[cfg(any(debug_assertions, feature = "check_generation"))]
         {
-            self.raw.generation.get() == self.generation
+            self.raw.0.generation.load(Ordering::Acquire) == self.generation
         }
         #[cfg(not(any(debug_assertions, feature = "check_generation")))]
         {
@@ -183,44 +324,84 @@ impl<T: 'static> GenerationalBox<T> {
         }
     }
 
-    /// Try to read the value. Returns None if the value is no longer valid.
-    pub fn try_read(&self) -> Option<Ref<'_, T>> {
-        self.validate()
-            .then(|| {
-                Ref::filter_map(self.raw.data.borrow(), |any| {
-                    any.as_ref()?.downcast_ref::<T>()
-                })
-                .ok()
-            })
-            .flatten()
+    #[track_caller]
+    fn borrow_location(&self) -> BorrowLocation {
+        #[cfg(feature = "debug_borrows")]
+        let created_at = self.created_at;
+        #[cfg(not(feature = "debug_borrows"))]
+        let created_at = Location::caller();
+        BorrowLocation::new(created_at, Location::caller(), &self.raw.0.borrow)
     }
 
-    /// Read the value. Panics if the value is no longer valid.
-    pub fn read(&self) -> Ref<'_, T> {
-        self.try_read().unwrap()
+    /// Try to read the value. Returns an error if the value is no longer valid or is already borrowed mutably.
+    #[track_caller]
+    pub fn try_read(&self) -> Result<S::Ref<T>, BorrowError> {
+        let at = self.borrow_location();
+        let read = self.raw.0.data.try_read(at);
+        self.validate_read(read, at)
     }
 
-    /// Try to write the value. Returns None if the value is no longer valid.
-    pub fn try_write(&self) -> Option<RefMut<'_, T>> {
-        self.validate()
-            .then(|| {
-                RefMut::filter_map(self.raw.data.borrow_mut(), |any| {
-                    any.as_mut()?.downcast_mut::<T>()
-                })
-                .ok()
-            })
-            .flatten()
+    /// Read the value. Panics if the value is no longer valid or is already borrowed mutably.
+    ///
+    /// With [`SyncStorage`], this waits for a mutable borrow on another thread to be released instead of panicking.
+    #[track_caller]
+    pub fn read(&self) -> S::Ref<T> {
+        let at = self.borrow_location();
+        let read = self.raw.0.data.read(at);
+        match self.validate_read(read, at) {
+            Ok(read) => read,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    fn validate_read(
+        &self,
+        read: Result<S::Ref<T>, BorrowError>,
+        at: BorrowLocation,
+    ) -> Result<S::Ref<T>, BorrowError> {
+        // The generation is checked after the value is borrowed so it can't be recycled in between
+        if !self.validate() {
+            return Err(BorrowError::Dropped(at.dropped()));
+        }
+        read
+    }
+
+    /// Try to write the value. Returns an error if the value is no longer valid or is already borrowed.
+    #[track_caller]
+    pub fn try_write(&self) -> Result<S::Mut<T>, BorrowMutError> {
+        let at = self.borrow_location();
+        let write = self.raw.0.data.try_write(at);
+        self.validate_write(write, at)
+    }
+
+    /// Write the value. Panics if the value is no longer valid or is already borrowed.
+    ///
+    /// With [`SyncStorage`], this waits for borrows on other threads to be released instead of panicking.
+    #[track_caller]
+    pub fn write(&self) -> S::Mut<T> {
+        let at = self.borrow_location();
+        let write = self.raw.0.data.write(at);
+        match self.validate_write(write, at) {
+            Ok(write) => write,
+            Err(error) => panic!("{}", error),
+        }
     }
 
-    /// Write the value. Panics if the value is no longer valid.
-    pub fn write(&self) -> RefMut<'_, T> {
-        self.try_write().unwrap()
+    fn validate_write(
+        &self,
+        write: Result<S::Mut<T>, BorrowMutError>,
+        at: BorrowLocation,
+    ) -> Result<S::Mut<T>, BorrowMutError> {
+        if !self.validate() {
+            return Err(BorrowMutError::Dropped(at.dropped()));
+        }
+        write
     }
 
-    /// Set the value. Panics if the value is no longer valid.
+    /// Set the value. Does nothing if the value is no longer valid.
     pub fn set(&self, value: T) {
         self.validate().then(|| {
-            *self.raw.data.borrow_mut() = Some(Box::new(value));
+            self.raw.0.data.set(value);
         });
     }
 
@@ -228,110 +409,358 @@ impl<T: 'static> GenerationalBox<T> {
     pub fn ptr_eq(&self, other: &Self) -> bool {
         #[cfg(any(debug_assertions, feature = "check_generation"))]
         {
-            self.raw.data.as_ptr() == other.raw.data.as_ptr() && self.generation == other.generation
+            self.raw.data_ptr() == other.raw.data_ptr() && self.generation == other.generation
         }
         #[cfg(not(any(debug_assertions, feature = "check_generation")))]
         {
-            self.raw.data.as_ptr() == other.raw.data.as_ptr()
+            self.raw.data_ptr() == other.raw.data_ptr()
         }
     }
 }
 
-impl<T> Copy for GenerationalBox<T> {}
+impl<T, S: 'static> Copy for GenerationalBox<T, S> {}
 
-impl<T> Clone for GenerationalBox<T> {
+impl<T, S: 'static> Clone for GenerationalBox<T, S> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-#[derive(Clone, Copy)]
-struct MemoryLocation {
-    data: &'static RefCell<Option<Box<dyn std::any::Any>>>,
-    #[cfg(any(debug_assertions, feature = "check_generation"))]
-    generation: &'static Cell<u32>,
+/// The operations every kind of storage supports, regardless of the type stored in it
+pub trait AnyStorage: Default + 'static {
+    /// A shared borrow of a value in the storage
+    type Ref<T: ?Sized + 'static>: Deref<Target = T> + 'static;
+    /// A mutable borrow of a value in the storage
+    type Mut<T: ?Sized + 'static>: DerefMut<Target = T> + 'static;
+
+    /// Try to map a shared borrow to a part of the value
+    fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>>;
+
+    /// Map a shared borrow to a part of the value
+    fn map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Ref<T>,
+        f: impl FnOnce(&T) -> &U,
+    ) -> Self::Ref<U> {
+        Self::try_map(borrow, |value| Some(f(value))).unwrap()
+    }
+
+    /// Try to map a mutable borrow to a part of the value
+    fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>>;
+
+    /// Map a mutable borrow to a part of the value
+    fn map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> &mut U,
+    ) -> Self::Mut<U> {
+        Self::try_map_mut(borrow, |value| Some(f(value))).unwrap()
+    }
+
+    /// Drop the value in the storage. Returns true if there was a value.
+    fn take(&self) -> bool;
+}
+
+/// A kind of storage that can hold values of type `Data`
+pub trait Storage<Data>: AnyStorage {
+    /// Try to borrow the value. Returns an error if the storage is empty, holds a different type or is already
+    /// borrowed mutably.
+    fn try_read(&'static self, at: BorrowLocation) -> Result<Self::Ref<Data>, BorrowError>;
+
+    /// Try to borrow the value mutably. Returns an error if the storage is empty, holds a different type or is already
+    /// borrowed.
+    fn try_write(&'static self, at: BorrowLocation) -> Result<Self::Mut<Data>, BorrowMutError>;
+
+    /// Borrow the value. Storage that can be shared between threads may wait for a mutable borrow to be released
+    /// instead of returning an error.
+    fn read(&'static self, at: BorrowLocation) -> Result<Self::Ref<Data>, BorrowError> {
+        self.try_read(at)
+    }
+
+    /// Borrow the value mutably. Storage that can be shared between threads may wait for other borrows to be released
+    /// instead of returning an error.
+    fn write(&'static self, at: BorrowLocation) -> Result<Self::Mut<Data>, BorrowMutError> {
+        self.try_write(at)
+    }
+
+    /// Replace the value in the storage
+    fn set(&self, value: Data);
 }
 
-impl MemoryLocation {
+/// Storage that can only be used on the thread it was created on. This is backed by a [`RefCell`].
+#[derive(Default)]
+pub struct UnsyncStorage(RefCell<Option<Box<dyn Any>>>);
+
+impl AnyStorage for UnsyncStorage {
+    type Ref<T: ?Sized + 'static> = GenerationalRef<Ref<'static, T>>;
+    type Mut<T: ?Sized + 'static> = GenerationalRefMut<RefMut<'static, T>>;
+
+    fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        borrow.try_map(|borrow| Ref::filter_map(borrow, f).ok())
+    }
+
+    fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        borrow.try_map(|borrow| RefMut::filter_map(borrow, f).ok())
+    }
+
+    fn take(&self) -> bool {
+        self.0.borrow_mut().take().is_some()
+    }
+}
+
+impl<T: 'static> Storage<T> for UnsyncStorage {
+    fn try_read(&'static self, at: BorrowLocation) -> Result<Self::Ref<T>, BorrowError> {
+        let borrow = self
+            .0
+            .try_borrow()
+            .map_err(|_| BorrowError::AlreadyBorrowedMut(at.already_borrowed_mut()))?;
+        let borrow = Ref::filter_map(borrow, |any| any.as_ref()?.downcast_ref())
+            .map_err(|_| BorrowError::Dropped(at.dropped()))?;
+        Ok(GenerationalRef::new(borrow, at.shared()))
+    }
+
+    fn try_write(&'static self, at: BorrowLocation) -> Result<Self::Mut<T>, BorrowMutError> {
+        let borrow = self.0.try_borrow_mut().map_err(|_| {
+            // Shared borrows can be taken while another shared borrow is active, but not while a mutable borrow is
+            if self.0.try_borrow().is_ok() {
+                BorrowMutError::AlreadyBorrowed(at.already_borrowed())
+            } else {
+                BorrowMutError::AlreadyBorrowedMut(at.already_borrowed_mut())
+            }
+        })?;
+        let borrow = RefMut::filter_map(borrow, |any| any.as_mut()?.downcast_mut())
+            .map_err(|_| BorrowMutError::Dropped(at.dropped()))?;
+        Ok(GenerationalRefMut::new(borrow, at.mutable()))
+    }
+
+    fn set(&self, value: T) {
+        *self.0.borrow_mut() = Some(Box::new(value));
+    }
+}
+
+/// Storage that can be shared between threads. This is backed by a [`RwLock`], so `read` and `write` block until the
+/// value is available instead of panicking. `try_read` and `try_write` never block.
+#[derive(Default)]
+pub struct SyncStorage(RwLock<Option<Box<dyn Any + Send + Sync>>>);
+
+impl AnyStorage for SyncStorage {
+    type Ref<T: ?Sized + 'static> = GenerationalRef<MappedRwLockReadGuard<'static, T>>;
+    type Mut<T: ?Sized + 'static> = GenerationalRefMut<MappedRwLockWriteGuard<'static, T>>;
+
+    fn try_map<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Ref<T>,
+        f: impl FnOnce(&T) -> Option<&U>,
+    ) -> Option<Self::Ref<U>> {
+        borrow.try_map(|borrow| MappedRwLockReadGuard::try_map(borrow, f).ok())
+    }
+
+    fn try_map_mut<T: ?Sized + 'static, U: ?Sized + 'static>(
+        borrow: Self::Mut<T>,
+        f: impl FnOnce(&mut T) -> Option<&mut U>,
+    ) -> Option<Self::Mut<U>> {
+        borrow.try_map(|borrow| MappedRwLockWriteGuard::try_map(borrow, f).ok())
+    }
+
+    fn take(&self) -> bool {
+        self.0.write().take().is_some()
+    }
+}
+
+impl SyncStorage {
+    fn map_read<T: 'static>(
+        guard: RwLockReadGuard<'static, Option<Box<dyn Any + Send + Sync>>>,
+        at: BorrowLocation,
+    ) -> Result<GenerationalRef<MappedRwLockReadGuard<'static, T>>, BorrowError> {
+        let borrow = RwLockReadGuard::try_map(guard, |any| any.as_ref()?.downcast_ref())
+            .map_err(|_| BorrowError::Dropped(at.dropped()))?;
+        Ok(GenerationalRef::new(borrow, at.shared()))
+    }
+
+    fn map_write<T: 'static>(
+        guard: RwLockWriteGuard<'static, Option<Box<dyn Any + Send + Sync>>>,
+        at: BorrowLocation,
+    ) -> Result<GenerationalRefMut<MappedRwLockWriteGuard<'static, T>>, BorrowMutError> {
+        let borrow = RwLockWriteGuard::try_map(guard, |any| any.as_mut()?.downcast_mut())
+            .map_err(|_| BorrowMutError::Dropped(at.dropped()))?;
+        Ok(GenerationalRefMut::new(borrow, at.mutable()))
+    }
+}
+
+impl<T: Send + Sync + 'static> Storage<T> for SyncStorage {
+    fn try_read(&'static self, at: BorrowLocation) -> Result<Self::Ref<T>, BorrowError> {
+        let guard = self
+            .0
+            .try_read()
+            .ok_or_else(|| BorrowError::AlreadyBorrowedMut(at.already_borrowed_mut()))?;
+        Self::map_read(guard, at)
+    }
+
+    fn try_write(&'static self, at: BorrowLocation) -> Result<Self::Mut<T>, BorrowMutError> {
+        let guard = self.0.try_write().ok_or_else(|| {
+            // Shared borrows can be taken while another shared borrow is active, but not while a mutable borrow is
+            if self.0.try_read().is_some() {
+                BorrowMutError::AlreadyBorrowed(at.already_borrowed())
+            } else {
+                BorrowMutError::AlreadyBorrowedMut(at.already_borrowed_mut())
+            }
+        })?;
+        Self::map_write(guard, at)
+    }
+
+    fn read(&'static self, at: BorrowLocation) -> Result<Self::Ref<T>, BorrowError> {
+        Self::map_read(self.0.read(), at)
+    }
+
+    fn write(&'static self, at: BorrowLocation) -> Result<Self::Mut<T>, BorrowMutError> {
+        Self::map_write(self.0.write(), at)
+    }
+
+    fn set(&self, value: T) {
+        *self.0.write() = Some(Box::new(value));
+    }
+}
+
+struct MemoryLocationInner<S> {
+    data: S,
+    generation: AtomicU32,
+    borrow: MemoryLocationBorrowInfo,
+}
+
+struct MemoryLocation<S: 'static>(&'static MemoryLocationInner<S>);
+
+impl<S: 'static> Clone for MemoryLocation<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: 'static> Copy for MemoryLocation<S> {}
+
+impl<S: AnyStorage> MemoryLocation<S> {
+    fn new() -> Self {
+        Self(Box::leak(Box::new(MemoryLocationInner {
+            data: S::default(),
+            generation: AtomicU32::new(0),
+            borrow: Default::default(),
+        })))
+    }
+
+    fn data_ptr(&self) -> *const S {
+        &self.0.data
+    }
+
     #[allow(unused)]
     fn drop(&self) {
-        let old = self.data.borrow_mut().take();
+        let old = self.0.data.take();
         #[cfg(any(debug_assertions, feature = "check_generation"))]
-        if old.is_some() {
-            drop(old);
-            let new_generation = self.generation.get() + 1;
-            self.generation.set(new_generation);
+        if old {
+            self.0.generation.fetch_add(1, Ordering::AcqRel);
         }
     }
 
-    fn replace<T: 'static>(&mut self, value: T) -> GenerationalBox<T> {
-        let mut inner_mut = self.data.borrow_mut();
+    #[track_caller]
+    fn replace<T: 'static>(&mut self, value: T) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
+        assert!(!self.0.data.take());
+        self.0.data.set(value);
+        self.key()
+    }
 
-        let raw = Box::new(value);
-        let old = inner_mut.replace(raw);
-        assert!(old.is_none());
+    #[track_caller]
+    fn key<T>(&self) -> GenerationalBox<T, S> {
         GenerationalBox {
             raw: *self,
             #[cfg(any(debug_assertions, feature = "check_generation"))]
-            generation: self.generation.get(),
+            generation: self.0.generation.load(Ordering::Acquire),
+            #[cfg(feature = "debug_borrows")]
+            created_at: Location::caller(),
             _marker: PhantomData,
         }
     }
 }
 
 /// Handles recycling generational boxes that have been dropped. Your application should have one store or one store per thread.
-#[derive(Clone)]
-pub struct Store {
-    bump: &'static Bump,
-    recycled: Rc<RefCell<Vec<MemoryLocation>>>,
+///
+/// The storage of the store decides which threads the boxes it creates can be used on. Use `Store::<SyncStorage>` to
+/// create boxes that can be shared between threads.
+pub struct Store<S: 'static = UnsyncStorage> {
+    recycled: Rc<RefCell<Vec<MemoryLocation<S>>>>,
+}
+
+impl<S: 'static> Clone for Store<S> {
+    fn clone(&self) -> Self {
+        Self {
+            recycled: self.recycled.clone(),
+        }
+    }
 }
 
 impl Default for Store {
     fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: AnyStorage> Store<S> {
+    /// Create a new store. `Store::default()` creates a store for the current thread.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
         Self {
-            bump: Box::leak(Box::new(Bump::new())),
             recycled: Default::default(),
         }
     }
-}
 
-impl Store {
-    fn recycle(&self, location: MemoryLocation) {
+    fn recycle(&self, location: MemoryLocation<S>, owner: &'static Location<'static>) {
+        location.0.borrow.drop_value(owner);
         location.drop();
         self.recycled.borrow_mut().push(location);
     }
 
-    fn claim(&self) -> MemoryLocation {
+    fn claim(&self) -> MemoryLocation<S> {
         if let Some(location) = self.recycled.borrow_mut().pop() {
             location
         } else {
-            let data: &'static RefCell<_> = self.bump.alloc(RefCell::new(None));
-            MemoryLocation {
-                data,
-                #[cfg(any(debug_assertions, feature = "check_generation"))]
-                generation: self.bump.alloc(Cell::new(0)),
-            }
+            MemoryLocation::new()
         }
     }
 
     /// Create a new owner. The owner will be responsible for dropping all of the generational boxes that it creates.
-    pub fn owner(&self) -> Owner {
+    #[track_caller]
+    pub fn owner(&self) -> Owner<S> {
         Owner {
             store: self.clone(),
             owned: Default::default(),
+            created_at: Location::caller(),
         }
     }
 }
 
 /// Owner: Handles dropping generational boxes. The owner acts like a runtime lifetime guard. Any states that you create with an owner will be dropped when that owner is dropped.
-pub struct Owner {
-    store: Store,
-    owned: Rc<RefCell<Vec<MemoryLocation>>>,
+pub struct Owner<S: AnyStorage = UnsyncStorage> {
+    store: Store<S>,
+    owned: Rc<RefCell<Vec<MemoryLocation<S>>>>,
+    created_at: &'static Location<'static>,
 }
 
-impl Owner {
+impl<S: AnyStorage> Owner<S> {
     /// Insert a value into the store. The value will be dropped when the owner is dropped.
-    pub fn insert<T: 'static>(&self, value: T) -> GenerationalBox<T> {
+    #[track_caller]
+    pub fn insert<T: 'static>(&self, value: T) -> GenerationalBox<T, S>
+    where
+        S: Storage<T>,
+    {
         let mut location = self.store.claim();
         let key = location.replace(value);
         self.owned.borrow_mut().push(location);
@@ -339,21 +768,16 @@ impl Owner {
     }
 
     /// Creates an invalid handle. This is useful for creating a handle that will be filled in later. If you use this before the value is filled in, you will get may get a panic or an out of date value.
-    pub fn invalid<T: 'static>(&self) -> GenerationalBox<T> {
-        let location = self.store.claim();
-        GenerationalBox {
-            raw: location,
-            #[cfg(any(debug_assertions, feature = "check_generation"))]
-            generation: location.generation.get(),
-            _marker: PhantomData,
-        }
+    #[track_caller]
+    pub fn invalid<T: 'static>(&self) -> GenerationalBox<T, S> {
+        self.store.claim().key()
     }
 }
 
-impl Drop for Owner {
+impl<S: AnyStorage> Drop for Owner<S> {
     fn drop(&mut self) {
         for location in self.owned.borrow().iter() {
-            self.store.recycle(*location)
+            self.store.recycle(*location, self.created_at)
         }
     }
 }
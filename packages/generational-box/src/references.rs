@@ -0,0 +1,203 @@
+use std::ops::{Deref, DerefMut};
+use std::panic::Location;
+
+use crate::{AlreadyBorrowedError, AlreadyBorrowedMutError, ValueDroppedError};
+
+/// The locations of the borrows of a memory location that are currently active
+#[derive(Debug, Default)]
+pub(crate) struct MemoryLocationBorrowInfo {
+    #[cfg(feature = "debug_borrows")]
+    borrowed_at: parking_lot::RwLock<Vec<&'static Location<'static>>>,
+    #[cfg(feature = "debug_borrows")]
+    borrowed_mut_at: parking_lot::RwLock<Option<&'static Location<'static>>>,
+    #[cfg(feature = "debug_borrows")]
+    dropped_at: parking_lot::RwLock<Option<&'static Location<'static>>>,
+}
+
+impl MemoryLocationBorrowInfo {
+    /// Remember the owner that dropped the value in the memory location
+    #[allow(unused)]
+    pub(crate) fn drop_value(&self, owner: &'static Location<'static>) {
+        #[cfg(feature = "debug_borrows")]
+        {
+            *self.dropped_at.write() = Some(owner);
+        }
+    }
+}
+
+/// Where a value is being borrowed from. [`crate::Storage`] implementations use this to build errors and to remember
+/// the borrow for as long as the reference they return is alive.
+///
+/// The locations are only recorded with the `debug_borrows` feature. Without it, this type is empty.
+#[derive(Debug, Clone, Copy)]
+pub struct BorrowLocation {
+    #[cfg(feature = "debug_borrows")]
+    created_at: &'static Location<'static>,
+    #[cfg(feature = "debug_borrows")]
+    borrowed_at: &'static Location<'static>,
+    #[cfg(feature = "debug_borrows")]
+    borrowed_from: &'static MemoryLocationBorrowInfo,
+}
+
+impl BorrowLocation {
+    #[allow(unused)]
+    pub(crate) fn new(
+        created_at: &'static Location<'static>,
+        borrowed_at: &'static Location<'static>,
+        borrowed_from: &'static MemoryLocationBorrowInfo,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "debug_borrows")]
+            created_at,
+            #[cfg(feature = "debug_borrows")]
+            borrowed_at,
+            #[cfg(feature = "debug_borrows")]
+            borrowed_from,
+        }
+    }
+
+    /// Remember a shared borrow until the returned guard is dropped
+    pub(crate) fn shared(self) -> GenerationalRefBorrowInfo {
+        #[cfg(feature = "debug_borrows")]
+        self.borrowed_from
+            .borrowed_at
+            .write()
+            .push(self.borrowed_at);
+        GenerationalRefBorrowInfo(self)
+    }
+
+    /// Remember a mutable borrow until the returned guard is dropped
+    pub(crate) fn mutable(self) -> GenerationalRefMutBorrowInfo {
+        #[cfg(feature = "debug_borrows")]
+        {
+            *self.borrowed_from.borrowed_mut_at.write() = Some(self.borrowed_at);
+        }
+        GenerationalRefMutBorrowInfo(self)
+    }
+
+    /// The error for a value that was dropped
+    pub(crate) fn dropped(&self) -> ValueDroppedError {
+        ValueDroppedError {
+            #[cfg(feature = "debug_borrows")]
+            created_at: self.created_at,
+            #[cfg(feature = "debug_borrows")]
+            dropped_at: *self.borrowed_from.dropped_at.read(),
+        }
+    }
+
+    /// The error for a value that is still borrowed mutably
+    pub(crate) fn already_borrowed_mut(&self) -> AlreadyBorrowedMutError {
+        AlreadyBorrowedMutError {
+            #[cfg(feature = "debug_borrows")]
+            borrowed_mut_at: *self.borrowed_from.borrowed_mut_at.read(),
+        }
+    }
+
+    /// The error for a value that is still borrowed
+    pub(crate) fn already_borrowed(&self) -> AlreadyBorrowedError {
+        AlreadyBorrowedError {
+            #[cfg(feature = "debug_borrows")]
+            borrowed_at: self.borrowed_from.borrowed_at.read().clone(),
+        }
+    }
+}
+
+/// Forgets a shared borrow when it is dropped
+pub(crate) struct GenerationalRefBorrowInfo(#[allow(unused)] BorrowLocation);
+
+#[cfg(feature = "debug_borrows")]
+impl Drop for GenerationalRefBorrowInfo {
+    fn drop(&mut self) {
+        let mut borrowed_at = self.0.borrowed_from.borrowed_at.write();
+        if let Some(index) = borrowed_at
+            .iter()
+            .position(|location| std::ptr::eq(*location, self.0.borrowed_at))
+        {
+            borrowed_at.swap_remove(index);
+        }
+    }
+}
+
+/// Forgets a mutable borrow when it is dropped
+pub(crate) struct GenerationalRefMutBorrowInfo(#[allow(unused)] BorrowLocation);
+
+#[cfg(feature = "debug_borrows")]
+impl Drop for GenerationalRefMutBorrowInfo {
+    fn drop(&mut self) {
+        self.0.borrowed_from.borrowed_mut_at.write().take();
+    }
+}
+
+/// A shared reference to a value in a [`crate::GenerationalBox`]. With the `debug_borrows` feature, the location of
+/// the borrow is remembered until the reference is dropped.
+pub struct GenerationalRef<R> {
+    inner: R,
+    // Forgets the borrow when the reference is dropped
+    #[allow(unused)]
+    borrow: GenerationalRefBorrowInfo,
+}
+
+impl<R> GenerationalRef<R> {
+    pub(crate) fn new(inner: R, borrow: GenerationalRefBorrowInfo) -> Self {
+        Self { inner, borrow }
+    }
+
+    /// Map the inner reference, keeping track of the original borrow
+    pub(crate) fn try_map<R2>(
+        self,
+        f: impl FnOnce(R) -> Option<R2>,
+    ) -> Option<GenerationalRef<R2>> {
+        Some(GenerationalRef {
+            inner: f(self.inner)?,
+            borrow: self.borrow,
+        })
+    }
+}
+
+impl<R: Deref> Deref for GenerationalRef<R> {
+    type Target = R::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+/// A mutable reference to a value in a [`crate::GenerationalBox`]. With the `debug_borrows` feature, the location of
+/// the borrow is remembered until the reference is dropped.
+pub struct GenerationalRefMut<W> {
+    inner: W,
+    // Forgets the borrow when the reference is dropped
+    #[allow(unused)]
+    borrow: GenerationalRefMutBorrowInfo,
+}
+
+impl<W> GenerationalRefMut<W> {
+    pub(crate) fn new(inner: W, borrow: GenerationalRefMutBorrowInfo) -> Self {
+        Self { inner, borrow }
+    }
+
+    /// Map the inner reference, keeping track of the original borrow
+    pub(crate) fn try_map<W2>(
+        self,
+        f: impl FnOnce(W) -> Option<W2>,
+    ) -> Option<GenerationalRefMut<W2>> {
+        Some(GenerationalRefMut {
+            inner: f(self.inner)?,
+            borrow: self.borrow,
+        })
+    }
+}
+
+impl<W: Deref> Deref for GenerationalRefMut<W> {
+    type Target = W::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<W: DerefMut> DerefMut for GenerationalRefMut<W> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
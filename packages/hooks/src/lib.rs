@@ -69,6 +69,9 @@ pub use use_state::{use_state, UseState};
 mod use_ref;
 pub use use_ref::*;
 
+mod use_collection;
+pub use use_collection::*;
+
 mod use_shared_state;
 pub use use_shared_state::*;
 
@@ -78,6 +81,9 @@ pub use use_coroutine::*;
 mod use_future;
 pub use use_future::*;
 
+mod use_query;
+pub use use_query::*;
+
 mod use_effect;
 pub use use_effect::*;
 
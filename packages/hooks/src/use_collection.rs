@@ -1,76 +1,827 @@
-/*
-a form of use_state explicitly for map-style collections (BTreeMap, HashMap, etc).
-
-Why?
----
-Traditionally, it's possible to use the "use_state" hook for collections in the React world.
-Adding a new entry would look something similar to:
-
-```js
-let (map, set_map) = useState({});
-set_map({ ...map, [key]: value });
-```
-The new value then causes the appropriate update when passed into children.
-
-This is moderately efficient because the fields of the map are moved, but the data itself is not cloned.
-However, if you used similar approach with Dioxus:
-
-```rust
-let (map, set_map) = use_state(cx, || HashMap::new());
-set_map({
-    let mut newmap = map.clone();
-    newmap.set(key, value);
-    newmap
-})
-```
-Unfortunately, you'd be cloning the entire state every time a value is changed. The obvious solution is to
-wrap every element in the HashMap with an Rc. That way, cloning the HashMap is on par with its JS equivalent.
-
-Fortunately, we can make this operation even more efficient in Dioxus, leveraging the borrow rules of Rust.
-
-This hook provides a memoized collection, memoized setters, and memoized getters. This particular hook is
-extremely powerful for implementing lists and supporting core state management needs for small apps.
-
-If you need something even more powerful, check out the dedicated atomic state management Dioxus Dataflow, which
-uses the same memoization on top of the use_context API.
-
-Here's a fully-functional todo app using the use_map API:
-```rust
-static TodoList: Component = |cx| {
-    let todos = use_map(cx, || HashMap::new());
-    let input = use_ref(|| None);
-
-    cx.render(rsx!{
-        div {
-            button {
-                "Add todo"
-                onclick: move |_| {
-                    let new_todo = TodoItem::new(input.contents());
-                    todos.insert(new_todo.id.clone(), new_todo);
-                    input.clear();
-                }
-            }
-            button {
-                "Clear todos"
-                onclick: move |_| todos.clear()
-            }
-            input {
-                placeholder: "What needs to be done?"
-                ref: input
-            }
-            ul {
-                {todos.iter().map(|todo| rsx!(
-                    li {
-                        key: todo.id
-                        span { "{todo.content}" }
-                        button {"x", onclick: move |_| todos.remove(todo.key.clone())}
-                    }
-                ))}
-            }
+//! Hooks for collections that memoize every entry of the collection.
+//!
+//! Storing a collection in `use_state` means cloning the whole collection every time a single entry changes.
+//! `use_ref` avoids the clone, but every child that receives the collection rerenders when anything in it changes.
+//!
+//! [`use_map`], [`use_set`] and [`use_vec`] mutate the collection in place and keep a generation for every entry.
+//! The entry handles they hand out only compare unequal once their entry changed, so children that take an entry as a
+//! prop are skipped when another part of the collection is updated.
+
+use dioxus_core::ScopeState;
+use std::{
+    cell::{Ref, RefCell},
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// The value of a collection and the generation of each of its entries
+struct Collection<C, G> {
+    value: C,
+    entries: G,
+    generation: usize,
+}
+
+impl<C, G> Collection<C, G> {
+    /// Get a new generation that is different from every generation handed out before
+    fn next_generation(&mut self) -> usize {
+        self.generation += 1;
+        self.generation
+    }
+}
+
+type Shared<C, G> = Rc<RefCell<Collection<C, G>>>;
+
+/// `use_map` stores a [`HashMap`] in the component and memoizes each of its entries.
+///
+/// Changing one entry does not clone the map. [`UseMap::entry`] returns a handle to a single entry that can be passed
+/// to a child component. The handle only compares unequal to the handle from the last render if that entry changed,
+/// so only the child that renders the changed entry rerenders.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use std::collections::HashMap;
+/// fn TodoList(cx: Scope) -> Element {
+///     let todos = use_map(cx, || HashMap::from([(0, "Write the docs".to_string())]));
+///
+///     render! {
+///         button {
+///             onclick: move |_| {
+///                 let id = todos.len();
+///                 todos.insert(id, "New todo".to_string());
+///             },
+///             "Add todo"
+///         }
+///         ul {
+///             for id in todos.keys() {
+///                 TodoItem { key: "{id}", todo: todos.entry(id) }
+///             }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn TodoItem(cx: Scope, todo: MapEntry<usize, String>) -> Element {
+///     let title = todo.read()?.clone();
+///
+///     render! {
+///         li {
+///             "{title}"
+///             button { onclick: move |_| { todo.remove(); }, "x" }
+///         }
+///     }
+/// }
+/// ```
+pub fn use_map<K: Hash + Eq + Clone + 'static, V: 'static>(
+    cx: &ScopeState,
+    initialize_map: impl FnOnce() -> HashMap<K, V>,
+) -> &UseMap<K, V> {
+    let hook = cx.use_hook(|| {
+        let value = initialize_map();
+        let entries: HashMap<K, usize> = value.keys().cloned().zip(1..).collect();
+        let generation = entries.len();
+        UseMap {
+            state: Rc::new(RefCell::new(Collection {
+                value,
+                entries,
+                generation,
+            })),
+            update: cx.schedule_update(),
+            generation,
+        }
+    });
+    hook.generation = hook.state.borrow().generation;
+    hook
+}
+
+/// A type created by the [`use_map`] hook. See its documentation for more details.
+pub struct UseMap<K, V> {
+    state: Shared<HashMap<K, V>, HashMap<K, usize>>,
+    update: Arc<dyn Fn()>,
+    generation: usize,
+}
+
+impl<K, V> Clone for UseMap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> UseMap<K, V> {
+    /// Read the whole map. Writing to the map while the `Ref` is alive will panic.
+    pub fn read(&self) -> Ref<'_, HashMap<K, V>> {
+        Ref::map(self.state.borrow(), |state| &state.value)
+    }
+
+    /// Get a reference to the value of a key without cloning it
+    pub fn get(&self, key: &K) -> Option<Ref<'_, V>> {
+        Ref::filter_map(self.state.borrow(), |state| state.value.get(key)).ok()
+    }
+
+    /// Returns true if the map contains the key
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.state.borrow().value.contains_key(key)
+    }
+
+    /// The number of entries in the map
+    pub fn len(&self) -> usize {
+        self.state.borrow().value.len()
+    }
+
+    /// Returns true if the map has no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clone the keys of the map. This is useful to render an entry for each key.
+    pub fn keys(&self) -> Vec<K> {
+        self.state.borrow().value.keys().cloned().collect()
+    }
+
+    /// Get a memoized handle to the entry for a key. The entry does not need to exist yet.
+    pub fn entry(&self, key: K) -> MapEntry<K, V> {
+        let generation = self.state.borrow().entries.get(&key).copied();
+        MapEntry {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            key,
+            generation,
+        }
+    }
+
+    /// Insert a value into the map and mark the component as dirty. Returns the old value of the key.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let old = {
+            let mut state = self.state.borrow_mut();
+            let generation = state.next_generation();
+            state.entries.insert(key.clone(), generation);
+            state.value.insert(key, value)
+        };
+        (self.update)();
+        old
+    }
+
+    /// Remove a key from the map. The component is only marked as dirty if the key existed.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let old = {
+            let mut state = self.state.borrow_mut();
+            let old = state.value.remove(key)?;
+            state.entries.remove(key);
+            state.next_generation();
+            old
+        };
+        (self.update)();
+        Some(old)
+    }
+
+    /// Remove every entry from the map and mark the component as dirty
+    pub fn clear(&self) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.value.clear();
+            state.entries.clear();
+            state.next_generation();
+        }
+        (self.update)();
+    }
+
+    /// Modify the whole map in place. Every entry is treated as changed, so prefer [`UseMap::entry`] or
+    /// [`UseMap::insert`] when only a few entries change.
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut HashMap<K, V>) -> O) -> O {
+        let output = {
+            let mut state = self.state.borrow_mut();
+            let output = f(&mut state.value);
+            let start = state.generation + 1;
+            let entries: HashMap<K, usize> = state.value.keys().cloned().zip(start..).collect();
+            state.generation += entries.len() + 1;
+            state.entries = entries;
+            output
+        };
+        (self.update)();
+        output
+    }
+}
+
+// UseMap memoizes on the generation of the whole map. It changes whenever any entry changes.
+impl<K, V> PartialEq for UseMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state) && self.generation == other.generation
+    }
+}
+
+/// A handle to one entry of a [`UseMap`]. Handles for the same key are equal until the value of that key changes.
+pub struct MapEntry<K, V> {
+    state: Shared<HashMap<K, V>, HashMap<K, usize>>,
+    update: Arc<dyn Fn()>,
+    key: K,
+    generation: Option<usize>,
+}
+
+impl<K: Clone, V> Clone for MapEntry<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            key: self.key.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> MapEntry<K, V> {
+    /// The key of the entry
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Read the value of the entry if it exists
+    pub fn read(&self) -> Option<Ref<'_, V>> {
+        Ref::filter_map(self.state.borrow(), |state| state.value.get(&self.key)).ok()
+    }
+
+    /// Set the value of the entry and mark the component that owns the map as dirty
+    pub fn set(&self, value: V) {
+        {
+            let mut state = self.state.borrow_mut();
+            let generation = state.next_generation();
+            state.entries.insert(self.key.clone(), generation);
+            state.value.insert(self.key.clone(), value);
+        }
+        (self.update)();
+    }
+
+    /// Modify the value of the entry in place if it exists. Only this entry is marked as changed.
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut V) -> O) -> Option<O> {
+        let output = {
+            let mut state = self.state.borrow_mut();
+            let output = f(state.value.get_mut(&self.key)?);
+            let generation = state.next_generation();
+            state.entries.insert(self.key.clone(), generation);
+            output
+        };
+        (self.update)();
+        Some(output)
+    }
+
+    /// Remove the entry from the map
+    pub fn remove(&self) -> Option<V> {
+        let old = {
+            let mut state = self.state.borrow_mut();
+            let old = state.value.remove(&self.key)?;
+            state.entries.remove(&self.key);
+            state.next_generation();
+            old
+        };
+        (self.update)();
+        Some(old)
+    }
+}
+
+impl<K: PartialEq, V> PartialEq for MapEntry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+            && self.key == other.key
+            && self.generation == other.generation
+    }
+}
+
+/// `use_set` stores a [`HashSet`] in the component and memoizes the membership of each value.
+///
+/// [`UseSet::entry`] returns a handle that only compares unequal to the handle from the last render if that value
+/// was inserted or removed. This is useful for selections in large lists.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use std::collections::HashSet;
+/// fn Rows(cx: Scope) -> Element {
+///     let selected = use_set(cx, HashSet::<usize>::new);
+///
+///     render! {
+///         for id in 0..1000 {
+///             Row { key: "{id}", selected: selected.entry(id) }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Row(cx: Scope, selected: SetEntry<usize>) -> Element {
+///     let class = if selected.contains() { "selected" } else { "" };
+///     let id = selected.value();
+///
+///     render! {
+///         div { class: class, onclick: move |_| selected.toggle(), "{id}" }
+///     }
+/// }
+/// ```
+pub fn use_set<T: Hash + Eq + Clone + 'static>(
+    cx: &ScopeState,
+    initialize_set: impl FnOnce() -> HashSet<T>,
+) -> &UseSet<T> {
+    let hook = cx.use_hook(|| {
+        let value = initialize_set();
+        let entries: HashMap<T, usize> = value.iter().cloned().zip(1..).collect();
+        let generation = entries.len();
+        UseSet {
+            state: Rc::new(RefCell::new(Collection {
+                value,
+                entries,
+                generation,
+            })),
+            update: cx.schedule_update(),
+            generation,
         }
-    })
+    });
+    hook.generation = hook.state.borrow().generation;
+    hook
 }
 
-```
+/// A type created by the [`use_set`] hook. See its documentation for more details.
+pub struct UseSet<T> {
+    state: Shared<HashSet<T>, HashMap<T, usize>>,
+    update: Arc<dyn Fn()>,
+    generation: usize,
+}
+
+impl<T> Clone for UseSet<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> UseSet<T> {
+    /// Read the whole set. Writing to the set while the `Ref` is alive will panic.
+    pub fn read(&self) -> Ref<'_, HashSet<T>> {
+        Ref::map(self.state.borrow(), |state| &state.value)
+    }
+
+    /// Returns true if the set contains the value
+    pub fn contains(&self, value: &T) -> bool {
+        self.state.borrow().value.contains(value)
+    }
+
+    /// The number of values in the set
+    pub fn len(&self) -> usize {
+        self.state.borrow().value.len()
+    }
+
+    /// Returns true if the set has no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a memoized handle to the membership of a value
+    pub fn entry(&self, value: T) -> SetEntry<T> {
+        let generation = self.state.borrow().entries.get(&value).copied();
+        SetEntry {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            value,
+            generation,
+        }
+    }
+
+    /// Insert a value into the set. The component is only marked as dirty if the value was not in the set yet.
+    pub fn insert(&self, value: T) -> bool {
+        insert_into_set(&self.state, value, &self.update)
+    }
+
+    /// Remove a value from the set. The component is only marked as dirty if the value was in the set.
+    pub fn remove(&self, value: &T) -> bool {
+        remove_from_set(&self.state, value, &self.update)
+    }
+
+    /// Remove every value from the set and mark the component as dirty
+    pub fn clear(&self) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.value.clear();
+            state.entries.clear();
+            state.next_generation();
+        }
+        (self.update)();
+    }
+}
+
+fn insert_into_set<T: Hash + Eq + Clone>(
+    state: &Shared<HashSet<T>, HashMap<T, usize>>,
+    value: T,
+    update: &Arc<dyn Fn()>,
+) -> bool {
+    {
+        let mut state = state.borrow_mut();
+        if !state.value.insert(value.clone()) {
+            return false;
+        }
+        let generation = state.next_generation();
+        state.entries.insert(value, generation);
+    }
+    update();
+    true
+}
+
+fn remove_from_set<T: Hash + Eq + Clone>(
+    state: &Shared<HashSet<T>, HashMap<T, usize>>,
+    value: &T,
+    update: &Arc<dyn Fn()>,
+) -> bool {
+    {
+        let mut state = state.borrow_mut();
+        if !state.value.remove(value) {
+            return false;
+        }
+        state.entries.remove(value);
+        state.next_generation();
+    }
+    update();
+    true
+}
 
-*/
+impl<T> PartialEq for UseSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state) && self.generation == other.generation
+    }
+}
+
+/// A handle to the membership of one value in a [`UseSet`]. Handles for the same value are equal until the value is
+/// inserted into or removed from the set.
+pub struct SetEntry<T> {
+    state: Shared<HashSet<T>, HashMap<T, usize>>,
+    update: Arc<dyn Fn()>,
+    value: T,
+    generation: Option<usize>,
+}
+
+impl<T: Clone> Clone for SetEntry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            value: self.value.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> SetEntry<T> {
+    /// The value this entry tracks
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns true if the value is in the set
+    pub fn contains(&self) -> bool {
+        self.state.borrow().value.contains(&self.value)
+    }
+
+    /// Insert the value into the set. Returns false if it was already in the set.
+    pub fn insert(&self) -> bool {
+        insert_into_set(&self.state, self.value.clone(), &self.update)
+    }
+
+    /// Remove the value from the set. Returns false if it was not in the set.
+    pub fn remove(&self) -> bool {
+        remove_from_set(&self.state, &self.value, &self.update)
+    }
+
+    /// Insert the value if it is not in the set, or remove it if it is
+    pub fn toggle(&self) {
+        if !self.remove() {
+            self.insert();
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for SetEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+            && self.value == other.value
+            && self.generation == other.generation
+    }
+}
+
+/// `use_vec` stores a [`Vec`] in the component and memoizes each of its items.
+///
+/// [`UseVec::entry`] returns a handle to the item at an index. The handle only compares unequal to the handle from the
+/// last render if the item at that index changed, including when items are inserted or removed before it.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// fn Counters(cx: Scope) -> Element {
+///     let counters = use_vec(cx, || vec![0; 100]);
+///
+///     render! {
+///         button { onclick: move |_| counters.push(0), "Add counter" }
+///         for index in 0..counters.len() {
+///             Counter { key: "{index}", count: counters.entry(index) }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn Counter(cx: Scope, count: VecEntry<i32>) -> Element {
+///     let value = *count.read()?;
+///
+///     render! {
+///         button { onclick: move |_| { count.with_mut(|count| *count += 1); }, "{value}" }
+///     }
+/// }
+/// ```
+pub fn use_vec<T: 'static>(cx: &ScopeState, initialize_vec: impl FnOnce() -> Vec<T>) -> &UseVec<T> {
+    let hook = cx.use_hook(|| {
+        let value = initialize_vec();
+        let entries: Vec<usize> = (1..=value.len()).collect();
+        let generation = entries.len();
+        UseVec {
+            state: Rc::new(RefCell::new(Collection {
+                value,
+                entries,
+                generation,
+            })),
+            update: cx.schedule_update(),
+            generation,
+        }
+    });
+    hook.generation = hook.state.borrow().generation;
+    hook
+}
+
+/// A type created by the [`use_vec`] hook. See its documentation for more details.
+pub struct UseVec<T> {
+    state: Shared<Vec<T>, Vec<usize>>,
+    update: Arc<dyn Fn()>,
+    generation: usize,
+}
+
+impl<T> Clone for UseVec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> UseVec<T> {
+    /// Read the whole vec. Writing to the vec while the `Ref` is alive will panic.
+    pub fn read(&self) -> Ref<'_, Vec<T>> {
+        Ref::map(self.state.borrow(), |state| &state.value)
+    }
+
+    /// Get a reference to the item at an index without cloning it
+    pub fn get(&self, index: usize) -> Option<Ref<'_, T>> {
+        Ref::filter_map(self.state.borrow(), |state| state.value.get(index)).ok()
+    }
+
+    /// The number of items in the vec
+    pub fn len(&self) -> usize {
+        self.state.borrow().value.len()
+    }
+
+    /// Returns true if the vec has no items
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get a memoized handle to the item at an index. The index does not need to exist yet.
+    pub fn entry(&self, index: usize) -> VecEntry<T> {
+        let generation = self.state.borrow().entries.get(index).copied();
+        VecEntry {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            index,
+            generation,
+        }
+    }
+
+    /// Set the item at an index and mark the component as dirty. Panics if the index is out of bounds.
+    pub fn set(&self, index: usize, value: T) {
+        self.entry(index).set(value)
+    }
+
+    /// Add an item to the end of the vec and mark the component as dirty
+    pub fn push(&self, value: T) {
+        {
+            let mut state = self.state.borrow_mut();
+            let generation = state.next_generation();
+            state.entries.push(generation);
+            state.value.push(value);
+        }
+        (self.update)();
+    }
+
+    /// Remove the last item of the vec. The component is only marked as dirty if the vec was not empty.
+    pub fn pop(&self) -> Option<T> {
+        let old = {
+            let mut state = self.state.borrow_mut();
+            let old = state.value.pop()?;
+            state.entries.pop();
+            state.next_generation();
+            old
+        };
+        (self.update)();
+        Some(old)
+    }
+
+    /// Insert an item at an index, shifting every item after it. Panics if the index is out of bounds.
+    pub fn insert(&self, index: usize, value: T) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.value.insert(index, value);
+            let generation = state.next_generation();
+            state.entries.insert(index, generation);
+        }
+        (self.update)();
+    }
+
+    /// Remove the item at an index, shifting every item after it. Panics if the index is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        let old = {
+            let mut state = self.state.borrow_mut();
+            let old = state.value.remove(index);
+            state.entries.remove(index);
+            state.next_generation();
+            old
+        };
+        (self.update)();
+        old
+    }
+
+    /// Remove every item from the vec and mark the component as dirty
+    pub fn clear(&self) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.value.clear();
+            state.entries.clear();
+            state.next_generation();
+        }
+        (self.update)();
+    }
+
+    /// Modify the whole vec in place. Every item is treated as changed, so prefer [`UseVec::entry`] or
+    /// [`UseVec::set`] when only a few items change.
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut Vec<T>) -> O) -> O {
+        let output = {
+            let mut state = self.state.borrow_mut();
+            let output = f(&mut state.value);
+            let start = state.generation + 1;
+            let entries: Vec<usize> = (start..start + state.value.len()).collect();
+            state.generation += entries.len() + 1;
+            state.entries = entries;
+            output
+        };
+        (self.update)();
+        output
+    }
+}
+
+impl<T> PartialEq for UseVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state) && self.generation == other.generation
+    }
+}
+
+/// A handle to the item at one index of a [`UseVec`]. Handles for the same index are equal until the item at that
+/// index changes.
+pub struct VecEntry<T> {
+    state: Shared<Vec<T>, Vec<usize>>,
+    update: Arc<dyn Fn()>,
+    index: usize,
+    generation: Option<usize>,
+}
+
+impl<T> Clone for VecEntry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            update: self.update.clone(),
+            index: self.index,
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> VecEntry<T> {
+    /// The index of the item
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Read the item if the index is in bounds
+    pub fn read(&self) -> Option<Ref<'_, T>> {
+        Ref::filter_map(self.state.borrow(), |state| state.value.get(self.index)).ok()
+    }
+
+    /// Set the item and mark the component that owns the vec as dirty. Panics if the index is out of bounds.
+    pub fn set(&self, value: T) {
+        {
+            let mut state = self.state.borrow_mut();
+            state.value[self.index] = value;
+            let generation = state.next_generation();
+            state.entries[self.index] = generation;
+        }
+        (self.update)();
+    }
+
+    /// Modify the item in place if the index is in bounds. Only this item is marked as changed.
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut T) -> O) -> Option<O> {
+        let output = {
+            let mut state = self.state.borrow_mut();
+            let output = f(state.value.get_mut(self.index)?);
+            let generation = state.next_generation();
+            state.entries[self.index] = generation;
+            output
+        };
+        (self.update)();
+        Some(output)
+    }
+
+    /// Remove the item from the vec, shifting every item after it. Returns None if the index is out of bounds.
+    pub fn remove(&self) -> Option<T> {
+        let old = {
+            let mut state = self.state.borrow_mut();
+            if self.index >= state.value.len() {
+                return None;
+            }
+            state.entries.remove(self.index);
+            state.next_generation();
+            state.value.remove(self.index)
+        };
+        (self.update)();
+        Some(old)
+    }
+}
+
+impl<T> PartialEq for VecEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.state, &other.state)
+            && self.index == other.index
+            && self.generation == other.generation
+    }
+}
+
+#[test]
+fn entries_are_memoized() {
+    use dioxus::prelude::render;
+    use dioxus_core::{Element, Scope, VirtualDom};
+
+    type Handles = (
+        Vec<MapEntry<usize, &'static str>>,
+        Vec<SetEntry<usize>>,
+        Vec<VecEntry<usize>>,
+    );
+
+    fn app(cx: Scope<Rc<RefCell<Vec<Handles>>>>) -> Element {
+        let map = use_map(cx, || HashMap::from([(0, "a"), (1, "b")]));
+        let set = use_set(cx, || HashSet::from([0]));
+        let vec = use_vec(cx, || vec![0, 1, 2]);
+
+        cx.props.borrow_mut().push((
+            vec![map.entry(0), map.entry(1)],
+            vec![set.entry(0), set.entry(1)],
+            (0..4).map(|index| vec.entry(index)).collect(),
+        ));
+
+        if cx.props.borrow().len() == 1 {
+            map.entry(0).set("c");
+            set.entry(1).toggle();
+            vec.insert(1, 3);
+        }
+
+        let len = map.len();
+        render! { "{len}" }
+    }
+
+    let handles = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(app, handles.clone());
+    let _ = dom.rebuild();
+    dom.process_events();
+    let _ = dom.render_immediate();
+
+    let handles = handles.borrow();
+    let ((map_before, set_before, vec_before), (map_after, set_after, vec_after)) =
+        (&handles[0], &handles[1]);
+
+    // Only the entries that changed compare unequal
+    assert!(map_before[0] != map_after[0]);
+    assert!(map_before[1] == map_after[1]);
+    assert!(set_before[0] == set_after[0]);
+    assert!(set_before[1] != set_after[1]);
+    assert!(vec_before[0] == vec_after[0]);
+    assert!(vec_before[1] != vec_after[1]);
+    assert!(vec_before[2] != vec_after[2]);
+    assert!(vec_before[3] != vec_after[3]);
+
+    assert_eq!(*map_after[0].read().unwrap(), "c");
+    assert!(set_after[1].contains());
+    assert_eq!(*vec_after[1].read().unwrap(), 3);
+}
@@ -0,0 +1,609 @@
+use dioxus_core::{ScopeId, ScopeState};
+use futures_timer::Delay;
+use instant::Instant;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    future::Future,
+    marker::PhantomData,
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+
+/// A key in the cache. Keys are compared by their serialized form, so keys of different types that serialize to the
+/// same value are equal.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct QueryKey(Rc<str>);
+
+impl QueryKey {
+    fn new<K: Serialize>(key: &K) -> Self {
+        match serde_json::to_string(key) {
+            Ok(key) => Self(key.into()),
+            Err(err) => panic!("Failed to serialize the query key: {err}"),
+        }
+    }
+
+    fn deserialize<K: DeserializeOwned>(&self) -> Option<K> {
+        serde_json::from_str(&self.0).ok()
+    }
+}
+
+/// How long an entry stays in the cache after the last component that uses it unmounts, unless configured otherwise
+const DEFAULT_CACHE_TIME: Duration = Duration::from_secs(5 * 60);
+
+struct QueryEntry {
+    /// The last result of the query. This is always a `Result<T, E>`
+    value: Option<Rc<dyn Any>>,
+    updated_at: Option<Instant>,
+    /// The scope that is running the request for this key, and the number of invalidations when it started. Only one
+    /// request runs for each key at a time
+    fetching: Option<(ScopeId, usize)>,
+    invalidated: bool,
+    /// How often the key was invalidated. If this changes while a request is running, the result of the request is
+    /// already stale when it arrives.
+    invalidations: usize,
+    subscribers: HashSet<ScopeId>,
+    /// When the last subscriber unsubscribed, or when the entry was created without a subscriber
+    unused_since: Option<Instant>,
+    cache_time: Duration,
+}
+
+impl Default for QueryEntry {
+    fn default() -> Self {
+        Self {
+            value: None,
+            updated_at: None,
+            fetching: None,
+            invalidated: false,
+            invalidations: 0,
+            subscribers: HashSet::new(),
+            unused_since: Some(Instant::now()),
+            cache_time: DEFAULT_CACHE_TIME,
+        }
+    }
+}
+
+impl QueryEntry {
+    fn is_stale(&self, stale_time: Duration) -> bool {
+        match self.updated_at {
+            Some(updated_at) => self.invalidated || updated_at.elapsed() >= stale_time,
+            None => true,
+        }
+    }
+
+    fn is_expired(&self) -> bool {
+        self.fetching.is_none()
+            && self.unused_since.map_or(false, |unused_since| {
+                unused_since.elapsed() >= self.cache_time
+            })
+    }
+}
+
+/// The cache shared by every [`use_query`] in the app. Get it with [`use_query_cache`].
+///
+/// Results are cached by their key. Invalidating a key reruns every component that uses it, and the first of those
+/// components to rerun fetches the key again.
+///
+/// Once no component uses a key anymore, its result is kept for the [cache time](QueryOptions::cache_time) and then
+/// removed the next time a component subscribes to a key or [`QueryCache::collect_garbage`] is called.
+#[derive(Clone)]
+pub struct QueryCache {
+    entries: Rc<RefCell<HashMap<QueryKey, QueryEntry>>>,
+    update_any: Arc<dyn Fn(ScopeId)>,
+}
+
+impl QueryCache {
+    /// Get the last result cached for a key, even if it is stale
+    pub fn get<K: Serialize, T: 'static, E: 'static>(&self, key: &K) -> Option<Rc<Result<T, E>>> {
+        self.get_any(&QueryKey::new(key))
+    }
+
+    /// Store a result for a key as if it was just fetched. This can be used to seed the cache with data that was
+    /// fetched on the server, or to update the cache with the response of a mutation.
+    pub fn set<K: Serialize, T: 'static, E: 'static>(&self, key: K, value: Result<T, E>) {
+        self.finish(&QueryKey::new(&key), None, Rc::new(value));
+    }
+
+    /// Mark a key as stale. Every component that uses the key reruns and the key is fetched again.
+    ///
+    /// If the key is being fetched, it is fetched again once the running request finishes.
+    pub fn invalidate<K: Serialize>(&self, key: &K) {
+        self.invalidate_any(&QueryKey::new(key));
+    }
+
+    /// Mark every key that deserializes into a `K` and matches a filter as stale
+    ///
+    /// ```rust, no_run
+    /// # use dioxus::prelude::*;
+    /// # fn app(cx: Scope) -> Element {
+    /// let cache = use_query_cache(cx);
+    /// // Invalidate every user, but not the other queries keyed by (kind, id) tuples
+    /// cache.invalidate_matching(|(kind, _): &(String, u32)| kind == "user");
+    /// # None
+    /// # }
+    /// ```
+    pub fn invalidate_matching<K: DeserializeOwned>(&self, filter: impl Fn(&K) -> bool) {
+        let keys: Vec<_> = self
+            .entries
+            .borrow()
+            .keys()
+            .filter(|key| key.deserialize::<K>().map_or(false, |key| filter(&key)))
+            .cloned()
+            .collect();
+        for key in keys {
+            self.invalidate_any(&key);
+        }
+    }
+
+    /// Mark every key in the cache as stale
+    pub fn invalidate_all(&self) {
+        let keys: Vec<_> = self.entries.borrow().keys().cloned().collect();
+        for key in keys {
+            self.invalidate_any(&key);
+        }
+    }
+
+    fn get_any<T: 'static, E: 'static>(&self, key: &QueryKey) -> Option<Rc<Result<T, E>>> {
+        let value = self.entries.borrow().get(key)?.value.clone()?;
+        value.downcast().ok()
+    }
+
+    /// Remove the entries that no component used for longer than their cache time
+    pub fn collect_garbage(&self) {
+        self.entries
+            .borrow_mut()
+            .retain(|_, entry| !entry.is_expired());
+    }
+
+    fn invalidate_any(&self, key: &QueryKey) {
+        let subscribers = {
+            let mut entries = self.entries.borrow_mut();
+            let entry = match entries.get_mut(key) {
+                Some(entry) => entry,
+                None => return,
+            };
+            entry.invalidated = true;
+            entry.invalidations += 1;
+            entry.subscribers.clone()
+        };
+        for scope in subscribers {
+            (self.update_any)(scope);
+        }
+    }
+
+    fn subscribe(&self, key: &QueryKey, scope: ScopeId) {
+        self.collect_garbage();
+        let mut entries = self.entries.borrow_mut();
+        let entry = entries.entry(key.clone()).or_default();
+        entry.subscribers.insert(scope);
+        entry.unused_since = None;
+    }
+
+    fn unsubscribe(&self, key: &QueryKey, scope: ScopeId, cache_time: Duration) {
+        let orphaned = {
+            let mut entries = self.entries.borrow_mut();
+            let entry = match entries.get_mut(key) {
+                Some(entry) => entry,
+                None => return,
+            };
+            entry.subscribers.remove(&scope);
+            if entry.subscribers.is_empty() {
+                entry.unused_since = Some(Instant::now());
+                entry.cache_time = cache_time;
+            }
+            // The request is cancelled with the scope that ran it, so another subscriber needs to fetch the key
+            if entry.fetching.map(|(fetching, _)| fetching) == Some(scope) {
+                entry.fetching = None;
+                entry.subscribers.clone()
+            } else {
+                HashSet::new()
+            }
+        };
+        for scope in orphaned {
+            (self.update_any)(scope);
+        }
+    }
+
+    /// Claim the request for a key if no other scope is fetching it and the key was never fetched, was invalidated,
+    /// or is stale when `check_stale` is true
+    fn try_start(
+        &self,
+        key: &QueryKey,
+        scope: ScopeId,
+        stale_time: Duration,
+        check_stale: bool,
+    ) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        let entry = entries.entry(key.clone()).or_default();
+        let needs_fetch = entry.value.is_none()
+            || entry.invalidated
+            || (check_stale && entry.is_stale(stale_time));
+        if entry.fetching.is_some() || !needs_fetch {
+            return false;
+        }
+        entry.fetching = Some((scope, entry.invalidations));
+        true
+    }
+
+    /// Store the result of a request, or a value that was set directly if `scope` is `None`
+    fn finish(&self, key: &QueryKey, scope: Option<ScopeId>, value: Rc<dyn Any>) {
+        let subscribers = {
+            let mut entries = self.entries.borrow_mut();
+            let entry = entries.entry(key.clone()).or_default();
+            // If the key was invalidated while the request was running, the result is stale and the subscribers
+            // fetch it again when they rerun
+            let mut invalidated = false;
+            if let Some((fetching, invalidations)) = entry.fetching {
+                if Some(fetching) == scope {
+                    entry.fetching = None;
+                    invalidated = entry.invalidations != invalidations;
+                }
+            }
+            entry.value = Some(value);
+            entry.updated_at = Some(Instant::now());
+            entry.invalidated = invalidated;
+            entry.subscribers.clone()
+        };
+        for scope in subscribers {
+            (self.update_any)(scope);
+        }
+    }
+
+    fn is_fetching(&self, key: &QueryKey) -> bool {
+        self.entries
+            .borrow()
+            .get(key)
+            .map_or(false, |entry| entry.fetching.is_some())
+    }
+
+    fn is_stale(&self, key: &QueryKey, stale_time: Duration) -> bool {
+        self.entries
+            .borrow()
+            .get(key)
+            .map_or(true, |entry| entry.is_stale(stale_time))
+    }
+}
+
+/// Get the [`QueryCache`] shared by every query in the app. The cache is created in the root scope the first time it
+/// is used.
+pub fn use_query_cache(cx: &ScopeState) -> &QueryCache {
+    cx.use_hook(|| {
+        cx.consume_context::<QueryCache>().unwrap_or_else(|| {
+            cx.provide_root_context(QueryCache {
+                entries: Default::default(),
+                update_any: cx.schedule_update_any(),
+            })
+        })
+    })
+}
+
+/// Options for [`use_query_with_options`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryOptions {
+    /// How long a result is fresh after it was fetched. Components that mount while the result is stale fetch it
+    /// again. Defaults to zero, so every component that mounts fetches the key again unless a request is already
+    /// running.
+    pub stale_time: Duration,
+    /// Fetch the key again on an interval while the component is mounted
+    pub refetch_interval: Option<Duration>,
+    /// How long the result is kept in the cache after the last component that uses the key unmounts. Defaults to five
+    /// minutes.
+    pub cache_time: Duration,
+}
+
+impl Default for QueryOptions {
+    fn default() -> Self {
+        Self {
+            stale_time: Duration::ZERO,
+            refetch_interval: None,
+            cache_time: DEFAULT_CACHE_TIME,
+        }
+    }
+}
+
+/// Fetch a value and share it with every other component that queries the same key.
+///
+/// Results are stored in the [`QueryCache`]. If ten components query the same key at the same time, the fetcher only
+/// runs once and all of them rerender when it resolves. Use [`use_query_with_options`] to control how long results
+/// stay fresh and to refetch on an interval.
+///
+/// - key: a value that identifies the request. Any serializable type works. Keys are compared by their serialized
+///   form, so `("user", 1)` and `("user".to_string(), 1)` are the same key.
+/// - fetcher: creates the request for the key. It is only called when the key needs to be fetched.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # async fn fetch_user(id: u32) -> Result<String, String> { todo!() }
+/// # async fn rename_user(id: u32, name: &str) -> Result<(), String> { todo!() }
+/// #[component]
+/// fn UserName(cx: Scope, id: u32) -> Element {
+///     let user = use_query(cx, ("user", *id), |(_, id)| fetch_user(id));
+///     let cache = use_query_cache(cx);
+///
+///     let name = match user.value().as_deref() {
+///         Some(Ok(name)) => name.clone(),
+///         Some(Err(error)) => format!("Failed to load the user: {error}"),
+///         None => "Loading...".to_string(),
+///     };
+///
+///     render! {
+///         "{name}"
+///         button {
+///             onclick: move |_| {
+///                 to_owned![cache, id];
+///                 cx.spawn(async move {
+///                     if rename_user(id, "Ferris").await.is_ok() {
+///                         // Every component that shows this user fetches it again
+///                         cache.invalidate(&("user", id));
+///                     }
+///                 });
+///             },
+///             "Rename"
+///         }
+///     }
+/// }
+/// ```
+pub fn use_query<K, T, E, F>(
+    cx: &ScopeState,
+    key: K,
+    fetcher: impl FnOnce(K) -> F,
+) -> &UseQuery<T, E>
+where
+    K: Serialize + Clone,
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    use_query_with_options(cx, key, QueryOptions::default(), fetcher)
+}
+
+/// Fetch a value and share it with every other component that queries the same key. See [`use_query`] for more
+/// details.
+///
+/// ```rust, no_run
+/// # use dioxus::prelude::*;
+/// # use std::time::Duration;
+/// # async fn fetch_prices() -> Result<Vec<f64>, String> { todo!() }
+/// fn Prices(cx: Scope) -> Element {
+///     let options = QueryOptions {
+///         stale_time: Duration::from_secs(10),
+///         refetch_interval: Some(Duration::from_secs(60)),
+///         ..Default::default()
+///     };
+///     let prices = use_query_with_options(cx, "prices", options, |_| fetch_prices());
+///
+///     render! { "{prices.value():?}" }
+/// }
+/// ```
+pub fn use_query_with_options<K, T, E, F>(
+    cx: &ScopeState,
+    key: K,
+    options: QueryOptions,
+    fetcher: impl FnOnce(K) -> F,
+) -> &UseQuery<T, E>
+where
+    K: Serialize + Clone,
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+{
+    let cache = use_query_cache(cx);
+    let scope = cx.scope_id();
+
+    let mut check_stale = false;
+    let owner = cx.use_hook(|| {
+        check_stale = true;
+        let key = QueryKey::new(&key);
+        cache.subscribe(&key, scope);
+        let key = Rc::new(RefCell::new(key));
+
+        if let Some(interval) = options.refetch_interval {
+            let cache = cache.clone();
+            let key = key.clone();
+            cx.spawn(async move {
+                loop {
+                    Delay::new(interval).await;
+                    let key = key.borrow().clone();
+                    cache.invalidate_any(&key);
+                }
+            });
+        }
+
+        UseQueryOwner {
+            query: UseQuery {
+                cache: cache.clone(),
+                key,
+                stale_time: options.stale_time,
+                cache_time: options.cache_time,
+                phantom: PhantomData,
+            },
+            scope,
+        }
+    });
+    let query = &mut owner.query;
+    query.stale_time = options.stale_time;
+    query.cache_time = options.cache_time;
+
+    let new_key = QueryKey::new(&key);
+    if *query.key.borrow() != new_key {
+        let old_key = query.key.replace(new_key.clone());
+        cache.unsubscribe(&old_key, scope, options.cache_time);
+        cache.subscribe(&new_key, scope);
+        check_stale = true;
+    }
+
+    if cache.try_start(&new_key, scope, options.stale_time, check_stale) {
+        let request = fetcher(key);
+        let cache = cache.clone();
+        cx.spawn(async move {
+            let value = request.await;
+            cache.finish(&new_key, Some(scope), Rc::new(value));
+        });
+    }
+
+    query
+}
+
+/// Unsubscribes from the cache when the component is unmounted
+struct UseQueryOwner<T, E> {
+    query: UseQuery<T, E>,
+    scope: ScopeId,
+}
+
+impl<T, E> Drop for UseQueryOwner<T, E> {
+    fn drop(&mut self) {
+        let key = self.query.key.borrow().clone();
+        self.query
+            .cache
+            .unsubscribe(&key, self.scope, self.query.cache_time);
+    }
+}
+
+/// A query created by [`use_query`]. See its documentation for more details.
+pub struct UseQuery<T, E> {
+    cache: QueryCache,
+    key: Rc<RefCell<QueryKey>>,
+    stale_time: Duration,
+    cache_time: Duration,
+    phantom: PhantomData<fn() -> (T, E)>,
+}
+
+impl<T, E> UseQuery<T, E> {
+    /// Get the last result of the query, even if it is stale or being fetched again.
+    ///
+    /// If the query has never been fetched, this returns `None`.
+    pub fn value(&self) -> Option<Rc<Result<T, E>>> {
+        self.cache.get_any(&self.key.borrow())
+    }
+
+    /// Returns true if a request for the key is running
+    pub fn is_fetching(&self) -> bool {
+        self.cache.is_fetching(&self.key.borrow())
+    }
+
+    /// Returns true if the value is older than the stale time or was invalidated
+    pub fn is_stale(&self) -> bool {
+        self.cache.is_stale(&self.key.borrow(), self.stale_time)
+    }
+
+    /// Mark the key as stale so it is fetched again. Every component that uses the key reruns.
+    pub fn invalidate(&self) {
+        self.cache.invalidate_any(&self.key.borrow());
+    }
+
+    /// Get the cache this query is stored in
+    pub fn cache(&self) -> &QueryCache {
+        &self.cache
+    }
+}
+
+#[test]
+fn queries_are_deduplicated() {
+    use dioxus::prelude::render;
+    use dioxus_core::{Element, Scope, VirtualDom};
+    use std::cell::Cell;
+
+    thread_local! {
+        static FETCHES: Cell<usize> = Cell::new(0);
+        static CACHE: RefCell<Option<QueryCache>> = RefCell::new(None);
+    }
+
+    fn app(cx: Scope) -> Element {
+        let cache = use_query_cache(cx);
+        CACHE.with(|c| *c.borrow_mut() = Some(cache.clone()));
+        render! { Child {} Child {} }
+    }
+
+    #[allow(non_snake_case)]
+    fn Child(cx: Scope) -> Element {
+        let user = use_query(cx, ("user", 1), |_| {
+            FETCHES.with(|fetches| fetches.set(fetches.get() + 1));
+            async { Ok::<_, ()>(FETCHES.with(|fetches| fetches.get())) }
+        });
+        let value = format!("{:?}", user.value());
+        render! { "{value}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let _ = dom.rebuild();
+    dom.process_events();
+    let _ = dom.render_immediate();
+
+    assert_eq!(FETCHES.with(|fetches| fetches.get()), 1);
+    let cache = CACHE.with(|cache| cache.borrow().clone().unwrap());
+    assert_eq!(cache.get(&("user", 1)), Some(Rc::new(Ok::<usize, ()>(1))));
+
+    // Invalidating the key fetches it again, but only once
+    cache.invalidate(&("user", 1));
+    dom.process_events();
+    let _ = dom.render_immediate();
+    dom.process_events();
+    let _ = dom.render_immediate();
+
+    assert_eq!(FETCHES.with(|fetches| fetches.get()), 2);
+    assert_eq!(cache.get(&("user", 1)), Some(Rc::new(Ok::<usize, ()>(2))));
+}
+
+#[cfg(test)]
+fn test_cache() -> QueryCache {
+    QueryCache {
+        entries: Default::default(),
+        update_any: Arc::new(|_| {}),
+    }
+}
+
+#[test]
+fn invalidating_during_a_fetch_fetches_again() {
+    let cache = test_cache();
+    let key = QueryKey::new(&("user", 1));
+    let scope = ScopeId(1);
+    cache.subscribe(&key, scope);
+
+    assert!(cache.try_start(&key, scope, Duration::MAX, false));
+    cache.invalidate(&("user", 1));
+    // the request is already running, so it isn't started twice
+    assert!(!cache.try_start(&key, scope, Duration::MAX, false));
+
+    // the response was requested before the invalidation, so it is stale when it arrives
+    cache.finish(&key, Some(scope), Rc::new(Ok::<usize, ()>(1)));
+    assert!(cache.is_stale(&key, Duration::MAX));
+    assert!(cache.try_start(&key, scope, Duration::MAX, false));
+
+    cache.finish(&key, Some(scope), Rc::new(Ok::<usize, ()>(2)));
+    assert!(!cache.is_stale(&key, Duration::MAX));
+    assert_eq!(cache.get(&("user", 1)), Some(Rc::new(Ok::<usize, ()>(2))));
+}
+
+#[test]
+fn keys_are_compared_by_their_serialized_form() {
+    let cache = test_cache();
+    cache.set(("user", 1), Ok::<usize, ()>(1));
+
+    assert_eq!(
+        cache.get(&("user".to_string(), 1u64)),
+        Some(Rc::new(Ok::<usize, ()>(1)))
+    );
+    assert_eq!(cache.get::<_, usize, ()>(&("user", 2)), None);
+}
+
+#[test]
+fn unused_entries_are_collected() {
+    let cache = test_cache();
+    let kept = QueryKey::new(&"kept");
+    let dropped = QueryKey::new(&"dropped");
+    for key in [&kept, &dropped] {
+        cache.subscribe(key, ScopeId(1));
+    }
+    cache.set("kept", Ok::<usize, ()>(1));
+    cache.set("dropped", Ok::<usize, ()>(2));
+
+    cache.unsubscribe(&kept, ScopeId(1), Duration::MAX);
+    cache.unsubscribe(&dropped, ScopeId(1), Duration::ZERO);
+    cache.collect_garbage();
+
+    assert_eq!(cache.get(&"kept"), Some(Rc::new(Ok::<usize, ()>(1))));
+    assert_eq!(cache.get::<_, usize, ()>(&"dropped"), None);
+}
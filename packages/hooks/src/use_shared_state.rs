@@ -1,5 +1,8 @@
 use self::error::{UseSharedStateError, UseSharedStateResult};
 use dioxus_core::{ScopeId, ScopeState};
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_util::StreamExt;
+use slab::Slab;
 use std::{collections::HashSet, rc::Rc, sync::Arc};
 
 #[cfg(debug_assertions)]
@@ -81,6 +84,8 @@ pub(crate) struct ProvidedStateInner<T> {
     value: T,
     notify_any: Arc<dyn Fn(ScopeId)>,
     consumers: HashSet<ScopeId>,
+    // Consumers that only rerender if their projection of the value changes. They are checked after the write ends
+    selectors: Slab<UnboundedSender<()>>,
     gen: usize,
 }
 
@@ -90,6 +95,9 @@ impl<T> ProvidedStateInner<T> {
         for consumer in self.consumers.iter() {
             (self.notify_any)(*consumer);
         }
+        for (_, selector) in self.selectors.iter() {
+            let _ = selector.unbounded_send(());
+        }
     }
 }
 
@@ -248,10 +256,10 @@ impl<T> UseSharedState<T> {
         }
     }
 
-    /// Calling "write" will force the component to re-render
-    ///
+    /// Calling "write" will force the components that use the state to re-render
     ///
-    // TODO: We prevent unncessary notifications only in the hook, but we should figure out some more global lock
+    /// Components that use [`use_shared_state_select`] are checked once the write ends, and only re-render if their
+    /// projection of the state changed.
     #[cfg_attr(debug_assertions, track_caller)]
     #[cfg_attr(debug_assertions, inline(never))]
     pub fn write(&self) -> RefMut<'_, T> {
@@ -320,6 +328,101 @@ impl<T> PartialEq for UseSharedState<T> {
     }
 }
 
+/// Subscribe to a projection of some shared state. The component only rerenders when the projection changes.
+///
+/// [`use_shared_state`] rerenders the component on every write to the state. If a component only needs part of the
+/// state, `use_shared_state_select` avoids rerendering it when other parts of the state change. The selector runs
+/// after every write and the component rerenders if the new projection is not equal to the last one.
+///
+/// Returns `None` if no parent component provides the state.
+///
+/// # Example
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// struct Settings {
+///     theme: String,
+///     font_size: u32,
+/// }
+///
+/// // Only rerenders when the theme changes, not when the font size changes
+/// fn ThemeName(cx: Scope) -> Element {
+///     let theme = use_shared_state_select(cx, |settings: &Settings| settings.theme.clone())?;
+///
+///     render! { "{theme}" }
+/// }
+/// ```
+pub fn use_shared_state_select<T: 'static, O: PartialEq + Clone + 'static>(
+    cx: &ScopeState,
+    select: impl Fn(&T) -> O + 'static,
+) -> Option<O> {
+    let mut select = Some(select);
+    let owner: &mut Option<UseSharedStateSelectOwner<T, O>> = &mut *cx.use_hook(|| {
+        let root = cx.consume_context::<ProvidedState<T>>()?;
+
+        let (tx, mut rx) = unbounded();
+        let id = root.borrow_mut().selectors.insert(tx);
+
+        let selector: Selector<T, O> = Rc::new(RefCell::new(Box::new(select.take()?)));
+        let last = Rc::new(RefCell::new(None));
+
+        cx.push_future({
+            let (root, selector, last) = (root.clone(), selector.clone(), last.clone());
+            let update = cx.schedule_update();
+            async move {
+                while rx.next().await.is_some() {
+                    let new = match root.try_borrow() {
+                        Ok(root) => (selector.borrow())(&root.value),
+                        // The state is still borrowed, so we can't tell if the projection changed
+                        Err(_) => {
+                            update();
+                            continue;
+                        }
+                    };
+                    let changed = last.borrow().as_ref() != Some(&new);
+                    if changed {
+                        *last.borrow_mut() = Some(new);
+                        update();
+                    }
+                }
+            }
+        });
+
+        Some(UseSharedStateSelectOwner {
+            root,
+            id,
+            selector,
+            last,
+        })
+    });
+    let owner = owner.as_mut()?;
+
+    // Use the selector from the latest render in case it captures props
+    if let Some(select) = select {
+        *owner.selector.borrow_mut() = Box::new(select);
+    }
+    let value = (owner.selector.borrow())(&owner.root.borrow().value);
+    *owner.last.borrow_mut() = Some(value.clone());
+
+    Some(value)
+}
+
+type Selector<T, O> = Rc<RefCell<Box<dyn Fn(&T) -> O>>>;
+
+/// This wrapper stops checking the selector when the component is unmounted
+struct UseSharedStateSelectOwner<T, O> {
+    root: ProvidedState<T>,
+    id: usize,
+    selector: Selector<T, O>,
+    last: Rc<RefCell<Option<O>>>,
+}
+
+impl<T, O> Drop for UseSharedStateSelectOwner<T, O> {
+    fn drop(&mut self) {
+        self.root.borrow_mut().selectors.remove(self.id);
+    }
+}
+
 /// Provide some state for components down the hierarchy to consume without having to drill props. See [`use_shared_state`] to consume the state
 ///
 ///
@@ -366,9 +469,68 @@ pub fn use_shared_state_provider<T: 'static>(cx: &ScopeState, f: impl FnOnce() -
             value: f(),
             notify_any: cx.schedule_update_any(),
             consumers: HashSet::new(),
+            selectors: Slab::new(),
             gen: 0,
         }));
 
         cx.provide_context(state);
     });
 }
+
+#[test]
+fn selectors_skip_unchanged_projections() {
+    use dioxus::prelude::render;
+    use dioxus_core::{Element, Scope, VirtualDom};
+    use std::cell::Cell;
+
+    struct Settings {
+        theme: &'static str,
+        font_size: u32,
+    }
+
+    thread_local! {
+        static RENDERS: Cell<(usize, usize)> = Cell::new((0, 0));
+        static STATE: std::cell::RefCell<Option<UseSharedState<Settings>>> = std::cell::RefCell::new(None);
+    }
+
+    fn app(cx: Scope) -> Element {
+        use_shared_state_provider(cx, || Settings {
+            theme: "dark",
+            font_size: 12,
+        });
+        let state = use_shared_state::<Settings>(cx).unwrap();
+        STATE.with(|s| *s.borrow_mut() = Some(state.clone()));
+        render! { Theme {} FontSize {} }
+    }
+
+    #[allow(non_snake_case)]
+    fn Theme(cx: Scope) -> Element {
+        let theme = use_shared_state_select(cx, |s: &Settings| s.theme)?;
+        RENDERS.with(|r| r.set((r.get().0 + 1, r.get().1)));
+        render! { "{theme}" }
+    }
+
+    #[allow(non_snake_case)]
+    fn FontSize(cx: Scope) -> Element {
+        let font_size = use_shared_state_select(cx, |s: &Settings| s.font_size)?;
+        RENDERS.with(|r| r.set((r.get().0, r.get().1 + 1)));
+        render! { "{font_size}" }
+    }
+
+    let mut dom = VirtualDom::new(app);
+    let _ = dom.rebuild();
+    assert_eq!(RENDERS.with(|r| r.get()), (1, 1));
+
+    let state = STATE.with(|s| s.borrow().clone().unwrap());
+    state.write().font_size = 14;
+    dom.process_events();
+    let _ = dom.render_immediate();
+
+    assert_eq!(RENDERS.with(|r| r.get()), (1, 2));
+
+    state.write().theme = "light";
+    dom.process_events();
+    let _ = dom.render_immediate();
+
+    assert_eq!(RENDERS.with(|r| r.get()), (2, 2));
+}
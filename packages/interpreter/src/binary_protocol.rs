@@ -0,0 +1,343 @@
+//! A compact binary encoding of [`Mutations`] for renderers that send edits to the interpreter over a channel
+//!
+//! Each frame starts with [`FRAME_MARKER`] followed by a byte of flags. The marker is never valid UTF-8, so transports
+//! that carry both JSON and binary messages can tell them apart. Several frames may be sent back to back in one
+//! message, the decoder applies them in order.
+//!
+//! Numbers are written as LEB128 varints. Tag names, attribute names, namespaces, event names and template names are
+//! interned: the first time a string is written it is given the next index in the string table and written inline,
+//! after that only the index is sent. The string table is shared between every frame written by the same
+//! [`MutationEncoder`], so frames must be decoded in the order they were encoded.
+//!
+//! The matching decoder lives in `interpreter.js` and is used through `Interpreter.handleBinaryEdits`.
+
+use dioxus_core::{
+    BorrowedAttributeValue, Mutation, Mutations, Template, TemplateAttribute, TemplateNode,
+};
+use std::collections::HashMap;
+
+/// The first byte of every binary frame
+pub const FRAME_MARKER: u8 = 0xFF;
+
+/// Set in the flags of a frame when the decoder should clear its string table before reading the frame
+pub const FLAG_RESET_STRINGS: u8 = 1;
+
+/// The format edits are sent to the interpreter in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EditEncoding {
+    /// Serialize edits as JSON. This is larger and slower to decode, but easy to inspect in the browser's devtools.
+    Json,
+    /// Pack edits with the [`MutationEncoder`]
+    #[default]
+    Binary,
+}
+
+/// Encodes batches of mutations into binary frames, interning strings across frames
+#[derive(Debug)]
+pub struct MutationEncoder {
+    strings: HashMap<String, u32>,
+    reset: bool,
+}
+
+impl Default for MutationEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MutationEncoder {
+    /// Create a new encoder. The first frame it writes resets the decoder's string table.
+    pub fn new() -> Self {
+        Self {
+            strings: HashMap::new(),
+            reset: true,
+        }
+    }
+
+    /// Forget every interned string. This must be called whenever the client creates a new decoder, for example
+    /// after the page reloads.
+    pub fn reset(&mut self) {
+        self.strings.clear();
+        self.reset = true;
+    }
+
+    /// Encode a batch of mutations into a single frame
+    pub fn encode(&mut self, mutations: &Mutations) -> Vec<u8> {
+        let mut buf = vec![FRAME_MARKER];
+        buf.push(if std::mem::take(&mut self.reset) {
+            FLAG_RESET_STRINGS
+        } else {
+            0
+        });
+
+        write_varint(&mut buf, mutations.templates.len() as u64);
+        for template in &mutations.templates {
+            self.write_template(&mut buf, template);
+        }
+
+        write_varint(&mut buf, mutations.edits.len() as u64);
+        for edit in &mutations.edits {
+            self.write_edit(&mut buf, edit);
+        }
+
+        buf
+    }
+
+    // The interpreter only reads the roots of a template, so the node and attribute paths are not sent
+    fn write_template(&mut self, buf: &mut Vec<u8>, template: &Template) {
+        self.write_interned(buf, template.name);
+        write_varint(buf, template.roots.len() as u64);
+        for root in template.roots {
+            self.write_template_node(buf, root);
+        }
+    }
+
+    fn write_template_node(&mut self, buf: &mut Vec<u8>, node: &TemplateNode) {
+        match node {
+            TemplateNode::Element {
+                tag,
+                namespace,
+                attrs,
+                children,
+            } => {
+                buf.push(0);
+                self.write_interned(buf, tag);
+                self.write_optional_interned(buf, *namespace);
+
+                // Dynamic attributes are set with edits, so only the static attributes are part of the template
+                let static_attrs = attrs.iter().filter_map(|attr| match attr {
+                    TemplateAttribute::Static {
+                        name,
+                        value,
+                        namespace,
+                    } => Some((name, value, namespace)),
+                    TemplateAttribute::Dynamic { .. } => None,
+                });
+                write_varint(buf, static_attrs.clone().count() as u64);
+                for (name, value, namespace) in static_attrs {
+                    self.write_interned(buf, name);
+                    write_str(buf, value);
+                    self.write_optional_interned(buf, *namespace);
+                }
+
+                write_varint(buf, children.len() as u64);
+                for child in children.iter() {
+                    self.write_template_node(buf, child);
+                }
+            }
+            TemplateNode::Text { text } => {
+                buf.push(1);
+                write_str(buf, text);
+            }
+            TemplateNode::Dynamic { .. } => buf.push(2),
+            TemplateNode::DynamicText { .. } => buf.push(3),
+        }
+    }
+
+    fn write_edit(&mut self, buf: &mut Vec<u8>, edit: &Mutation) {
+        match edit {
+            Mutation::AppendChildren { id, m } => {
+                buf.push(0);
+                write_varint(buf, id.0 as u64);
+                write_varint(buf, *m as u64);
+            }
+            Mutation::AssignId { path, id } => {
+                buf.push(1);
+                write_bytes(buf, path);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::CreatePlaceholder { id } => {
+                buf.push(2);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::CreateTextNode { value, id } => {
+                buf.push(3);
+                write_str(buf, value);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::HydrateText { path, value, id } => {
+                buf.push(4);
+                write_bytes(buf, path);
+                write_str(buf, value);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::LoadTemplate { name, index, id } => {
+                buf.push(5);
+                self.write_interned(buf, name);
+                write_varint(buf, *index as u64);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::ReplaceWith { id, m } => {
+                buf.push(6);
+                write_varint(buf, id.0 as u64);
+                write_varint(buf, *m as u64);
+            }
+            Mutation::ReplacePlaceholder { path, m } => {
+                buf.push(7);
+                write_bytes(buf, path);
+                write_varint(buf, *m as u64);
+            }
+            Mutation::InsertAfter { id, m } => {
+                buf.push(8);
+                write_varint(buf, id.0 as u64);
+                write_varint(buf, *m as u64);
+            }
+            Mutation::InsertBefore { id, m } => {
+                buf.push(9);
+                write_varint(buf, id.0 as u64);
+                write_varint(buf, *m as u64);
+            }
+            Mutation::SetAttribute {
+                name,
+                value,
+                id,
+                ns,
+            } => {
+                buf.push(10);
+                self.write_interned(buf, name);
+                write_attribute_value(buf, value);
+                write_varint(buf, id.0 as u64);
+                self.write_optional_interned(buf, *ns);
+            }
+            Mutation::SetText { value, id } => {
+                buf.push(11);
+                write_str(buf, value);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::NewEventListener { name, id } => {
+                buf.push(12);
+                self.write_interned(buf, name);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::RemoveEventListener { name, id } => {
+                buf.push(13);
+                self.write_interned(buf, name);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::Remove { id } => {
+                buf.push(14);
+                write_varint(buf, id.0 as u64);
+            }
+            Mutation::PushRoot { id } => {
+                buf.push(15);
+                write_varint(buf, id.0 as u64);
+            }
+        }
+    }
+
+    /// Write the index of an interned string, followed by the string itself if this is the first time it was written
+    fn write_interned(&mut self, buf: &mut Vec<u8>, value: &str) {
+        if let Some(idx) = self.strings.get(value) {
+            write_varint(buf, *idx as u64);
+            return;
+        }
+
+        let idx = self.strings.len() as u32;
+        self.strings.insert(value.to_string(), idx);
+        write_varint(buf, idx as u64);
+        write_str(buf, value);
+    }
+
+    fn write_optional_interned(&mut self, buf: &mut Vec<u8>, value: Option<&str>) {
+        match value {
+            Some(value) => {
+                buf.push(1);
+                self.write_interned(buf, value);
+            }
+            None => buf.push(0),
+        }
+    }
+}
+
+fn write_attribute_value(buf: &mut Vec<u8>, value: &BorrowedAttributeValue) {
+    match value {
+        BorrowedAttributeValue::Text(text) => {
+            buf.push(0);
+            write_str(buf, text);
+        }
+        BorrowedAttributeValue::Float(float) => {
+            buf.push(1);
+            buf.extend_from_slice(&float.to_le_bytes());
+        }
+        BorrowedAttributeValue::Int(int) => {
+            buf.push(2);
+            // zigzag encode the integer so small negative numbers stay small
+            write_varint(buf, ((*int << 1) ^ (*int >> 63)) as u64);
+        }
+        BorrowedAttributeValue::Bool(bool) => {
+            buf.push(3);
+            buf.push(*bool as u8);
+        }
+        BorrowedAttributeValue::None => buf.push(4),
+        BorrowedAttributeValue::Any(_) => panic!("Any cannot be serialized"),
+    }
+}
+
+fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn write_bytes(buf: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
+
+fn write_str(buf: &mut Vec<u8>, value: &str) {
+    write_bytes(buf, value.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::ElementId;
+
+    #[test]
+    fn varints() {
+        let mut buf = Vec::new();
+        write_varint(&mut buf, 1);
+        write_varint(&mut buf, 300);
+        assert_eq!(buf, [1, 0b1010_1100, 0b0000_0010]);
+    }
+
+    #[test]
+    fn strings_are_interned_across_frames() {
+        let mut encoder = MutationEncoder::new();
+        let mutations = || Mutations {
+            edits: vec![Mutation::NewEventListener {
+                name: "click",
+                id: ElementId(1),
+            }],
+            ..Default::default()
+        };
+
+        let first = encoder.encode(&mutations());
+        assert_eq!(
+            first,
+            [
+                FRAME_MARKER,
+                FLAG_RESET_STRINGS,
+                0,
+                1,
+                12,
+                0,
+                5,
+                b'c',
+                b'l',
+                b'i',
+                b'c',
+                b'k',
+                1
+            ]
+        );
+
+        let second = encoder.encode(&mutations());
+        assert_eq!(second, [FRAME_MARKER, 0, 0, 1, 12, 0, 1]);
+
+        encoder.reset();
+        assert_eq!(encoder.encode(&mutations()), first);
+    }
+}
@@ -61,6 +61,225 @@ class InterpreterConfig {
   }
 }
 
+// Decodes the binary edit frames written by the MutationEncoder in
+// binary_protocol.rs into the same shape as the JSON edits
+class BinaryEditDecoder {
+  constructor() {
+    this.strings = [];
+    this.utf8 = new TextDecoder();
+  }
+
+  // A message may hold several frames back to back. Returns every frame in
+  // the message in order
+  decode(bytes) {
+    this.bytes = bytes instanceof Uint8Array ? bytes : new Uint8Array(bytes);
+    this.view = new DataView(
+      this.bytes.buffer,
+      this.bytes.byteOffset,
+      this.bytes.byteLength
+    );
+    this.offset = 0;
+
+    const frames = [];
+    while (this.offset < this.bytes.length) {
+      frames.push(this.frame());
+    }
+    return frames;
+  }
+
+  frame() {
+    if (this.u8() !== 0xff) {
+      throw new Error("Invalid binary edit frame");
+    }
+    if (this.u8() & 1) {
+      this.strings = [];
+    }
+
+    const templates = [];
+    const template_count = this.varint();
+    for (let i = 0; i < template_count; i++) {
+      const name = this.interned();
+      const roots = [];
+      const root_count = this.varint();
+      for (let j = 0; j < root_count; j++) {
+        roots.push(this.templateNode());
+      }
+      templates.push({ name, roots });
+    }
+
+    const edits = [];
+    const edit_count = this.varint();
+    for (let i = 0; i < edit_count; i++) {
+      edits.push(this.edit());
+    }
+
+    return { templates, edits };
+  }
+
+  u8() {
+    return this.bytes[this.offset++];
+  }
+
+  // Read a LEB128 varint without bitwise operators so values above 2^31 are not
+  // truncated
+  varint() {
+    let value = 0;
+    let scale = 1;
+    let byte;
+    do {
+      byte = this.u8();
+      value += (byte & 0x7f) * scale;
+      scale *= 128;
+    } while (byte & 0x80);
+    return value;
+  }
+
+  bytesOf() {
+    const len = this.varint();
+    const bytes = this.bytes.subarray(this.offset, this.offset + len);
+    this.offset += len;
+    return bytes;
+  }
+
+  string() {
+    return this.utf8.decode(this.bytesOf());
+  }
+
+  path() {
+    return Array.from(this.bytesOf());
+  }
+
+  interned() {
+    const idx = this.varint();
+    if (idx === this.strings.length) {
+      this.strings.push(this.string());
+    }
+    return this.strings[idx];
+  }
+
+  optionalInterned() {
+    return this.u8() ? this.interned() : null;
+  }
+
+  attributeValue() {
+    switch (this.u8()) {
+      case 0:
+        return this.string();
+      case 1: {
+        const value = this.view.getFloat64(this.offset, true);
+        this.offset += 8;
+        return value;
+      }
+      case 2: {
+        const zigzag = this.varint();
+        return zigzag % 2 === 0 ? zigzag / 2 : -(zigzag + 1) / 2;
+      }
+      case 3:
+        return this.u8() === 1;
+      case 4:
+        return null;
+    }
+  }
+
+  templateNode() {
+    switch (this.u8()) {
+      case 0: {
+        const tag = this.interned();
+        const namespace = this.optionalInterned();
+        const attrs = [];
+        const attr_count = this.varint();
+        for (let i = 0; i < attr_count; i++) {
+          const name = this.interned();
+          const value = this.string();
+          const namespace = this.optionalInterned();
+          attrs.push({ type: "Static", name, value, namespace });
+        }
+        const children = [];
+        const child_count = this.varint();
+        for (let i = 0; i < child_count; i++) {
+          children.push(this.templateNode());
+        }
+        return { type: "Element", tag, namespace, attrs, children };
+      }
+      case 1:
+        return { type: "Text", text: this.string() };
+      case 2:
+        return { type: "Dynamic" };
+      case 3:
+        return { type: "DynamicText" };
+    }
+  }
+
+  edit() {
+    switch (this.u8()) {
+      case 0:
+        return { type: "AppendChildren", id: this.varint(), m: this.varint() };
+      case 1:
+        return { type: "AssignId", path: this.path(), id: this.varint() };
+      case 2:
+        return { type: "CreatePlaceholder", id: this.varint() };
+      case 3:
+        return {
+          type: "CreateTextNode",
+          value: this.string(),
+          id: this.varint(),
+        };
+      case 4:
+        return {
+          type: "HydrateText",
+          path: this.path(),
+          value: this.string(),
+          id: this.varint(),
+        };
+      case 5:
+        return {
+          type: "LoadTemplate",
+          name: this.interned(),
+          index: this.varint(),
+          id: this.varint(),
+        };
+      case 6:
+        return { type: "ReplaceWith", id: this.varint(), m: this.varint() };
+      case 7:
+        return {
+          type: "ReplacePlaceholder",
+          path: this.path(),
+          m: this.varint(),
+        };
+      case 8:
+        return { type: "InsertAfter", id: this.varint(), m: this.varint() };
+      case 9:
+        return { type: "InsertBefore", id: this.varint(), m: this.varint() };
+      case 10:
+        return {
+          type: "SetAttribute",
+          name: this.interned(),
+          value: this.attributeValue(),
+          id: this.varint(),
+          ns: this.optionalInterned(),
+        };
+      case 11:
+        return { type: "SetText", value: this.string(), id: this.varint() };
+      case 12:
+        return {
+          type: "NewEventListener",
+          name: this.interned(),
+          id: this.varint(),
+        };
+      case 13:
+        return {
+          type: "RemoveEventListener",
+          name: this.interned(),
+          id: this.varint(),
+        };
+      case 14:
+        return { type: "Remove", id: this.varint() };
+      case 15:
+        return { type: "PushRoot", id: this.varint() };
+    }
+  }
+}
+
 class Interpreter {
   constructor(root, config) {
     this.config = config;
@@ -71,6 +290,7 @@ class Interpreter {
     this.handlers = {};
     this.templates = {};
     this.lastNodeWasText = false;
+    this.decoder = new BinaryEditDecoder();
   }
   top() {
     return this.stack[this.stack.length - 1];
@@ -235,6 +455,12 @@ class Interpreter {
     /*POST_HANDLE_EDITS*/
   }
 
+  handleBinaryEdits(bytes) {
+    for (let edits of this.decoder.decode(bytes)) {
+      this.handleEdits(edits);
+    }
+  }
+
   SaveTemplate(template) {
     let roots = [];
     for (let root of template.roots) {
@@ -5,6 +5,9 @@
 pub static INTERPRETER_JS: &str = include_str!("./interpreter.js");
 pub static COMMON_JS: &str = include_str!("./common.js");
 
+#[cfg(feature = "binary-protocol")]
+pub mod binary_protocol;
+
 #[cfg(feature = "sledgehammer")]
 mod sledgehammer_bindings;
 #[cfg(feature = "sledgehammer")]
@@ -12,13 +12,16 @@ pub fn axum_socket(ws: WebSocket) -> impl LiveViewSocket {
 }
 
 fn transform_rx(message: Result<Message, axum::Error>) -> Result<Vec<u8>, LiveViewError> {
-    message
+    // File uploads are sent as binary messages, everything else is text
+    Ok(message
         .map_err(|_| LiveViewError::SendingFailed)?
-        .into_text()
-        .map(|s| s.into_bytes())
-        .map_err(|_| LiveViewError::SendingFailed)
+        .into_data())
 }
 
 async fn transform_tx(message: Vec<u8>) -> Result<Message, axum::Error> {
-    Ok(Message::Text(String::from_utf8_lossy(&message).to_string()))
+    // Binary edits are never valid UTF-8, everything else is JSON
+    Ok(match String::from_utf8(message) {
+        Ok(text) => Message::Text(text),
+        Err(err) => Message::Binary(err.into_bytes()),
+    })
 }
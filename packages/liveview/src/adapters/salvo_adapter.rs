@@ -19,5 +19,9 @@ fn transform_rx(message: Result<Message, salvo::Error>) -> Result<Vec<u8>, LiveV
 }
 
 async fn transform_tx(message: Vec<u8>) -> Result<Message, salvo::Error> {
-    Ok(Message::text(String::from_utf8_lossy(&message).to_string()))
+    // Binary edits are never valid UTF-8, everything else is JSON
+    Ok(match String::from_utf8(message) {
+        Ok(text) => Message::text(text),
+        Err(err) => Message::binary(err.into_bytes()),
+    })
 }
@@ -21,5 +21,9 @@ fn transform_rx(message: Result<Message, warp::Error>) -> Result<Vec<u8>, LiveVi
 }
 
 async fn transform_tx(message: Vec<u8>) -> Result<Message, warp::Error> {
-    Ok(Message::text(String::from_utf8_lossy(&message).to_string()))
+    // Binary edits are never valid UTF-8, everything else is JSON
+    Ok(match String::from_utf8(message) {
+        Ok(text) => Message::text(text),
+        Err(err) => Message::binary(err.into_bytes()),
+    })
 }
@@ -0,0 +1,277 @@
+use dioxus_html::HtmlEvent;
+use std::{collections::HashSet, time::Duration};
+use tokio::time::Instant;
+
+/// Controls how a liveview groups the events it receives and the edits it sends back
+///
+/// Events that arrive while the VirtualDom is busy or the socket is sending are handled together and rendered once,
+/// so a slow client receives fewer, larger frames instead of falling further and further behind.
+///
+/// ```rust, ignore
+/// let pool = LiveViewPool::new().with_event_batching(
+///     EventBatching::new()
+///         .with_debounce(Duration::from_millis(50))
+///         .with_min_frame_interval(Duration::from_millis(16)),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventBatching {
+    pub(crate) high_frequency_events: HashSet<String>,
+    pub(crate) debounce: Duration,
+    pub(crate) max_batch_size: usize,
+    pub(crate) min_frame_interval: Duration,
+}
+
+impl Default for EventBatching {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBatching {
+    /// Create the default batching configuration
+    ///
+    /// Repeated `mousemove`, `pointermove`, `touchmove`, `scroll`, `wheel`, `drag`, `dragover` and `input` events on
+    /// the same element are coalesced into the latest one. Nothing is debounced and frames are sent as fast as the
+    /// client reads them.
+    pub fn new() -> Self {
+        Self {
+            high_frequency_events: [
+                "mousemove",
+                "pointermove",
+                "touchmove",
+                "scroll",
+                "wheel",
+                "drag",
+                "dragover",
+                "input",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            debounce: Duration::ZERO,
+            max_batch_size: 128,
+            min_frame_interval: Duration::ZERO,
+        }
+    }
+
+    /// Set the event types that are coalesced and debounced. Names are written without the `on` prefix, for example
+    /// `"mousemove"` for `onmousemove`.
+    pub fn with_high_frequency_events(
+        mut self,
+        events: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.high_frequency_events = events.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only handle a high frequency event once no other event of the same type has reached the same element for this
+    /// long. Any other event handles the pending high frequency events first, so they are never reordered. Defaults to
+    /// zero, which handles the latest event in every batch.
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set the most events read from the socket before the VirtualDom is rendered. Defaults to 128.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Set the shortest time between two frames of edits. Edits rendered in between are merged into the next frame.
+    /// Defaults to zero.
+    pub fn with_min_frame_interval(mut self, min_frame_interval: Duration) -> Self {
+        self.min_frame_interval = min_frame_interval;
+        self
+    }
+
+    fn is_high_frequency(&self, event: &HtmlEvent) -> bool {
+        self.high_frequency_events.contains(&event.name)
+    }
+}
+
+/// The events received from the client that have not been handled yet
+pub(crate) struct EventQueue {
+    config: EventBatching,
+    queued: Vec<HtmlEvent>,
+    debounced: Vec<(Instant, HtmlEvent)>,
+}
+
+impl EventQueue {
+    pub(crate) fn new(config: EventBatching) -> Self {
+        Self {
+            config,
+            queued: Vec::new(),
+            debounced: Vec::new(),
+        }
+    }
+
+    pub(crate) fn config(&self) -> &EventBatching {
+        &self.config
+    }
+
+    pub(crate) fn push(&mut self, event: HtmlEvent) {
+        self.push_at(event, Instant::now())
+    }
+
+    fn push_at(&mut self, event: HtmlEvent, now: Instant) {
+        if !self.config.is_high_frequency(&event) {
+            // Debounced events arrived first, so they are handled before this event even if they are not due yet
+            self.queued
+                .extend(self.debounced.drain(..).map(|(_, pending)| pending));
+            self.queued.push(event);
+            return;
+        }
+
+        if !self.config.debounce.is_zero() {
+            let deadline = now + self.config.debounce;
+            self.debounced
+                .retain(|(_, pending)| !same_target(pending, &event));
+            self.debounced.push((deadline, event));
+            return;
+        }
+
+        // Only merge with the last event so the order relative to other events is kept
+        match self.queued.last_mut() {
+            Some(last) if same_target(last, &event) => *last = event,
+            _ => self.queued.push(event),
+        }
+    }
+
+    /// When the next debounced event is due
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.debounced.iter().map(|(deadline, _)| *deadline).min()
+    }
+
+    /// Take every event that is ready to be handled in the order it should be handled in
+    pub(crate) fn take_ready(&mut self) -> Vec<HtmlEvent> {
+        self.take_ready_at(Instant::now())
+    }
+
+    fn take_ready_at(&mut self, now: Instant) -> Vec<HtmlEvent> {
+        let mut ready = std::mem::take(&mut self.queued);
+        let mut idx = 0;
+        while idx < self.debounced.len() {
+            if self.debounced[idx].0 <= now {
+                ready.push(self.debounced.remove(idx).1);
+            } else {
+                idx += 1;
+            }
+        }
+        ready
+    }
+}
+
+fn same_target(a: &HtmlEvent, b: &HtmlEvent) -> bool {
+    a.name == b.name && a.element == b.element
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus_core::ElementId;
+    use dioxus_html::EventData;
+
+    fn event(name: &str, element: usize) -> HtmlEvent {
+        HtmlEvent {
+            element: ElementId(element),
+            name: name.to_string(),
+            bubbles: true,
+            data: EventData::Mounted,
+        }
+    }
+
+    fn names(events: &[HtmlEvent]) -> Vec<(&str, usize)> {
+        events
+            .iter()
+            .map(|event| (event.name.as_str(), event.element.0))
+            .collect()
+    }
+
+    #[test]
+    fn merges_repeated_high_frequency_events() {
+        let mut queue = EventQueue::new(EventBatching::new());
+        queue.push(event("mousemove", 1));
+        queue.push(event("mousemove", 1));
+        queue.push(event("click", 1));
+        queue.push(event("mousemove", 1));
+        queue.push(event("mousemove", 2));
+        queue.push(event("click", 1));
+        queue.push(event("click", 1));
+
+        // events are only merged with the last event, so the order of the clicks is kept
+        assert_eq!(
+            names(&queue.take_ready()),
+            [
+                ("mousemove", 1),
+                ("click", 1),
+                ("mousemove", 1),
+                ("mousemove", 2),
+                ("click", 1),
+                ("click", 1),
+            ]
+        );
+        assert!(queue.take_ready().is_empty());
+    }
+
+    #[test]
+    fn debounces_high_frequency_events() {
+        let mut queue =
+            EventQueue::new(EventBatching::new().with_debounce(Duration::from_millis(10)));
+        let start = Instant::now();
+        queue.push_at(event("input", 1), start);
+        queue.push_at(event("mousemove", 2), start);
+        queue.push_at(event("input", 1), start + Duration::from_millis(5));
+
+        // nothing is handled until the debounce has passed
+        assert!(queue.take_ready_at(start).is_empty());
+        assert_eq!(
+            queue.next_deadline(),
+            Some(start + Duration::from_millis(10))
+        );
+
+        assert_eq!(
+            names(&queue.take_ready_at(start + Duration::from_millis(10))),
+            [("mousemove", 2)]
+        );
+        // only the latest input is handled once the debounce has passed
+        assert_eq!(
+            names(&queue.take_ready_at(start + Duration::from_millis(15))),
+            [("input", 1)]
+        );
+        assert_eq!(queue.next_deadline(), None);
+    }
+
+    #[test]
+    fn debounced_events_are_handled_before_later_events() {
+        let mut queue =
+            EventQueue::new(EventBatching::new().with_debounce(Duration::from_millis(10)));
+        let start = Instant::now();
+        queue.push_at(event("input", 1), start);
+        queue.push_at(event("input", 1), start);
+        queue.push_at(event("click", 2), start);
+
+        // the click flushes the pending input so the handler sees the latest value first
+        assert_eq!(
+            names(&queue.take_ready_at(start)),
+            [("input", 1), ("click", 2)]
+        );
+        assert_eq!(queue.next_deadline(), None);
+    }
+
+    #[test]
+    fn custom_high_frequency_events() {
+        let mut queue =
+            EventQueue::new(EventBatching::new().with_high_frequency_events(["keydown"]));
+        queue.push(event("keydown", 1));
+        queue.push(event("keydown", 1));
+        queue.push(event("mousemove", 1));
+        queue.push(event("mousemove", 1));
+
+        assert_eq!(
+            names(&queue.take_ready()),
+            [("keydown", 1), ("mousemove", 1), ("mousemove", 1)]
+        );
+    }
+}
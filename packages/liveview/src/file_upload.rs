@@ -0,0 +1,400 @@
+use dioxus_html::FileEngine;
+use serde::Deserialize;
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::sync::mpsc;
+
+/// The first byte of every file chunk the client sends. It is never valid UTF-8, so chunks can't be confused with
+/// the JSON messages sent over the same socket.
+pub(crate) const FILE_CHUNK_MARKER: u8 = 0xFF;
+
+/// The number of bytes the client reads from a file at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The largest file the server reads from the client unless the pool is configured otherwise
+pub(crate) const DEFAULT_MAX_UPLOAD_SIZE: u64 = 100 * 1024 * 1024;
+
+/// A file the user selected in the browser
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct SelectedFile {
+    name: String,
+    size: u64,
+}
+
+enum FileChunk {
+    Data(Vec<u8>),
+    Error,
+}
+
+/// Tracks the files that are currently being read from the client
+#[derive(Clone)]
+pub(crate) struct FileTransfers {
+    active_reads: Arc<Mutex<HashMap<u32, mpsc::UnboundedSender<FileChunk>>>>,
+    // Ids are never reused so chunks that arrive after a read was cancelled are dropped
+    next_read: Arc<AtomicU32>,
+    query_tx: mpsc::UnboundedSender<String>,
+    // The size of a file is reported by the client, so it can't be trusted to allocate the contents up front
+    max_upload_size: u64,
+}
+
+impl FileTransfers {
+    pub(crate) fn new(query_tx: mpsc::UnboundedSender<String>, max_upload_size: u64) -> Self {
+        Self {
+            active_reads: Default::default(),
+            next_read: Default::default(),
+            query_tx,
+            max_upload_size,
+        }
+    }
+
+    /// Route a binary chunk from the client to the read it belongs to
+    ///
+    /// Chunks are laid out as the marker byte, the id of the read as a little endian u32, and then the contents.
+    pub(crate) fn receive_chunk(&self, chunk: &[u8]) {
+        let Some(id) = chunk.get(1..5) else {
+            return;
+        };
+        let id = u32::from_le_bytes(id.try_into().unwrap());
+        if let Some(read) = self.active_reads.lock().unwrap().get(&id) {
+            _ = read.send(FileChunk::Data(chunk[5..].to_vec()));
+        }
+    }
+
+    /// The client could not read a file
+    pub(crate) fn receive_error(&self, id: u32) {
+        if let Some(read) = self.active_reads.lock().unwrap().get(&id) {
+            _ = read.send(FileChunk::Error);
+        }
+    }
+
+    /// Fail every read that is in progress. The client can't send the rest of the files after it disconnects.
+    pub(crate) fn cancel_all(&self) {
+        // Dropping the senders ends the reads
+        for read in self.active_reads.lock().unwrap().values_mut() {
+            let (closed, _) = mpsc::unbounded_channel();
+            *read = closed;
+        }
+    }
+
+    /// Cancel every read that is in progress once the returned guard is dropped
+    pub(crate) fn cancel_reads_on_drop(&self) -> CancelReads {
+        CancelReads(self.clone())
+    }
+
+    /// Create a file engine for the files the client selected in an upload
+    pub(crate) fn engine(&self, upload: u32, files: Vec<SelectedFile>) -> LiveViewFileEngine {
+        LiveViewFileEngine {
+            upload,
+            files,
+            transfers: self.clone(),
+        }
+    }
+
+    fn run_script(&self, script: String) {
+        if let Err(err) = self.query_tx.send(script) {
+            tracing::warn!("File upload error: {err}");
+        }
+    }
+}
+
+pub(crate) struct CancelReads(FileTransfers);
+
+impl Drop for CancelReads {
+    fn drop(&mut self) {
+        self.0.cancel_all();
+    }
+}
+
+/// How much of a file has been read from the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileProgress {
+    /// The number of bytes read so far
+    pub bytes_read: u64,
+    /// The size of the file in bytes
+    pub total_bytes: u64,
+}
+
+impl FileProgress {
+    /// The fraction of the file that has been read, between 0 and 1
+    pub fn fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.bytes_read as f64 / self.total_bytes as f64
+        }
+    }
+}
+
+/// The files selected in a file input, streamed from the browser over the liveview socket when they are read
+pub(crate) struct LiveViewFileEngine {
+    upload: u32,
+    files: Vec<SelectedFile>,
+    transfers: FileTransfers,
+}
+
+impl LiveViewFileEngine {
+    fn file(&self, name: &str) -> Option<LiveViewFile> {
+        let file = self.files.iter().find(|file| file.name == name)?;
+        Some(LiveViewFile {
+            upload: self.upload,
+            name: file.name.clone(),
+            size: file.size,
+            transfers: self.transfers.clone(),
+        })
+    }
+}
+
+impl Drop for LiveViewFileEngine {
+    fn drop(&mut self) {
+        // The client holds on to the selected files until the server no longer needs them
+        self.transfers
+            .run_script(format!("window.ipc.releaseUpload({});", self.upload));
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileEngine for LiveViewFileEngine {
+    fn files(&self) -> Vec<String> {
+        self.files.iter().map(|file| file.name.clone()).collect()
+    }
+
+    async fn read_file(&self, file: &str) -> Option<Vec<u8>> {
+        self.file(file)?.read_with_progress(|_| {}).await
+    }
+
+    async fn read_file_to_string(&self, file: &str) -> Option<String> {
+        self.read_file(file)
+            .await
+            .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+    }
+
+    async fn get_native_file(&self, file: &str) -> Option<Box<dyn Any>> {
+        self.file(file).map(|file| Box::new(file) as Box<dyn Any>)
+    }
+}
+
+/// A file selected in the browser. This is the native file type of the liveview renderer.
+pub struct LiveViewFile {
+    upload: u32,
+    name: String,
+    size: u64,
+    transfers: FileTransfers,
+}
+
+impl LiveViewFile {
+    /// The name of the file
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The size of the file in bytes
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Read the whole file from the client, calling `on_progress` after every chunk
+    ///
+    /// Returns `None` if the file is larger than the maximum upload size of the
+    /// [`LiveViewPool`](crate::LiveViewPool), or if the client sends more data than the size of the file.
+    pub async fn read_with_progress(
+        &self,
+        mut on_progress: impl FnMut(FileProgress),
+    ) -> Option<Vec<u8>> {
+        if self.size > self.transfers.max_upload_size {
+            tracing::warn!(
+                "Refusing to read {:?} ({} bytes), it is larger than the maximum upload size of {} bytes",
+                self.name,
+                self.size,
+                self.transfers.max_upload_size
+            );
+            return None;
+        }
+
+        let mut progress = FileProgress {
+            bytes_read: 0,
+            total_bytes: self.size,
+        };
+        let mut contents = Vec::new();
+
+        if self.size > 0 {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let id = self.transfers.next_read.fetch_add(1, Ordering::Relaxed);
+            self.transfers.active_reads.lock().unwrap().insert(id, tx);
+            let mut read = ActiveRead {
+                id,
+                transfers: &self.transfers,
+                finished: false,
+            };
+
+            let name = serde_json::to_string(&self.name).unwrap();
+            self.transfers.run_script(format!(
+                "window.ipc.readFile({}, {name}, {}, {CHUNK_SIZE});",
+                self.upload, read.id
+            ));
+
+            while progress.bytes_read < progress.total_bytes {
+                match rx.recv().await? {
+                    FileChunk::Data(chunk) => {
+                        progress.bytes_read += chunk.len() as u64;
+                        // Dropping the read tells the client to stop sending the rest
+                        if progress.bytes_read > progress.total_bytes {
+                            tracing::warn!(
+                                "The client sent more data than the size of {:?}",
+                                self.name
+                            );
+                            return None;
+                        }
+                        contents.extend(chunk);
+                        on_progress(progress);
+                    }
+                    FileChunk::Error => {
+                        read.finished = true;
+                        return None;
+                    }
+                }
+            }
+            read.finished = true;
+        } else {
+            on_progress(progress);
+        }
+
+        Some(contents)
+    }
+}
+
+/// Removes a read from the active reads once it finishes or is cancelled
+struct ActiveRead<'a> {
+    id: u32,
+    transfers: &'a FileTransfers,
+    finished: bool,
+}
+
+impl Drop for ActiveRead<'_> {
+    fn drop(&mut self) {
+        let removed = self.transfers.active_reads.lock().unwrap().remove(&self.id);
+
+        // The future reading the file was dropped, so the client doesn't need to send the rest of it
+        if !self.finished && removed.map_or(false, |read| !read.is_closed()) {
+            self.transfers
+                .run_script(format!("window.ipc.cancelRead({});", self.id));
+        }
+    }
+}
+
+/// Helper trait to read files in liveview with progress reporting
+#[async_trait::async_trait(?Send)]
+pub trait LiveViewFileEngineExt {
+    /// Read a file from the client, calling `on_progress` after every chunk. Returns `None` if the file does not exist
+    /// or the client could not read it.
+    async fn read_file_with_progress<F: FnMut(FileProgress)>(
+        &self,
+        file: &str,
+        on_progress: F,
+    ) -> Option<Vec<u8>>;
+
+    /// Returns the liveview representation of a file
+    async fn get_liveview_file(&self, file: &str) -> Option<LiveViewFile>;
+}
+
+#[async_trait::async_trait(?Send)]
+impl LiveViewFileEngineExt for Arc<dyn FileEngine> {
+    async fn read_file_with_progress<F: FnMut(FileProgress)>(
+        &self,
+        file: &str,
+        on_progress: F,
+    ) -> Option<Vec<u8>> {
+        self.get_liveview_file(file)
+            .await?
+            .read_with_progress(on_progress)
+            .await
+    }
+
+    async fn get_liveview_file(&self, file: &str) -> Option<LiveViewFile> {
+        let native_file = self.get_native_file(file).await?;
+        let ret = native_file.downcast::<LiveViewFile>().ok()?;
+        Some(*ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(read: u32, contents: &[u8]) -> Vec<u8> {
+        let mut chunk = vec![FILE_CHUNK_MARKER];
+        chunk.extend(read.to_le_bytes());
+        chunk.extend(contents);
+        chunk
+    }
+
+    fn file(transfers: &FileTransfers, size: u64) -> LiveViewFile {
+        LiveViewFile {
+            upload: 0,
+            name: "file.txt".to_string(),
+            size,
+            transfers: transfers.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reassembles_chunks() {
+        let (query_tx, mut query_rx) = mpsc::unbounded_channel();
+        let transfers = FileTransfers::new(query_tx, DEFAULT_MAX_UPLOAD_SIZE);
+        let file = file(&transfers, 11);
+
+        let mut progress = Vec::new();
+        let read = file.read_with_progress(|p| progress.push(p.bytes_read));
+        let send = async {
+            let script = query_rx.recv().await.unwrap();
+            assert_eq!(
+                script,
+                format!("window.ipc.readFile(0, \"file.txt\", 0, {CHUNK_SIZE});")
+            );
+            transfers.receive_chunk(&chunk(0, b"hello "));
+            // chunks of other reads are ignored
+            transfers.receive_chunk(&chunk(1, b"other"));
+            transfers.receive_chunk(&chunk(0, b"world"));
+        };
+        let (contents, ()) = tokio::join!(read, send);
+
+        assert_eq!(contents.as_deref(), Some(&b"hello world"[..]));
+        assert_eq!(progress, [6, 11]);
+        assert!(transfers.active_reads.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_files_over_the_size_limit() {
+        let (query_tx, mut query_rx) = mpsc::unbounded_channel();
+        let transfers = FileTransfers::new(query_tx, 8);
+
+        assert_eq!(
+            file(&transfers, u64::MAX).read_with_progress(|_| {}).await,
+            None
+        );
+        // the file is never requested from the client
+        assert!(query_rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_more_data_than_the_file_size() {
+        let (query_tx, mut query_rx) = mpsc::unbounded_channel();
+        let transfers = FileTransfers::new(query_tx, DEFAULT_MAX_UPLOAD_SIZE);
+        let file = file(&transfers, 4);
+
+        let read = file.read_with_progress(|_| {});
+        let send = async {
+            query_rx.recv().await.unwrap();
+            transfers.receive_chunk(&chunk(0, b"too long"));
+        };
+        let (contents, ()) = tokio::join!(read, send);
+
+        assert_eq!(contents, None);
+        assert_eq!(query_rx.recv().await.unwrap(), "window.ipc.cancelRead(0);");
+    }
+}
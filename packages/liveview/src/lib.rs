@@ -22,9 +22,15 @@ pub mod adapters {
 
 pub use adapters::*;
 
+mod batching;
 mod element;
+mod file_upload;
 pub mod pool;
 mod query;
+mod session;
+pub use batching::EventBatching;
+pub use dioxus_interpreter_js::binary_protocol::EditEncoding;
+pub use file_upload::{FileProgress, LiveViewFile, LiveViewFileEngineExt};
 use futures_util::{SinkExt, StreamExt};
 pub use pool::*;
 mod eval;
@@ -45,40 +51,16 @@ use once_cell::sync::Lazy;
 
 static INTERPRETER_JS: Lazy<String> = Lazy::new(|| {
     let interpreter = dioxus_interpreter_js::INTERPRETER_JS;
+    // File contents are streamed to the server in chunks when the app reads them
     let serialize_file_uploads = r#"if (
       target.tagName === "INPUT" &&
       (event.type === "change" || event.type === "input")
     ) {
       const type = target.getAttribute("type");
       if (type === "file") {
-        async function read_files() {
-          const files = target.files;
-          const file_contents = {};
-
-          for (let i = 0; i < files.length; i++) {
-            const file = files[i];
-
-            file_contents[file.name] = Array.from(
-              new Uint8Array(await file.arrayBuffer())
-            );
-          }
-          let file_engine = {
-            files: file_contents,
-          };
-          contents.files = file_engine;
-
-          if (realId === null) {
-            return;
-          }
-          const message = serializeIpcMessage("user_event", {
-            name: name,
-            element: parseInt(realId),
-            data: contents,
-            bubbles,
-          });
-          window.ipc.postMessage(message);
+        if (realId !== null) {
+          window.ipc.fileEvent(target, name, parseInt(realId), contents, bubbles);
         }
-        read_files();
         return;
       }
     }"#;
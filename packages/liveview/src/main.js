@@ -7,37 +7,73 @@ function main() {
 
 class IPC {
   constructor(root) {
-    // connect to the websocket
+    this.root = root;
     window.interpreter = new Interpreter(root, new InterpreterConfig(false));
+    // the token of the session on the server, used to resume it if the
+    // socket reconnects
+    this.session = null;
+    this.retries = 0;
+    // files selected in file inputs, kept until the server releases them
+    this.uploads = new Map();
+    this.nextUpload = 0;
+    this.activeReads = new Set();
+    this.cancelledReads = new Set();
+    this.connect();
+  }
 
+  connect() {
     let ws = new WebSocket(WS_ADDR);
+    // binary edits are read directly from an ArrayBuffer
+    ws.binaryType = "arraybuffer";
 
-    function ping() {
-      ws.send("__ping__");
-    }
+    // the first edits of every connection rebuild the page from scratch
+    let fresh = true;
+    let pingInterval = null;
 
     ws.onopen = () => {
+      this.retries = 0;
       // we ping every 30 seconds to keep the websocket alive
-      setInterval(ping, 30000);
-      ws.send(serializeIpcMessage("initialize"));
+      pingInterval = setInterval(() => ws.send("__ping__"), 30000);
+      const params = this.session ? { session: this.session } : {};
+      ws.send(serializeIpcMessage("initialize", params));
     };
 
     ws.onerror = (err) => {
-      // todo: retry the connection
+      // the connection is retried once the socket closes
+    };
+
+    ws.onclose = () => {
+      clearInterval(pingInterval);
+      const delay = Math.min(1000 * 2 ** this.retries, 10000);
+      this.retries += 1;
+      setTimeout(() => this.connect(), delay);
     };
 
     ws.onmessage = (message) => {
-      // Ignore pongs
-      if (message.data != "__pong__") {
+      // Binary messages are always edits. Ignore pongs
+      if (message.data instanceof ArrayBuffer) {
+        if (fresh) {
+          fresh = false;
+          this.reset();
+        }
+        window.interpreter.handleBinaryEdits(message.data);
+      } else if (message.data != "__pong__") {
         const event = JSON.parse(message.data);
         switch (event.type) {
           case "edits":
+            if (fresh) {
+              fresh = false;
+              this.reset();
+            }
             let edits = event.data;
             window.interpreter.handleEdits(edits);
             break;
           case "query":
             Function("Eval", `"use strict";${event.data};`)();
             break;
+          case "session":
+            this.session = event.data;
+            break;
         }
       }
     };
@@ -45,7 +81,82 @@ class IPC {
     this.ws = ws;
   }
 
+  // Clear the page and its listeners so it can be rebuilt by the server
+  reset() {
+    const root = this.root.cloneNode(false);
+    this.root.replaceWith(root);
+    this.root = root;
+    window.interpreter = new Interpreter(
+      this.root,
+      new InterpreterConfig(false)
+    );
+  }
+
+  // Send an event from a file input. The contents of the files are sent
+  // later, when the server reads them
+  fileEvent(target, name, element, contents, bubbles) {
+    const upload = this.nextUpload++;
+    const files = Array.from(target.files);
+    this.uploads.set(upload, files);
+    this.postMessage(
+      serializeIpcMessage("file_event", {
+        name,
+        element,
+        data: contents,
+        bubbles,
+        upload,
+        files: files.map((file) => ({ name: file.name, size: file.size })),
+      })
+    );
+  }
+
+  // Stream a file to the server in binary chunks. Each chunk starts with
+  // 0xFF and the id of the read as a little endian u32
+  async readFile(upload, name, read, chunkSize) {
+    const ws = this.ws;
+    this.activeReads.add(read);
+    try {
+      const file = this.uploads.get(upload).find((file) => file.name == name);
+      for (let offset = 0; offset < file.size; offset += chunkSize) {
+        const cancelled = this.cancelledReads.delete(read);
+        if (cancelled || ws.readyState !== WebSocket.OPEN) {
+          return;
+        }
+        // wait for the socket to drain so large files don't fill up memory
+        while (ws.bufferedAmount > chunkSize * 4) {
+          await new Promise((resolve) => setTimeout(resolve, 10));
+        }
+        const data = await file.slice(offset, offset + chunkSize).arrayBuffer();
+        const chunk = new Uint8Array(data.byteLength + 5);
+        chunk[0] = 0xff;
+        new DataView(chunk.buffer).setUint32(1, read, true);
+        chunk.set(new Uint8Array(data), 5);
+        ws.send(chunk);
+      }
+    } catch (err) {
+      console.error("Failed to read file", err);
+      this.postMessage(serializeIpcMessage("file_error", { read }));
+    } finally {
+      this.activeReads.delete(read);
+      this.cancelledReads.delete(read);
+    }
+  }
+
+  // Only reads that are still in flight can be cancelled, a cancel for a
+  // finished read would never be cleaned up
+  cancelRead(read) {
+    if (this.activeReads.has(read)) {
+      this.cancelledReads.add(read);
+    }
+  }
+
+  releaseUpload(upload) {
+    this.uploads.delete(upload);
+  }
+
   postMessage(msg) {
-    this.ws.send(msg);
+    if (this.ws.readyState === WebSocket.OPEN) {
+      this.ws.send(msg);
+    }
   }
 }
@@ -1,19 +1,32 @@
 use crate::{
+    batching::{EventBatching, EventQueue},
     element::LiveviewElement,
     eval::init_eval,
+    file_upload::{FileTransfers, SelectedFile, DEFAULT_MAX_UPLOAD_SIZE, FILE_CHUNK_MARKER},
     query::{QueryEngine, QueryResult},
-    LiveViewError,
+    session::{box_socket, read_session, Connection, SessionHandle, Sessions},
+    EditEncoding, LiveViewError,
 };
-use dioxus_core::{prelude::*, Mutations};
-use dioxus_html::{EventData, HtmlEvent, MountedData};
-use futures_util::{pin_mut, SinkExt, StreamExt};
+use dioxus_core::{prelude::*, ElementId, Mutations};
+use dioxus_html::{EventData, FormData, HtmlEvent, MountedData};
+use dioxus_interpreter_js::binary_protocol::MutationEncoder;
+use futures_util::{pin_mut, FutureExt, SinkExt, StreamExt};
 use serde::Serialize;
-use std::{rc::Rc, time::Duration};
+use std::{rc::Rc, sync::Arc, time::Duration};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Instant,
+};
 use tokio_util::task::LocalPoolHandle;
 
 #[derive(Clone)]
 pub struct LiveViewPool {
     pub(crate) pool: LocalPoolHandle,
+    pub(crate) edit_encoding: EditEncoding,
+    pub(crate) reconnect_grace_period: Duration,
+    pub(crate) sessions: Sessions,
+    pub(crate) event_batching: EventBatching,
+    pub(crate) max_upload_size: u64,
 }
 
 impl Default for LiveViewPool {
@@ -26,9 +39,48 @@ impl LiveViewPool {
     pub fn new() -> Self {
         LiveViewPool {
             pool: LocalPoolHandle::new(16),
+            edit_encoding: EditEncoding::default(),
+            reconnect_grace_period: Duration::from_secs(30),
+            sessions: Sessions::default(),
+            event_batching: EventBatching::default(),
+            max_upload_size: DEFAULT_MAX_UPLOAD_SIZE,
         }
     }
 
+    /// Set the format edits are sent to the client in. Edits are sent in the binary format by default.
+    ///
+    /// JSON edits are larger, but they can be read in the network tab of the browser's devtools.
+    pub fn with_edit_encoding(mut self, encoding: EditEncoding) -> Self {
+        self.edit_encoding = encoding;
+        self
+    }
+
+    /// Set how long a VirtualDom is kept alive after its client disconnects. Defaults to 30 seconds.
+    ///
+    /// If the client reconnects within the grace period, it resumes the same VirtualDom with all of its state instead
+    /// of starting the app over. Set the grace period to zero to drop the VirtualDom as soon as the client disconnects.
+    pub fn with_reconnect_grace_period(mut self, grace_period: Duration) -> Self {
+        self.reconnect_grace_period = grace_period;
+        self
+    }
+
+    /// Set how events from the client are batched and how often edits are sent back
+    ///
+    /// By default, bursts of high frequency events like `mousemove` are coalesced into the latest event and edits are
+    /// sent as soon as the client can take them. See [`EventBatching`] for the options.
+    pub fn with_event_batching(mut self, event_batching: EventBatching) -> Self {
+        self.event_batching = event_batching;
+        self
+    }
+
+    /// Set the largest file in bytes that can be read from the client. Defaults to 100 MiB.
+    ///
+    /// Reading a larger file returns `None` without requesting any of its contents from the client.
+    pub fn with_max_upload_size(mut self, max_upload_size: u64) -> Self {
+        self.max_upload_size = max_upload_size;
+        self
+    }
+
     pub async fn launch(
         &self,
         ws: impl LiveViewSocket,
@@ -47,20 +99,70 @@ impl LiveViewPool {
             .await
     }
 
+    /// Launch a VirtualDom for a client, or resume the VirtualDom of the session the client reconnected to
+    ///
+    /// This waits for the client to send its initialize message and returns once the client disconnects. The
+    /// VirtualDom may outlive the connection for the reconnect grace period.
     pub async fn launch_virtualdom<F: FnOnce() -> VirtualDom + Send + 'static>(
         &self,
         ws: impl LiveViewSocket,
         make_app: F,
     ) -> Result<(), LiveViewError> {
-        match self.pool.spawn_pinned(move || run(make_app(), ws)).await {
-            Ok(Ok(_)) => Ok(()),
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(LiveViewError::SendingFailed),
+        let mut ws = box_socket(ws);
+        let session = read_session(&mut ws).await?;
+
+        let (done, disconnected) = oneshot::channel();
+        let mut connection = Connection { ws, done };
+
+        // Try to hand the socket to the session the client was connected to before
+        if let Some(token) = session {
+            match self.sessions.resume(&token, connection) {
+                Ok(()) => {
+                    return disconnected
+                        .await
+                        .unwrap_or(Err(LiveViewError::SendingFailed))
+                }
+                Err(returned) => connection = returned,
+            }
         }
+
+        // Otherwise start a new session and tell the client its token. The session is removed again if this returns
+        // early or the session panics, because the handle is dropped.
+        let (session, reconnections) = self.sessions.create();
+        let message =
+            serde_json::to_string(&ClientUpdate::Session(session.token().to_string())).unwrap();
+        connection.ws.send(message.into_bytes()).await?;
+
+        let grace_period = self.reconnect_grace_period;
+        let encoding = self.edit_encoding;
+        let batching = self.event_batching.clone();
+        let max_upload_size = self.max_upload_size;
+        self.pool.spawn_pinned(move || {
+            run_session(
+                make_app(),
+                connection,
+                SessionConfig {
+                    session,
+                    reconnections,
+                    grace_period,
+                    encoding,
+                    batching,
+                    max_upload_size,
+                },
+            )
+        });
+
+        // If the session panics, the sender is dropped
+        disconnected
+            .await
+            .unwrap_or(Err(LiveViewError::SendingFailed))
     }
 }
 
-/// A LiveViewSocket is a Sink and Stream of Strings that Dioxus uses to communicate with the client
+/// A LiveViewSocket is a Sink and Stream of byte messages that Dioxus uses to communicate with the client
+///
+/// Edits are sent as binary messages by default and are never valid UTF-8. Everything else Dioxus sends is JSON text.
+/// The client sends JSON text for events and binary messages for file uploads.
 ///
 /// Most websockets from most HTTP frameworks can be converted into a LiveViewSocket using the appropriate adapter.
 ///
@@ -76,15 +178,18 @@ impl LiveViewPool {
 ///         .sink_map_err(|_| LiveViewError::SendingFailed)
 /// }
 ///
-/// fn transform_rx(message: Result<Message, axum::Error>) -> Result<String, LiveViewError> {
-///     message
+/// fn transform_rx(message: Result<Message, axum::Error>) -> Result<Vec<u8>, LiveViewError> {
+///     Ok(message
 ///         .map_err(|_| LiveViewError::SendingFailed)?
-///         .into_text()
-///         .map_err(|_| LiveViewError::SendingFailed)
+///         .into_data())
 /// }
 ///
-/// async fn transform_tx(message: String) -> Result<Message, axum::Error> {
-///     Ok(Message::Text(message))
+/// async fn transform_tx(message: Vec<u8>) -> Result<Message, axum::Error> {
+///     // Binary edits are never valid UTF-8, everything else is JSON
+///     Ok(match String::from_utf8(message) {
+///         Ok(text) => Message::Text(text),
+///         Err(err) => Message::Binary(err.into_bytes()),
+///     })
 /// }
 /// ```
 pub trait LiveViewSocket:
@@ -107,134 +212,616 @@ impl<S> LiveViewSocket for S where
 ///
 /// This function makes it easy to integrate Dioxus LiveView with any socket-based framework.
 ///
-/// As long as your framework can provide a Sink and Stream of Strings, you can use this function.
+/// As long as your framework can provide a Sink and Stream of byte messages, you can use this function.
 ///
 /// You might need to transform the error types of the web backend into the LiveView error type.
-pub async fn run(mut vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
-    #[cfg(all(feature = "hot-reload", debug_assertions))]
-    let mut hot_reload_rx = {
-        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
-        dioxus_hot_reload::connect(move |template| {
-            let _ = tx.send(template);
-        });
-        rx
-    };
+pub async fn run(vdom: VirtualDom, ws: impl LiveViewSocket) -> Result<(), LiveViewError> {
+    run_with_encoding(vdom, ws, EditEncoding::default()).await
+}
 
-    // Create the a proxy for query engine
-    let (query_tx, mut query_rx) = tokio::sync::mpsc::unbounded_channel();
-    let query_engine = QueryEngine::new(query_tx);
-    vdom.base_scope().provide_context(query_engine.clone());
-    init_eval(vdom.base_scope());
+/// The primary event loop for the VirtualDom, sending edits to the client in the given format
+pub async fn run_with_encoding(
+    vdom: VirtualDom,
+    ws: impl LiveViewSocket,
+    encoding: EditEncoding,
+) -> Result<(), LiveViewError> {
+    LiveView::new(vdom, DEFAULT_MAX_UPLOAD_SIZE)
+        .serve(ws, encoding, EventBatching::default(), None)
+        .await?;
+    Ok(())
+}
 
-    // todo: use an efficient binary packed format for this
-    let edits = serde_json::to_string(&ClientUpdate::Edits(vdom.rebuild())).unwrap();
+struct SessionConfig {
+    session: SessionHandle,
+    reconnections: mpsc::UnboundedReceiver<Connection>,
+    grace_period: Duration,
+    encoding: EditEncoding,
+    batching: EventBatching,
+    max_upload_size: u64,
+}
 
-    // pin the futures so we can use select!
-    pin_mut!(ws);
+/// Serve every client that connects to a session until no client reconnects within the grace period
+async fn run_session(vdom: VirtualDom, first: Connection, mut config: SessionConfig) {
+    let mut liveview = LiveView::new(vdom, config.max_upload_size);
+    let mut connection = first;
 
-    // send the initial render to the client
-    ws.send(edits.into_bytes()).await?;
+    loop {
+        let Connection { ws, done } = connection;
+        let result = liveview
+            .serve(
+                ws,
+                config.encoding,
+                config.batching.clone(),
+                Some(&mut config.reconnections),
+            )
+            .await;
+
+        // A newer connection to the same session replaces the current one right away
+        let next = match result {
+            Ok(Some(next)) => {
+                _ = done.send(Ok(()));
+                Some(next)
+            }
+            Ok(None) => {
+                _ = done.send(Ok(()));
+                None
+            }
+            Err(err) => {
+                _ = done.send(Err(err));
+                None
+            }
+        };
 
-    // desktop uses this wrapper struct thing around the actual event itself
-    // this is sorta driven by tao/wry
-    #[derive(serde::Deserialize, Debug)]
-    #[serde(tag = "method", content = "params")]
-    enum IpcMessage {
-        #[serde(rename = "user_event")]
-        Event(HtmlEvent),
-        #[serde(rename = "query")]
-        Query(QueryResult),
+        connection = match next {
+            Some(next) => next,
+            None => {
+                match tokio::time::timeout(config.grace_period, config.reconnections.recv()).await {
+                    Ok(Some(next)) => next,
+                    _ => match config.session.expire(&mut config.reconnections) {
+                        Some(next) => next,
+                        None => return,
+                    },
+                }
+            }
+        };
     }
+}
 
-    loop {
+/// A VirtualDom along with the state it needs to talk to a client
+struct LiveView {
+    vdom: VirtualDom,
+    query_engine: QueryEngine,
+    query_rx: mpsc::UnboundedReceiver<String>,
+    file_transfers: FileTransfers,
+    #[cfg(all(feature = "hot-reload", debug_assertions))]
+    hot_reload_rx: mpsc::UnboundedReceiver<dioxus_hot_reload::HotReloadMsg>,
+    rebuilt: bool,
+}
+
+impl LiveView {
+    fn new(vdom: VirtualDom, max_upload_size: u64) -> Self {
         #[cfg(all(feature = "hot-reload", debug_assertions))]
-        let hot_reload_wait = hot_reload_rx.recv();
-        #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
-        let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
-
-        tokio::select! {
-            // poll any futures or suspense
-            _ = vdom.wait_for_work() => {}
-
-            evt = ws.next() => {
-                match evt.as_ref().map(|o| o.as_deref()) {
-                    // respond with a pong every ping to keep the websocket alive
-                    Some(Ok(b"__ping__")) => {
-                        ws.send(b"__pong__".to_vec()).await?;
+        let hot_reload_rx = {
+            let (tx, rx) = mpsc::unbounded_channel();
+            dioxus_hot_reload::connect(move |template| {
+                let _ = tx.send(template);
+            });
+            rx
+        };
+
+        // Create the a proxy for query engine
+        let (query_tx, query_rx) = mpsc::unbounded_channel();
+        let file_transfers = FileTransfers::new(query_tx.clone(), max_upload_size);
+        let query_engine = QueryEngine::new(query_tx);
+        vdom.base_scope().provide_context(query_engine.clone());
+        init_eval(vdom.base_scope());
+
+        Self {
+            vdom,
+            query_engine,
+            query_rx,
+            file_transfers,
+            #[cfg(all(feature = "hot-reload", debug_assertions))]
+            hot_reload_rx,
+            rebuilt: false,
+        }
+    }
+
+    /// Serve a client until it disconnects
+    ///
+    /// The first client gets a fresh rebuild of the VirtualDom, clients that reconnect get a replay of the current
+    /// state. If a new connection arrives on `reconnections`, the current client is dropped and the new connection is
+    /// returned.
+    async fn serve(
+        &mut self,
+        ws: impl LiveViewSocket,
+        encoding: EditEncoding,
+        batching: EventBatching,
+        mut reconnections: Option<&mut mpsc::UnboundedReceiver<Connection>>,
+    ) -> Result<Option<Connection>, LiveViewError> {
+        let Self {
+            vdom,
+            query_engine,
+            query_rx,
+            file_transfers,
+            #[cfg(all(feature = "hot-reload", debug_assertions))]
+            hot_reload_rx,
+            rebuilt,
+        } = self;
+
+        // Files can only be read while the client that selected them is connected
+        let _cancel_reads = file_transfers.cancel_reads_on_drop();
+
+        let mut edit_serializer = EditSerializer::new(encoding);
+        if std::mem::replace(rebuilt, true) {
+            edit_serializer.push(vdom.replay());
+        } else {
+            edit_serializer.push(vdom.rebuild());
+        }
+
+        // pin the futures so we can use select!
+        pin_mut!(ws);
+
+        // send the initial render to the client
+        if let Some(frame) = edit_serializer.take_frame() {
+            ws.send(frame).await?;
+        }
+
+        let mut events = EventQueue::new(batching);
+        let mut next_frame = Instant::now();
+
+        loop {
+            #[cfg(all(feature = "hot-reload", debug_assertions))]
+            let hot_reload_wait = hot_reload_rx.recv();
+            #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+            let hot_reload_wait: std::future::Pending<Option<()>> = std::future::pending();
+
+            let reconnection_wait = async {
+                match reconnections.as_mut() {
+                    Some(reconnections) => reconnections.recv().await,
+                    None => std::future::pending().await,
+                }
+            };
+
+            let debounce_wait = async {
+                match events.next_deadline() {
+                    Some(deadline) => tokio::time::sleep_until(deadline).await,
+                    None => std::future::pending().await,
+                }
+            };
+
+            tokio::select! {
+                // poll any futures or suspense
+                _ = vdom.wait_for_work() => {}
+
+                evt = ws.next() => {
+                    let mut messages = match evt {
+                        Some(Ok(message)) => vec![message],
+                        // log this I guess? when would we get an error here?
+                        Some(Err(_e)) => Vec::new(),
+                        None => return Ok(None),
+                    };
+
+                    // Read everything the client already sent so it is handled in a single render
+                    let mut closed = false;
+                    while messages.len() < events.config().max_batch_size {
+                        match ws.next().now_or_never() {
+                            Some(Some(Ok(message))) => messages.push(message),
+                            Some(Some(Err(_e))) => {}
+                            Some(None) => {
+                                closed = true;
+                                break;
+                            }
+                            None => break,
+                        }
                     }
-                    Some(Ok(evt)) => {
-                        if let Ok(message) = serde_json::from_str::<IpcMessage>(&String::from_utf8_lossy(evt)) {
-                            match message {
-                                IpcMessage::Event(evt) => {
-                                    // Intercept the mounted event and insert a custom element type
-                                    if let EventData::Mounted = &evt.data {
-                                        let element = LiveviewElement::new(evt.element, query_engine.clone());
-                                        vdom.handle_event(
-                                            &evt.name,
-                                            Rc::new(MountedData::new(element)),
-                                            evt.element,
-                                            evt.bubbles,
-                                        );
-                                    }
-                                    else{
-                                        vdom.handle_event(
-                                            &evt.name,
-                                            evt.data.into_any(),
-                                            evt.element,
-                                            evt.bubbles,
-                                        );
-                                    }
-                                }
-                                IpcMessage::Query(result) => {
-                                    query_engine.send(result);
-                                },
+
+                    for message in messages {
+                        match ClientMessage::parse(&message) {
+                            // respond with a pong every ping to keep the websocket alive
+                            ClientMessage::Ping => ws.send(b"__pong__".to_vec()).await?,
+                            ClientMessage::Event(evt) => events.push(evt),
+                            ClientMessage::Query(result) => query_engine.send(result),
+                            ClientMessage::FileEvent(evt) => {
+                                let FileEvent {
+                                    name,
+                                    element,
+                                    bubbles,
+                                    data,
+                                    upload,
+                                    files,
+                                } = *evt;
+                                let engine = file_transfers.engine(upload, files);
+                                events.push(HtmlEvent {
+                                    name,
+                                    element,
+                                    bubbles,
+                                    data: EventData::Form(FormData {
+                                        files: Some(Arc::new(engine)),
+                                        ..data
+                                    }),
+                                });
                             }
+                            ClientMessage::FileChunk => file_transfers.receive_chunk(&message),
+                            ClientMessage::FileError(read) => file_transfers.receive_error(read),
+                            ClientMessage::Unknown => {}
                         }
                     }
-                    // log this I guess? when would we get an error here?
-                    Some(Err(_e)) => {}
-                    None => return Ok(()),
+
+                    if closed {
+                        return Ok(None);
+                    }
+                }
+
+                // a debounced event is ready to be handled
+                _ = debounce_wait => {}
+
+                // the edits held back by the frame interval can be sent
+                _ = tokio::time::sleep_until(next_frame), if edit_serializer.has_pending() => {}
+
+                // handle any new queries
+                Some(query) = query_rx.recv() => {
+                    ws.send(serde_json::to_string(&ClientUpdate::Query(query)).unwrap().into_bytes()).await?;
+                }
+
+                // the client reconnected to this session on a new socket
+                Some(connection) = reconnection_wait => {
+                    return Ok(Some(connection));
+                }
+
+                Some(msg) = hot_reload_wait => {
+                    #[cfg(all(feature = "hot-reload", debug_assertions))]
+                    match msg{
+                        dioxus_hot_reload::HotReloadMsg::UpdateTemplate(new_template) => {
+                            vdom.replace_template(new_template);
+                        }
+                        dioxus_hot_reload::HotReloadMsg::Shutdown => {
+                            std::process::exit(0);
+                        },
+                    }
+                    #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
+                    let () = msg;
                 }
             }
 
-            // handle any new queries
-            Some(query) = query_rx.recv() => {
-                ws.send(serde_json::to_string(&ClientUpdate::Query(query)).unwrap().into_bytes()).await?;
+            for evt in events.take_ready() {
+                handle_event(vdom, query_engine, evt);
+            }
+
+            let edits = vdom
+                .render_with_deadline(tokio::time::sleep(Duration::from_millis(10)))
+                .await;
+            edit_serializer.push(edits);
+
+            // Edits rendered before the next frame is due are merged into it. Sending waits for the client to
+            // accept the frame, and the events that arrive in the meantime are handled together afterwards.
+            if Instant::now() >= next_frame {
+                if let Some(frame) = edit_serializer.take_frame() {
+                    ws.send(frame).await?;
+                    next_frame = Instant::now() + events.config().min_frame_interval;
+                }
             }
+        }
+    }
+}
+
+/// A message sent by the client
+enum ClientMessage {
+    Ping,
+    Event(HtmlEvent),
+    Query(QueryResult),
+    /// A file input changed. The files are read later with binary chunks.
+    FileEvent(Box<FileEvent>),
+    FileChunk,
+    FileError(u32),
+    Unknown,
+}
+
+/// An event from a file input along with the files the user selected
+#[derive(serde::Deserialize)]
+struct FileEvent {
+    name: String,
+    element: ElementId,
+    bubbles: bool,
+    data: FormData,
+    upload: u32,
+    files: Vec<SelectedFile>,
+}
+
+impl ClientMessage {
+    fn parse(message: &[u8]) -> Self {
+        // desktop uses this wrapper struct thing around the actual event itself
+        // this is sorta driven by tao/wry
+        #[derive(serde::Deserialize, Debug)]
+        #[serde(tag = "method", content = "params")]
+        enum IpcMessage {
+            #[serde(rename = "user_event")]
+            Event(HtmlEvent),
+            #[serde(rename = "query")]
+            Query(QueryResult),
+            #[serde(rename = "file_event")]
+            FileEvent(Box<FileEvent>),
+            #[serde(rename = "file_error")]
+            FileError { read: u32 },
+        }
+
+        if message == b"__ping__" {
+            return Self::Ping;
+        }
+
+        if message.first() == Some(&FILE_CHUNK_MARKER) {
+            return Self::FileChunk;
+        }
+
+        match serde_json::from_slice::<IpcMessage>(message) {
+            Ok(IpcMessage::Event(evt)) => Self::Event(evt),
+            Ok(IpcMessage::Query(result)) => Self::Query(result),
+            Ok(IpcMessage::FileEvent(evt)) => Self::FileEvent(evt),
+            Ok(IpcMessage::FileError { read }) => Self::FileError(read),
+            Err(_) => Self::Unknown,
+        }
+    }
+}
+
+fn handle_event(vdom: &mut VirtualDom, query_engine: &QueryEngine, evt: HtmlEvent) {
+    // Intercept the mounted event and insert a custom element type
+    if let EventData::Mounted = &evt.data {
+        let element = LiveviewElement::new(evt.element, query_engine.clone());
+        vdom.handle_event(
+            &evt.name,
+            Rc::new(MountedData::new(element)),
+            evt.element,
+            evt.bubbles,
+        );
+    } else {
+        vdom.handle_event(&evt.name, evt.data.into_any(), evt.element, evt.bubbles);
+    }
+}
+
+/// Turns mutations into messages for the client in the format the pool was configured with
+///
+/// Mutations are buffered until the next frame is taken, so several renders can be sent to the client at once.
+enum EditSerializer {
+    Json(Option<serde_json::Value>),
+    Binary {
+        encoder: MutationEncoder,
+        pending: Vec<u8>,
+    },
+}
+
+impl EditSerializer {
+    fn new(encoding: EditEncoding) -> Self {
+        match encoding {
+            EditEncoding::Json => Self::Json(None),
+            EditEncoding::Binary => Self::Binary {
+                encoder: MutationEncoder::new(),
+                pending: Vec::new(),
+            },
+        }
+    }
+
+    /// Add a batch of mutations to the next frame
+    fn push(&mut self, edits: Mutations) {
+        if edits.edits.is_empty() && edits.templates.is_empty() {
+            return;
+        }
 
-            Some(msg) = hot_reload_wait => {
-                #[cfg(all(feature = "hot-reload", debug_assertions))]
-                match msg{
-                    dioxus_hot_reload::HotReloadMsg::UpdateTemplate(new_template) => {
-                        vdom.replace_template(new_template);
+        match self {
+            Self::Json(pending) => {
+                let value = serde_json::to_value(&edits).unwrap();
+                match pending {
+                    Some(pending) => {
+                        for key in ["templates", "edits"] {
+                            if let (Some(merged), Some(new)) =
+                                (pending[key].as_array_mut(), value[key].as_array())
+                            {
+                                merged.extend(new.iter().cloned());
+                            }
+                        }
                     }
-                    dioxus_hot_reload::HotReloadMsg::Shutdown => {
-                        std::process::exit(0);
-                    },
+                    None => *pending = Some(value),
                 }
-                #[cfg(not(all(feature = "hot-reload", debug_assertions)))]
-                let () = msg;
             }
+            // The client decodes frames that are sent back to back in order
+            Self::Binary { encoder, pending } => pending.extend(encoder.encode(&edits)),
         }
+    }
 
-        let edits = vdom
-            .render_with_deadline(tokio::time::sleep(Duration::from_millis(10)))
-            .await;
+    fn has_pending(&self) -> bool {
+        match self {
+            Self::Json(pending) => pending.is_some(),
+            Self::Binary { pending, .. } => !pending.is_empty(),
+        }
+    }
 
-        ws.send(
-            serde_json::to_string(&ClientUpdate::Edits(edits))
-                .unwrap()
-                .into_bytes(),
-        )
-        .await?;
+    /// Take every mutation pushed since the last frame as a single message
+    fn take_frame(&mut self) -> Option<Vec<u8>> {
+        match self {
+            Self::Json(pending) => pending.take().map(|edits| {
+                serde_json::to_string(&ClientUpdate::Edits(edits))
+                    .unwrap()
+                    .into_bytes()
+            }),
+            Self::Binary { pending, .. } => (!pending.is_empty()).then(|| std::mem::take(pending)),
+        }
     }
 }
 
 #[derive(Serialize)]
 #[serde(tag = "type", content = "data")]
-enum ClientUpdate<'a> {
+enum ClientUpdate {
     #[serde(rename = "edits")]
-    Edits(Mutations<'a>),
+    Edits(serde_json::Value),
     #[serde(rename = "query")]
     Query(String),
+    #[serde(rename = "session")]
+    Session(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dioxus::prelude::*;
+    use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+    use futures_util::{Sink, Stream};
+    use std::{
+        future::Future,
+        pin::Pin,
+        sync::atomic::{AtomicUsize, Ordering},
+        task::{Context, Poll},
+    };
+
+    fn app(cx: Scope) -> Element {
+        render! { div { "hello" } }
+    }
+
+    /// The server side of an in memory socket
+    struct TestSocket {
+        incoming: UnboundedReceiver<Result<Vec<u8>, LiveViewError>>,
+        outgoing: UnboundedSender<Vec<u8>>,
+    }
+
+    impl Stream for TestSocket {
+        type Item = Result<Vec<u8>, LiveViewError>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.incoming.poll_next_unpin(cx)
+        }
+    }
+
+    impl Sink<Vec<u8>> for TestSocket {
+        type Error = LiveViewError;
+
+        fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn start_send(self: Pin<&mut Self>, item: Vec<u8>) -> Result<(), Self::Error> {
+            self.outgoing
+                .unbounded_send(item)
+                .map_err(|_| LiveViewError::SendingFailed)
+        }
+
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    /// The client side of an in memory socket
+    struct TestClient {
+        _to_server: UnboundedSender<Result<Vec<u8>, LiveViewError>>,
+        from_server: UnboundedReceiver<Vec<u8>>,
+    }
+
+    impl TestClient {
+        /// Read the first message from the server, and return the token if it started a new session
+        async fn new_session(&mut self) -> Option<String> {
+            #[derive(serde::Deserialize)]
+            struct Update {
+                r#type: String,
+                data: serde_json::Value,
+            }
+
+            let message = self.from_server.next().await.unwrap();
+            serde_json::from_slice::<Update>(&message)
+                .ok()
+                .filter(|update| update.r#type == "session")
+                .and_then(|update| update.data.as_str().map(String::from))
+        }
+    }
+
+    /// Connect a client that resumes the session with the token, if there is one
+    fn connect(session: Option<&str>) -> (TestSocket, TestClient) {
+        let (to_server, incoming) = unbounded();
+        let (outgoing, from_server) = unbounded();
+        let initialize = match session {
+            Some(token) => {
+                serde_json::json!({ "method": "initialize", "params": { "session": token } })
+            }
+            None => serde_json::json!({ "method": "initialize" }),
+        };
+        to_server
+            .unbounded_send(Ok(initialize.to_string().into_bytes()))
+            .unwrap();
+        (
+            TestSocket { incoming, outgoing },
+            TestClient {
+                _to_server: to_server,
+                from_server,
+            },
+        )
+    }
+
+    /// Launch the app, counting how often a new VirtualDom is created
+    fn launch(
+        pool: &LiveViewPool,
+        socket: TestSocket,
+        launches: &Arc<AtomicUsize>,
+    ) -> impl Future<Output = Result<(), LiveViewError>> {
+        let pool = pool.clone();
+        let launches = launches.clone();
+        async move {
+            pool.launch_virtualdom(socket, move || {
+                launches.fetch_add(1, Ordering::SeqCst);
+                VirtualDom::new(app)
+            })
+            .await
+        }
+    }
+
+    /// Connect a client, read its first message and disconnect it again
+    async fn visit(
+        pool: &LiveViewPool,
+        session: Option<&str>,
+        launches: &Arc<AtomicUsize>,
+    ) -> Option<String> {
+        let (socket, mut client) = connect(session);
+        let (_, token) = tokio::join!(launch(pool, socket, launches), async move {
+            client.new_session().await
+        });
+        token
+    }
+
+    #[tokio::test]
+    async fn resumes_sessions_within_the_grace_period() {
+        let pool = LiveViewPool::new().with_reconnect_grace_period(Duration::from_secs(60));
+        let launches = Arc::new(AtomicUsize::new(0));
+
+        let token = visit(&pool, None, &launches).await.unwrap();
+        // the client resumes the same VirtualDom, so it doesn't get a new session
+        assert_eq!(visit(&pool, Some(&token), &launches).await, None);
+        assert_eq!(launches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn sessions_expire_after_the_grace_period() {
+        let pool = LiveViewPool::new().with_reconnect_grace_period(Duration::from_millis(10));
+        let launches = Arc::new(AtomicUsize::new(0));
+
+        let token = visit(&pool, None, &launches).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(pool.sessions.is_empty());
+
+        let new_token = visit(&pool, Some(&token), &launches).await.unwrap();
+        assert_ne!(token, new_token);
+        assert_eq!(launches.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn sessions_that_fail_to_start_are_removed() {
+        let pool = LiveViewPool::new();
+        let launches = Arc::new(AtomicUsize::new(0));
+
+        // the client disconnects before it receives its session
+        let (socket, client) = connect(None);
+        drop(client.from_server);
+        assert!(launch(&pool, socket, &launches).await.is_err());
+
+        assert!(pool.sessions.is_empty());
+        assert_eq!(launches.load(Ordering::SeqCst), 0);
+    }
 }
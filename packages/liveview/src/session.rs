@@ -0,0 +1,140 @@
+//! Sessions keep a VirtualDom alive after its socket disconnects so the client can reconnect and pick up where it left off
+
+use crate::{LiveViewError, LiveViewSocket};
+use futures_util::{Sink, Stream, StreamExt};
+use rand::{distributions::Alphanumeric, Rng};
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{mpsc, oneshot};
+
+/// A socket with its type erased so it can be handed to a session running on another thread
+pub(crate) type BoxedSocket = Pin<Box<dyn ErasedSocket>>;
+
+pub(crate) trait ErasedSocket:
+    Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+impl<S> ErasedSocket for S where
+    S: Sink<Vec<u8>, Error = LiveViewError> + Stream<Item = Result<Vec<u8>, LiveViewError>> + Send
+{
+}
+
+pub(crate) fn box_socket(ws: impl LiveViewSocket) -> BoxedSocket {
+    Box::pin(ws)
+}
+
+/// A client connected to a session, along with the channel to report back to once it disconnects
+pub(crate) struct Connection {
+    pub(crate) ws: BoxedSocket,
+    pub(crate) done: oneshot::Sender<Result<(), LiveViewError>>,
+}
+
+/// The sessions that are currently running, keyed by their token
+#[derive(Clone, Default)]
+pub(crate) struct Sessions {
+    sessions: Arc<Mutex<HashMap<String, mpsc::UnboundedSender<Connection>>>>,
+}
+
+impl Sessions {
+    /// Register a new session with a random token
+    pub(crate) fn create(&self) -> (SessionHandle, mpsc::UnboundedReceiver<Connection>) {
+        let token: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.sessions.lock().unwrap().insert(token.clone(), tx);
+        let handle = SessionHandle {
+            token,
+            sessions: self.clone(),
+        };
+        (handle, rx)
+    }
+
+    /// Send a connection to the session with this token. If the session no longer exists, the connection is returned.
+    pub(crate) fn resume(&self, token: &str, connection: Connection) -> Result<(), Connection> {
+        match self.sessions.lock().unwrap().get(token) {
+            Some(session) => session.send(connection).map_err(|err| err.0),
+            None => Err(connection),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.sessions.lock().unwrap().is_empty()
+    }
+}
+
+/// A session registered in [`Sessions`]. The session is removed once the handle is dropped, so a session that fails to
+/// start or panics can't be resumed.
+pub(crate) struct SessionHandle {
+    token: String,
+    sessions: Sessions,
+}
+
+impl SessionHandle {
+    pub(crate) fn token(&self) -> &str {
+        &self.token
+    }
+
+    /// Remove the session unless a client reconnected to it while it was expiring
+    pub(crate) fn expire(
+        &self,
+        reconnections: &mut mpsc::UnboundedReceiver<Connection>,
+    ) -> Option<Connection> {
+        // Holding the lock means no other client can send a connection while we check
+        let mut sessions = self.sessions.sessions.lock().unwrap();
+        match reconnections.try_recv() {
+            Ok(connection) => Some(connection),
+            Err(_) => {
+                sessions.remove(&self.token);
+                None
+            }
+        }
+    }
+}
+
+impl Drop for SessionHandle {
+    fn drop(&mut self) {
+        // The lock may be poisoned if the session panicked
+        if let Ok(mut sessions) = self.sessions.sessions.lock() {
+            sessions.remove(&self.token);
+        }
+    }
+}
+
+/// Wait for the client to send the initialize message and return the session it wants to resume, if any
+pub(crate) async fn read_session(ws: &mut BoxedSocket) -> Result<Option<String>, LiveViewError> {
+    #[derive(serde::Deserialize)]
+    struct Initialize {
+        method: String,
+        #[serde(default)]
+        params: InitializeParams,
+    }
+
+    #[derive(serde::Deserialize, Default)]
+    struct InitializeParams {
+        session: Option<String>,
+    }
+
+    loop {
+        let message = match ws.next().await {
+            Some(message) => message?,
+            None => return Err(LiveViewError::SendingFailed),
+        };
+
+        if message == b"__ping__" {
+            continue;
+        }
+
+        return Ok(serde_json::from_slice::<Initialize>(&message)
+            .ok()
+            .filter(|init| init.method == "initialize")
+            .and_then(|init| init.params.session));
+    }
+}
@@ -1,8 +1,9 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Path;
+use syn::{Expr, Path};
 
 use crate::nest::{Nest, NestId};
+use crate::route::parse_options;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LayoutId(pub usize);
@@ -11,6 +12,8 @@ pub struct LayoutId(pub usize);
 pub struct Layout {
     pub comp: Path,
     pub active_nests: Vec<NestId>,
+    pub guard: Option<Expr>,
+    pub loader: Option<Expr>,
 }
 
 impl Layout {
@@ -35,6 +38,22 @@ impl Layout {
         let _ = input.parse::<syn::Token![,]>();
         let comp: Path = input.parse()?;
 
-        Ok(Self { comp, active_nests })
+        // Then parse the optional guard and loader
+        let mut guard = None;
+        let mut loader = None;
+        for (name, value) in parse_options(input, &["guard", "loader"])? {
+            if name == "guard" {
+                guard = Some(value);
+            } else {
+                loader = Some(value);
+            }
+        }
+
+        Ok(Self {
+            comp,
+            active_nests,
+            guard,
+            loader,
+        })
     }
 }
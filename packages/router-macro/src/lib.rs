@@ -36,7 +36,8 @@ mod segment;
 /// 1. Static Segments: "/static"
 /// 2. Dynamic Segments: "/:dynamic" (where dynamic has a type that is FromStr in all child Variants)
 /// 3. Catch all Segments: "/:..segments" (where segments has a type that is FromSegments in all child Variants)
-/// 4. Query Segments: "/?:query" (where query has a type that is FromQuery in all child Variants)
+/// 4. Query Segments: "/?:query" (where query has a type that is FromQuery in all child Variants). A lone query segment receives the whole query string
+/// 5. Query Arguments: "/?:term&:page" (where each argument has a type that is FromStr, Display and Default). Named parsing is used when the query has more than one `&`-separated argument. Each argument is parsed from `name=value` in the query string. Missing arguments use their default value
 ///
 /// Routes are matched:
 /// 1. By there specificity this order: Query Routes ("/?:query"), Static Routes ("/route"), Dynamic Routes ("/:route"), Catch All Routes ("/:..route")
@@ -197,9 +198,93 @@ mod segment;
 ///     Home {},
 /// }
 /// ```
+///
+/// # `#[guard(function)]`
+///
+/// The `#[guard]` attribute adds a navigation guard to a route. It takes 1 parameter:
+/// - `function`: An async function (or a closure returning a future) that takes a `NavigationRequest` and returns a `GuardResult`
+///
+/// The guard runs before the router navigates to the route. It can allow the navigation, cancel it or redirect to another target. A route can have multiple guards, they run in order.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     // Redirects to the Index route unless the user is logged in
+///     #[route("/admin")]
+///     #[guard(|_| async { if logged_in() { GuardResult::Allow } else { GuardResult::Redirect(Route::Index {}.into()) } })]
+///     Admin {},
+/// }
+/// ```
+///
+/// Layouts can have a guard too. It runs before the router navigates to any route inside the layout, before the guards of the route:
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[layout(AdminFrame, guard = require_login)]
+///         #[route("/admin")]
+///         Admin {},
+///         #[route("/admin/users")]
+///         Users {},
+///     #[end_layout]
+///     #[route("/")]
+///     Index {},
+/// }
+/// ```
+///
+/// # Loaders
+///
+/// Routes and layouts can load data before they are rendered with a `loader = function` argument after the component:
+/// - `function`: An async function (or a function returning a future) that loads the data. A route loader takes the fields of the route in the order they are declared. A layout loader takes the dynamic segments of the nests the layout is in.
+///
+/// The loaders of the route and its layouts run in parallel when the router navigates to the route. Components read the data of the layout or route they are rendered in with `use_loader_data`.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[nest("/team/:team")]
+///         #[layout(TeamFrame, loader = load_team)]
+///             #[route("/user/:id", User, loader = load_user)]
+///             User { team: String, id: usize },
+/// }
+///
+/// async fn load_team(team: String) -> Team { todo!() }
+///
+/// async fn load_user(team: String, id: usize) -> UserData { todo!() }
+/// ```
+///
+/// # `#[static_routes(function)]`
+///
+/// The `#[static_routes]` attribute lists the concrete routes of a route with dynamic segments, so static site generation can render them. It takes 1 parameter:
+/// - `function`: A function that takes no arguments and returns an iterator of routes
+///
+/// Routes without dynamic segments are always generated and don't need this attribute.
+///
+/// ```rust, skip
+/// #[derive(Clone, Debug, PartialEq, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     #[route("/post/:id")]
+///     #[static_routes(|| (0..10).map(|id| Route::Post { id }))]
+///     Post { id: usize },
+/// }
+/// ```
 #[proc_macro_derive(
     Routable,
-    attributes(route, nest, end_nest, layout, end_layout, redirect, child)
+    attributes(
+        route,
+        nest,
+        end_nest,
+        layout,
+        end_layout,
+        redirect,
+        child,
+        guard,
+        static_routes
+    )
 )]
 pub fn routable(input: TokenStream) -> TokenStream {
     let routes_enum = parse_macro_input!(input as syn::ItemEnum);
@@ -485,8 +570,8 @@ impl RouteEnum {
                 fn from_str(s: &str) -> Result<Self, Self::Err> {
                     let route = s;
                     let (route, _hash) = route.split_once('#').unwrap_or((route, ""));
-                    let (route, query) = route.split_once('?').unwrap_or((route, ""));
-                    let query = dioxus_router::exports::urlencoding::decode(query).unwrap_or(query.into());
+                    let (route, raw_query) = route.split_once('?').unwrap_or((route, ""));
+                    let query = dioxus_router::exports::urlencoding::decode(raw_query).unwrap_or(raw_query.into());
                     let mut segments = route.split('/').map(|s| dioxus_router::exports::urlencoding::decode(s).unwrap_or(s.into()));
                     // skip the first empty segment
                     if s.starts_with('/') {
@@ -583,12 +668,68 @@ impl RouteEnum {
             matches.push(route.routable_match(&self.layouts, &self.nests));
         }
 
+        // Only override the default guards if any route or layout has a guard
+        let has_guards = self.layouts.iter().any(|layout| layout.guard.is_some())
+            || self.routes.iter().any(|route| !route.guards.is_empty());
+        let guards_impl = has_guards.then(|| {
+            let guards = self
+                .routes
+                .iter()
+                .map(|route| route.guards_match(&self.layouts));
+            quote! {
+                fn guards(&self) -> Vec<dioxus_router::guard::NavigationGuard<Self>> {
+                    match self {
+                        #(#guards)*
+                    }
+                }
+            }
+        });
+
+        // Only override the default loaders if any route or layout has a loader
+        let has_loaders = self.layouts.iter().any(|layout| layout.loader.is_some())
+            || self.routes.iter().any(|route| route.loader.is_some());
+        let loaders_impl = has_loaders.then(|| {
+            let loaders = self
+                .routes
+                .iter()
+                .map(|route| route.loaders_match(&self.layouts, &self.nests));
+            quote! {
+                fn loaders(&self) -> Vec<Option<dioxus_router::loader::RouteLoader>> {
+                    match self {
+                        #(#loaders)*
+                    }
+                }
+            }
+        });
+
+        // Only override the default enumerated routes if any route lists them
+        let static_routes = self
+            .routes
+            .iter()
+            .flat_map(|route| &route.static_routes)
+            .collect::<Vec<_>>();
+        let enumerated_routes_impl = (!static_routes.is_empty()).then(|| {
+            quote! {
+                fn enumerated_routes() -> Vec<Self> {
+                    let mut routes = Vec::new();
+                    #(routes.extend((#static_routes)());)*
+                    routes
+                }
+            }
+        });
+
         quote! {
             impl dioxus_router::routable::Routable for #name where Self: Clone {
                 const SITE_MAP: &'static [dioxus_router::routable::SiteMapSegment] = &[
                     #(#site_map,)*
                 ];
 
+                #guards_impl
+
+                #loaders_impl
+
+                #enumerated_routes_impl
+
                 fn render<'a>(&self, cx: &'a dioxus::prelude::ScopeState, level: usize) -> dioxus::prelude::Element<'a> {
                     let myself = self.clone();
                     match (level, myself) {
@@ -1,27 +1,79 @@
 use quote::quote;
-use syn::{Ident, Type};
+use syn::{ext::IdentExt, Ident, Type};
 
 use proc_macro2::TokenStream as TokenStream2;
 
 #[derive(Debug)]
-pub struct QuerySegment {
+pub enum QuerySegment {
+    /// `?:query` passes the whole query string to one field
+    Single(QueryArgument),
+    /// `?:term&:page` parses each argument of the query string into its own field
+    Segments(Vec<QueryArgument>),
+}
+
+#[derive(Debug)]
+pub struct QueryArgument {
     pub ident: Ident,
     pub ty: Type,
 }
 
 impl QuerySegment {
+    pub fn contains_ident(&self, ident: &Ident) -> bool {
+        match self {
+            QuerySegment::Single(argument) => &argument.ident == ident,
+            QuerySegment::Segments(arguments) => {
+                arguments.iter().any(|argument| &argument.ident == ident)
+            }
+        }
+    }
+
     pub fn parse(&self) -> TokenStream2 {
-        let ident = &self.ident;
-        let ty = &self.ty;
-        quote! {
-            let #ident = <#ty as dioxus_router::routable::FromQuery>::from_query(&*query);
+        match self {
+            QuerySegment::Single(QueryArgument { ident, ty }) => {
+                quote! {
+                    let #ident = <#ty as dioxus_router::routable::FromQuery>::from_query(&*query);
+                }
+            }
+            QuerySegment::Segments(arguments) => {
+                let parse_arguments = arguments.iter().map(|QueryArgument { ident, ty }| {
+                    let name = ident.unraw().to_string();
+                    quote! {
+                        let #ident = dioxus_router::routable::parse_query_argument::<#ty>(raw_query, #name);
+                    }
+                });
+                quote! {
+                    #(#parse_arguments)*
+                }
+            }
         }
     }
 
     pub fn write(&self) -> TokenStream2 {
-        let ident = &self.ident;
-        quote! {
-            write!(f, "?{}", #ident)?;
+        match self {
+            QuerySegment::Single(QueryArgument { ident, .. }) => {
+                quote! {
+                    write!(f, "?{}", #ident)?;
+                }
+            }
+            QuerySegment::Segments(arguments) => {
+                let write_arguments = arguments.iter().enumerate().map(|(i, argument)| {
+                    let ident = &argument.ident;
+                    let separator = if i == 0 { "?" } else { "&" };
+                    let name = ident.unraw().to_string();
+                    quote! {
+                        write!(
+                            f,
+                            "{}{}={}",
+                            #separator,
+                            #name,
+                            dioxus_router::exports::urlencoding::encode(&#ident.to_string())
+                        )?;
+                    }
+                });
+                quote! {
+                    #(#write_arguments)*
+                }
+            }
         }
     }
 }
@@ -2,6 +2,7 @@ use quote::{format_ident, quote};
 use syn::parse::Parse;
 use syn::parse::ParseStream;
 use syn::parse_quote;
+use syn::Expr;
 use syn::Field;
 use syn::Path;
 use syn::Type;
@@ -21,22 +22,64 @@ use crate::segment::RouteSegment;
 struct RouteArgs {
     route: LitStr,
     comp_name: Option<Path>,
+    loader: Option<Expr>,
 }
 
 impl Parse for RouteArgs {
     fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
         let route = input.parse::<LitStr>()?;
 
+        let _ = input.parse::<syn::Token![,]>();
+        // The component is optional, so `loader = ..` may follow the route directly
+        let comp_name = match input.peek(Ident) && input.peek2(syn::Token![=]) {
+            true => None,
+            false => input.parse().ok(),
+        };
+
+        let mut loader = None;
+        for (name, value) in parse_options(input, &["loader"])? {
+            if name == "loader" {
+                loader = Some(value);
+            }
+        }
+
         Ok(RouteArgs {
             route,
-            comp_name: {
-                let _ = input.parse::<syn::Token![,]>();
-                input.parse().ok()
-            },
+            comp_name,
+            loader,
         })
     }
 }
 
+/// Parse the `name = value` options that follow the arguments of a route or layout attribute
+pub(crate) fn parse_options(
+    input: ParseStream<'_>,
+    allowed: &[&str],
+) -> syn::Result<Vec<(Ident, Expr)>> {
+    let mut options = Vec::new();
+    loop {
+        let _ = input.parse::<syn::Token![,]>();
+        if input.is_empty() {
+            return Ok(options);
+        }
+
+        let name: Ident = input.parse()?;
+        if !allowed.iter().any(|allowed| name == allowed) {
+            let expected = allowed
+                .iter()
+                .map(|allowed| format!("`{allowed} = ..`"))
+                .collect::<Vec<_>>()
+                .join(" or ");
+            return Err(syn::Error::new(
+                name.span(),
+                format!("Expected {expected}, found `{name}`"),
+            ));
+        }
+        input.parse::<syn::Token![=]>()?;
+        options.push((name, input.parse()?));
+    }
+}
+
 struct ChildArgs {
     route: LitStr,
 }
@@ -58,6 +101,9 @@ pub(crate) struct Route {
     pub query: Option<QuerySegment>,
     pub nests: Vec<NestId>,
     pub layouts: Vec<LayoutId>,
+    pub guards: Vec<Expr>,
+    pub loader: Option<Expr>,
+    pub static_routes: Vec<Expr>,
     fields: Vec<(Ident, Type)>,
 }
 
@@ -73,6 +119,7 @@ impl Route {
             .find(|attr| attr.path().is_ident("route"));
         let route;
         let ty;
+        let mut loader = None;
         let route_name = variant.ident.clone();
         match route_attr {
             Some(attr) => {
@@ -82,6 +129,7 @@ impl Route {
                     component: comp_name,
                 };
                 route = args.route.value();
+                loader = args.loader;
             }
             None => {
                 if let Some(route_attr) = variant
@@ -144,6 +192,20 @@ impl Route {
             _ => Vec::new(),
         };
 
+        let guards = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("guard"))
+            .map(|attr| attr.parse_args::<Expr>())
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let static_routes = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("static_routes"))
+            .map(|attr| attr.parse_args::<Expr>())
+            .collect::<syn::Result<Vec<_>>>()?;
+
         let (route_segments, query) = {
             parse_route_segments(
                 variant.ident.span(),
@@ -160,10 +222,49 @@ impl Route {
             query,
             nests,
             layouts,
+            guards,
+            loader,
+            static_routes,
             fields,
         })
     }
 
+    pub fn guards_match(&self, layouts: &[Layout]) -> TokenStream2 {
+        let name = &self.route_name;
+        let guards = self
+            .layouts
+            .iter()
+            .filter_map(|id| layouts[id.0].guard.as_ref())
+            .chain(&self.guards);
+
+        quote! {
+            Self::#name { .. } => vec![#(dioxus_router::guard::NavigationGuard::new(#guards),)*],
+        }
+    }
+
+    pub fn loaders_match(&self, layouts: &[Layout], nests: &[Nest]) -> TokenStream2 {
+        let name = &self.route_name;
+        let dynamic_segments = self.dynamic_segments();
+
+        // Every level of the route gets an entry. Layouts load with the dynamic segments of their
+        // nests, the route with all of its fields.
+        let layout_loaders = self.layouts.iter().map(|id| {
+            let layout = &layouts[id.0];
+            let params = layout
+                .active_nests
+                .iter()
+                .flat_map(|id| nests[id.0].dynamic_segments());
+            loader_call(layout.loader.as_ref(), params)
+        });
+        let route_loader = loader_call(self.loader.as_ref(), self.dynamic_segments());
+        let loaders = layout_loaders.chain(std::iter::once(route_loader));
+
+        quote! {
+            #[allow(unused)]
+            Self::#name { #(#dynamic_segments,)* .. } => vec![#(#loaders,)*],
+        }
+    }
+
     pub fn display_match(&self, nests: &[Nest]) -> TokenStream2 {
         let name = &self.route_name;
         let dynamic_segments = self.dynamic_segments();
@@ -282,7 +383,7 @@ impl Route {
                 }
             }
             if let Some(query) = &self.query {
-                if &query.ident == name {
+                if query.contains_ident(name) {
                     from_route = true
                 }
             }
@@ -344,3 +445,12 @@ pub(crate) enum RouteType {
     Child(Field),
     Leaf { component: Path },
 }
+
+fn loader_call(loader: Option<&Expr>, params: impl Iterator<Item = TokenStream2>) -> TokenStream2 {
+    match loader {
+        Some(loader) => {
+            quote! { Some(dioxus_router::loader::RouteLoader::new((#loader)(#(#params.clone(),)*))) }
+        }
+        None => quote! { None },
+    }
+}
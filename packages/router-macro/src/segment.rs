@@ -3,7 +3,7 @@ use syn::{Ident, Type};
 
 use proc_macro2::{Span, TokenStream as TokenStream2};
 
-use crate::query::QuerySegment;
+use crate::query::{QueryArgument, QuerySegment};
 
 #[derive(Debug, Clone)]
 pub enum RouteSegment {
@@ -130,10 +130,11 @@ pub fn static_segment_idx(idx: usize) -> Ident {
 
 pub fn parse_route_segments<'a>(
     route_span: Span,
-    mut fields: impl Iterator<Item = (&'a Ident, &'a Type)>,
+    fields: impl Iterator<Item = (&'a Ident, &'a Type)>,
     route: &str,
 ) -> syn::Result<(Vec<RouteSegment>, Option<QuerySegment>)> {
     let mut route_segments = Vec::new();
+    let fields: Vec<_> = fields.collect();
 
     let (route_string, query) = match route.rsplit_once('?') {
         Some((route, query)) => (route, Some(query)),
@@ -163,7 +164,7 @@ pub fn parse_route_segments<'a>(
                 segment.to_string()
             };
 
-            let field = fields.find(|(name, _)| **name == ident);
+            let field = fields.iter().copied().find(|(name, _)| **name == ident);
 
             let ty = if let Some(field) = field {
                 field.1.clone()
@@ -198,36 +199,56 @@ pub fn parse_route_segments<'a>(
         }
     }
 
-    // check if the route has a query string
+    // check if the route has a query string. A lone `?:query` receives the whole query string, named parsing is only
+    // used when there are multiple `&`-separated arguments
     let parsed_query = match query {
-        Some(query) => {
-            if let Some(query) = query.strip_prefix(':') {
-                let query_ident = Ident::new(query, Span::call_site());
-                let field = fields.find(|(name, _)| *name == &query_ident);
-
-                let ty = if let Some((_, ty)) = field {
-                    ty.clone()
-                } else {
-                    return Err(syn::Error::new(
+        Some(query) if query.contains('&') => {
+            // multiple query arguments are parsed into their own fields
+            let mut arguments = Vec::new();
+            for argument in query.split('&') {
+                let argument = argument.strip_prefix(':').ok_or_else(|| {
+                    syn::Error::new(
                         route_span,
-                        format!("Could not find a field with the name '{}'", query_ident),
-                    ));
-                };
-
-                Some(QuerySegment {
-                    ident: query_ident,
-                    ty,
-                })
-            } else {
-                None
+                        format!(
+                            "Query arguments must be dynamic (`:name`), found '{}' in the route '{}'",
+                            argument, route
+                        ),
+                    )
+                })?;
+                arguments.push(query_argument(route_span, &fields, argument)?);
             }
+            Some(QuerySegment::Segments(arguments))
         }
+        Some(query) => match query.strip_prefix(':') {
+            Some(query) => Some(QuerySegment::Single(query_argument(
+                route_span, &fields, query,
+            )?)),
+            None => None,
+        },
         None => None,
     };
 
     Ok((route_segments, parsed_query))
 }
 
+fn query_argument(
+    route_span: Span,
+    fields: &[(&Ident, &Type)],
+    name: &str,
+) -> syn::Result<QueryArgument> {
+    let ident = Ident::new(name, Span::call_site());
+    match fields.iter().find(|(name, _)| *name == &ident) {
+        Some((_, ty)) => Ok(QueryArgument {
+            ident,
+            ty: Type::clone(ty),
+        }),
+        None => Err(syn::Error::new(
+            route_span,
+            format!("Could not find a field with the name '{}'", ident),
+        )),
+    }
+}
+
 pub(crate) fn create_error_type(
     error_name: Ident,
     segments: &[RouteSegment],
@@ -30,7 +30,7 @@ async fn main() {
 
     // This function is available if you enable the ssr feature
     // on the dioxus_router crate.
-    pre_cache_static_routes::<Route, _>(
+    let report = export_static_site::<Route, _>(
         &mut renderer,
         &DefaultRenderer {
             before_body: r#"<!DOCTYPE html>
@@ -48,8 +48,20 @@ async fn main() {
                 .to_string(),
         },
     )
-    .await
-    .unwrap();
+    .await;
+
+    for (route, err) in &report.failed {
+        println!("Failed to render {route}: {err}");
+    }
+
+    // Describe the generated pages for search engines and hosts
+    Sitemap::new::<Route>("https://example.com")
+        .lastmod_from_report(&report)
+        .write_to("./static/sitemap.xml")
+        .unwrap();
+    RouteManifest::new::<Route>()
+        .write_to("./static/routes.json")
+        .unwrap();
 }
 
 #[component]
@@ -97,6 +109,7 @@ enum Route {
         #[route("/post/index")]
         PostHome {},
         #[route("/post/:id")]
+        #[static_routes(|| (0..5).map(|id| Route::Post { id }))]
         Post {
             id: usize,
         },
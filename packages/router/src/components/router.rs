@@ -117,7 +117,7 @@ where
 {
     use crate::prelude::{outlet::OutletContext, RouterContext};
 
-    use_context_provider(cx, || {
+    let router = use_context_provider(cx, || {
         RouterContext::new(
             (cx.props
                 .config
@@ -127,6 +127,7 @@ where
             cx.schedule_update_any(),
         )
     });
+    cx.use_hook(|| cx.spawn(router.navigation_task()));
     use_context_provider(cx, || OutletContext::<R> {
         current_level: 0,
         _marker: std::marker::PhantomData,
@@ -144,7 +145,7 @@ where
     <R as FromStr>::Err: std::fmt::Display,
     R: serde::Serialize + serde::de::DeserializeOwned,
 {
-    use_context_provider(cx, || {
+    let router = use_context_provider(cx, || {
         RouterContext::new(
             (cx.props
                 .config
@@ -154,6 +155,7 @@ where
             cx.schedule_update_any(),
         )
     });
+    cx.use_hook(|| cx.spawn(router.navigation_task()));
     use_context_provider(cx, || OutletContext::<R> {
         current_level: 0,
         _marker: std::marker::PhantomData,
@@ -16,6 +16,11 @@ impl<R> Clone for OutletContext<R> {
     }
 }
 
+/// The level of the layout or route the children of an outlet are rendered in. Unlike
+/// [`OutletContext`], it doesn't depend on the route type.
+#[derive(Clone, Copy)]
+pub(crate) struct RenderedLevel(pub usize);
+
 pub(crate) fn use_outlet_context<R: 'static>(cx: &ScopeState) -> &OutletContext<R> {
     let outlet_context = cx.use_hook(|| {
         cx.consume_context().unwrap_or(OutletContext::<R> {
@@ -42,6 +47,7 @@ impl<R> OutletContext<R> {
                 _marker: std::marker::PhantomData,
             }
         });
+        cx.provide_context(RenderedLevel(current_level));
 
         if let Some(error) = router.render_error(cx) {
             if current_level == 0 {
@@ -51,6 +57,19 @@ impl<R> OutletContext<R> {
             }
         }
 
-        router.current::<R>().render(cx, current_level)
+        // the outermost outlet starts the loaders of the route, and every outlet renders the route
+        // that finished loading
+        let route = match current_level {
+            0 => router.load_current_route(cx),
+            _ => router.rendered_route(),
+        };
+        match route {
+            Some(route) => route.downcast::<R>().unwrap().render(cx, current_level),
+            // the first route is still loading
+            None => {
+                cx.suspend();
+                None
+            }
+        }
     }
 }
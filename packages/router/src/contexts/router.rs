@@ -1,13 +1,24 @@
 use std::{
     any::Any,
+    cell::Cell,
     collections::HashSet,
+    future::Future,
+    pin::Pin,
     rc::Rc,
     sync::{Arc, RwLock},
+    task::Poll,
 };
 
 use dioxus::prelude::*;
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{future::poll_fn, FutureExt, StreamExt};
 
 use crate::{
+    guard::{
+        AnyNavigationGuard, GuardId, GuardResult, NavigationGuard, NavigationKind,
+        NavigationRequest,
+    },
+    loader::{create_loaders, join_loaders, RouteLoader},
     navigation::NavigationTarget,
     prelude::{AnyHistoryProvider, IntoRoutable},
     routable::Routable,
@@ -24,6 +35,12 @@ pub(crate) type RoutingCallback<R> =
 pub(crate) type AnyRoutingCallback =
     Arc<dyn Fn(RouterContext) -> Option<NavigationTarget<Rc<dyn Any>>>>;
 
+/// A navigation that waits for async guards
+type PendingNavigation = Pin<Box<dyn Future<Output = ()>>>;
+
+/// How often guards may redirect a single navigation before it is cancelled
+const MAX_REDIRECTS: usize = 16;
+
 struct MutableRouterState {
     /// The current prefix.
     prefix: Option<String>,
@@ -31,6 +48,33 @@ struct MutableRouterState {
     history: Box<dyn AnyHistoryProvider>,
 
     unresolved_error: Option<ExternalNavigationFailure>,
+
+    /// The route the router last navigated to. When the history changes outside of the router,
+    /// it is ahead of this route until the guards are checked.
+    last_route: Rc<dyn Any>,
+}
+
+#[derive(Default)]
+struct NavigationGuards {
+    next_id: usize,
+    guards: Vec<(GuardId, AnyNavigationGuard)>,
+}
+
+impl NavigationGuards {
+    fn insert(&mut self, guard: AnyNavigationGuard) -> GuardId {
+        let id = GuardId(self.next_id);
+        self.next_id += 1;
+        self.guards.push((id, guard));
+        id
+    }
+}
+
+#[derive(Default)]
+struct RouteLoaders {
+    /// The last route whose loaders finished, and their data for every level of the route
+    loaded: Option<(Rc<dyn Any>, Vec<Option<Rc<dyn Any>>>)>,
+    /// The route whose loaders are running, and the task running them
+    loading: Option<(String, TaskId)>,
 }
 
 /// A collection of router data that manages all routing functionality.
@@ -45,6 +89,19 @@ pub struct RouterContext {
     failure_external_navigation: fn(Scope) -> Element,
 
     any_route_to_string: fn(&dyn Any) -> String,
+
+    guards: Rc<RefCell<NavigationGuards>>,
+    route_guards: fn(&dyn Any) -> Vec<AnyNavigationGuard>,
+
+    loaders: Rc<RefCell<RouteLoaders>>,
+    route_loaders: fn(&dyn Any) -> Vec<Option<RouteLoader>>,
+
+    /// Increased every time a navigation starts, so pending navigations know if they are outdated
+    navigation: Rc<Cell<usize>>,
+    pending_navigations: UnboundedSender<PendingNavigation>,
+    #[allow(clippy::type_complexity)]
+    navigation_receivers:
+        Rc<RefCell<Option<(UnboundedReceiver<()>, UnboundedReceiver<PendingNavigation>)>>>,
 }
 
 impl RouterContext {
@@ -56,19 +113,27 @@ impl RouterContext {
         R: Clone,
         <R as std::str::FromStr>::Err: std::fmt::Display,
     {
+        let history = cfg.take_history();
+        let last_route = history.current_route();
         let state = Rc::new(RefCell::new(MutableRouterState {
             prefix: Default::default(),
-            history: cfg.take_history(),
+            history,
             unresolved_error: None,
+            last_route,
         }));
 
-        let subscriber_update = mark_dirty.clone();
-        let subscribers = Arc::new(RwLock::new(HashSet::new()));
+        let mut guards = NavigationGuards::default();
+        for guard in std::mem::take(&mut cfg.guards) {
+            guards.insert(guard.into_any());
+        }
+
+        let (history_changes_tx, history_changes) = unbounded();
+        let (pending_navigations, pending_navigations_rx) = unbounded();
 
         let myself = Self {
             state,
-            subscribers: subscribers.clone(),
-            subscriber_update,
+            subscribers: Arc::new(RwLock::new(HashSet::new())),
+            subscriber_update: mark_dirty,
 
             routing_callback: cfg.on_update.map(|update| {
                 Arc::new(move |ctx| {
@@ -101,15 +166,43 @@ impl RouterContext {
                     })
                     .to_string()
             },
+
+            guards: Rc::new(RefCell::new(guards)),
+            route_guards: |route| {
+                route
+                    .downcast_ref::<R>()
+                    .map(|route| {
+                        route
+                            .guards()
+                            .into_iter()
+                            .map(NavigationGuard::into_any)
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            },
+
+            loaders: Default::default(),
+            route_loaders: |route| {
+                route
+                    .downcast_ref::<R>()
+                    .map(Routable::loaders)
+                    .unwrap_or_default()
+            },
+
+            navigation: Default::default(),
+            pending_navigations,
+            navigation_receivers: Rc::new(RefCell::new(Some((
+                history_changes,
+                pending_navigations_rx,
+            )))),
         };
 
         // set the updater
         {
             let mut state = myself.state.borrow_mut();
             state.history.updater(Arc::new(move || {
-                for &id in subscribers.read().unwrap().iter() {
-                    (mark_dirty)(id);
-                }
+                // the guards are checked in the navigation task before the subscribers are updated
+                let _ = history_changes_tx.unbounded_send(());
             }));
         }
 
@@ -137,37 +230,21 @@ impl RouterContext {
     ///
     /// Will fail silently if there is no previous location to go to.
     pub fn go_back(&self) {
-        {
-            self.state.borrow_mut().history.go_back();
-        }
-
-        self.change_route();
+        self.traverse(NavigationKind::Back);
     }
 
     /// Go back to the next location.
     ///
     /// Will fail silently if there is no next location to go to.
     pub fn go_forward(&self) {
-        {
-            self.state.borrow_mut().history.go_forward();
-        }
-
-        self.change_route();
+        self.traverse(NavigationKind::Forward);
     }
 
     pub(crate) fn push_any(
         &self,
         target: NavigationTarget<Rc<dyn Any>>,
     ) -> Option<ExternalNavigationFailure> {
-        match target {
-            NavigationTarget::Internal(p) => {
-                let mut state = self.state_mut();
-                state.history.push(p)
-            }
-            NavigationTarget::External(e) => return self.external(e),
-        }
-
-        self.change_route()
+        self.navigate(NavigationKind::Push, target)
     }
 
     /// Push a new location.
@@ -175,15 +252,7 @@ impl RouterContext {
     /// The previous location will be available to go back to.
     pub fn push(&self, target: impl Into<IntoRoutable>) -> Option<ExternalNavigationFailure> {
         let target = self.resolve_into_routable(target.into());
-        match target {
-            NavigationTarget::Internal(p) => {
-                let mut state = self.state_mut();
-                state.history.push(p)
-            }
-            NavigationTarget::External(e) => return self.external(e),
-        }
-
-        self.change_route()
+        self.navigate(NavigationKind::Push, target)
     }
 
     /// Replace the current location.
@@ -191,16 +260,40 @@ impl RouterContext {
     /// The previous location will **not** be available to go back to.
     pub fn replace(&self, target: impl Into<IntoRoutable>) -> Option<ExternalNavigationFailure> {
         let target = self.resolve_into_routable(target.into());
+        self.navigate(NavigationKind::Replace, target)
+    }
 
-        {
-            let mut state = self.state_mut();
-            match target {
-                NavigationTarget::Internal(p) => state.history.replace(p),
-                NavigationTarget::External(e) => return self.external(e),
-            }
-        }
+    /// Add a guard that runs before every navigation. See [`NavigationGuard`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `R` is not the route type of the router.
+    pub fn add_guard<R, F, Fut>(&self, guard: F) -> GuardId
+    where
+        R: Routable,
+        F: Fn(NavigationRequest<R>) -> Fut + 'static,
+        Fut: Future<Output = GuardResult<R>> + 'static,
+    {
+        assert!(
+            self.state
+                .borrow()
+                .history
+                .accepts_type_id(&std::any::TypeId::of::<R>()),
+            "Guard is not for the route type of the router: {}",
+            std::any::type_name::<R>()
+        );
 
-        self.change_route()
+        self.guards
+            .borrow_mut()
+            .insert(NavigationGuard::new(guard).into_any())
+    }
+
+    /// Remove a guard added with [`Self::add_guard`].
+    pub fn remove_guard(&self, id: GuardId) {
+        self.guards
+            .borrow_mut()
+            .guards
+            .retain(|(guard_id, _)| *guard_id != id);
     }
 
     /// The route that is currently active.
@@ -220,6 +313,74 @@ impl RouterContext {
         self.any_route_to_string(&*self.state.borrow().history.current_route())
     }
 
+    /// Start the loaders of the current route if they are not running yet, and return the route
+    /// that should be rendered.
+    ///
+    /// This is the current route once its loaders are finished. While they are running, it is the
+    /// last route that finished loading, or [`None`] if no route finished loading yet.
+    pub(crate) fn load_current_route(&self, cx: &ScopeState) -> Option<Rc<dyn Any>> {
+        let current = self.state.borrow().history.current_route();
+        let key = self.any_route_to_string(&*current);
+
+        let mut loaders = self.loaders.borrow_mut();
+        if let Some((loaded, _)) = &loaders.loaded {
+            if self.any_route_to_string(&**loaded) == key {
+                // the router navigated back before the loaders of another route finished
+                if let Some((_, task)) = loaders.loading.take() {
+                    cx.remove_future(task);
+                }
+                return Some(current);
+            }
+        }
+
+        if !matches!(&loaders.loading, Some((loading, _)) if *loading == key) {
+            // the route changed while the loaders of another route were running
+            if let Some((_, task)) = loaders.loading.take() {
+                cx.remove_future(task);
+            }
+
+            let initial = loaders.loaded.is_none();
+            let route_loaders = create_loaders(initial, || (self.route_loaders)(&*current));
+            let mut data = Box::pin(join_loaders(route_loaders));
+
+            match data.as_mut().now_or_never() {
+                Some(data) => {
+                    loaders.loaded = Some((current.clone(), data));
+                    return Some(current);
+                }
+                None => {
+                    let myself = self.clone();
+                    let task = cx.push_future(async move {
+                        let data = data.await;
+                        {
+                            let mut loaders = myself.loaders.borrow_mut();
+                            loaders.loading = None;
+                            loaders.loaded = Some((current, data));
+                        }
+                        myself.update_subscribers();
+                    });
+                    loaders.loading = Some((key, task));
+                }
+            }
+        }
+
+        loaders.loaded.as_ref().map(|(route, _)| route.clone())
+    }
+
+    /// The route that is rendered. This is the route that was returned from
+    /// [`Self::load_current_route`] last.
+    pub(crate) fn rendered_route(&self) -> Option<Rc<dyn Any>> {
+        let loaders = self.loaders.borrow();
+        loaders.loaded.as_ref().map(|(route, _)| route.clone())
+    }
+
+    /// The data the loader of the layout or route at `level` of the rendered route loaded
+    pub(crate) fn loader_data<T: 'static>(&self, level: usize) -> Option<Rc<T>> {
+        let loaders = self.loaders.borrow();
+        let (_, data) = loaders.loaded.as_ref()?;
+        data.get(level)?.clone()?.downcast::<T>().ok()
+    }
+
     pub(crate) fn any_route_to_string(&self, route: &dyn Any) -> String {
         (self.any_route_to_string)(route)
     }
@@ -295,18 +456,234 @@ impl RouterContext {
             .and_then(|_| (self.failure_external_navigation)(cx))
     }
 
+    /// Drives the navigations that wait for async guards, and checks the guards when the history
+    /// changes outside of the router. The [`Router`](crate::prelude::Router) component spawns this
+    /// once.
+    pub(crate) fn navigation_task(&self) -> impl Future<Output = ()> {
+        let myself = self.clone();
+        let (mut history_changes, mut pending_navigations) = self
+            .navigation_receivers
+            .borrow_mut()
+            .take()
+            .expect("the navigation task was already started");
+        let mut pending: Option<PendingNavigation> = None;
+
+        poll_fn(move |cx| {
+            while let Poll::Ready(Some(())) = history_changes.poll_next_unpin(cx) {
+                myself.history_changed();
+            }
+            // only the latest navigation can finish, so older ones are dropped
+            while let Poll::Ready(Some(navigation)) = pending_navigations.poll_next_unpin(cx) {
+                pending = Some(navigation);
+            }
+            if let Some(navigation) = &mut pending {
+                if navigation.as_mut().poll(cx).is_ready() {
+                    pending = None;
+                }
+            }
+            Poll::Pending
+        })
+    }
+
+    fn history_changed(&self) {
+        let (from, to) = {
+            let state = self.state.borrow();
+            (state.last_route.clone(), state.history.current_route())
+        };
+
+        if self.any_route_to_string(&*from) == self.any_route_to_string(&*to) {
+            self.update_subscribers();
+            return;
+        }
+
+        self.navigate(NavigationKind::Traverse, NavigationTarget::Internal(to));
+    }
+
+    fn traverse(&self, kind: NavigationKind) {
+        let from = self.current_route_string();
+        self.move_history(kind);
+        let to = self.state.borrow().history.current_route();
+
+        // histories that move asynchronously (like the web history) report the change through
+        // their updater, so the guards are checked once that happens
+        if self.any_route_to_string(&*to) == from {
+            self.change_route();
+            return;
+        }
+
+        // undo the move, so nothing changes until the guards allow it
+        self.move_history(match kind {
+            NavigationKind::Back => NavigationKind::Forward,
+            _ => NavigationKind::Back,
+        });
+        self.navigate(kind, NavigationTarget::Internal(to));
+    }
+
+    fn move_history(&self, kind: NavigationKind) {
+        let mut state = self.state_mut();
+        match kind {
+            NavigationKind::Back => state.history.go_back(),
+            NavigationKind::Forward => state.history.go_forward(),
+            _ => {}
+        }
+    }
+
+    fn guards_for(&self, target: &NavigationTarget<Rc<dyn Any>>) -> Vec<AnyNavigationGuard> {
+        let mut guards: Vec<_> = self
+            .guards
+            .borrow()
+            .guards
+            .iter()
+            .map(|(_, guard)| guard.clone())
+            .collect();
+        if let NavigationTarget::Internal(route) = target {
+            guards.extend((self.route_guards)(&**route));
+        }
+        guards
+    }
+
+    fn navigate(
+        &self,
+        kind: NavigationKind,
+        target: NavigationTarget<Rc<dyn Any>>,
+    ) -> Option<ExternalNavigationFailure> {
+        let id = self.navigation.get() + 1;
+        self.navigation.set(id);
+
+        let guards = self.guards_for(&target);
+        if guards.is_empty() {
+            return self.commit(kind, target, false);
+        }
+
+        let from = self.state.borrow().last_route.clone();
+        let mut resolve = Box::pin(self.clone().resolve(kind, from, target, guards));
+
+        match resolve.as_mut().now_or_never() {
+            Some(resolved) => self.finish(id, kind, resolved),
+            None => {
+                let myself = self.clone();
+                let _ = self
+                    .pending_navigations
+                    .unbounded_send(Box::pin(async move {
+                        let resolved = resolve.await;
+                        myself.finish(id, kind, resolved);
+                    }));
+                None
+            }
+        }
+    }
+
+    /// Run the guards for a navigation and follow their redirects. Returns the final target and
+    /// if it was redirected, or [`None`] if the navigation was cancelled.
+    async fn resolve(
+        self,
+        kind: NavigationKind,
+        from: Rc<dyn Any>,
+        mut target: NavigationTarget<Rc<dyn Any>>,
+        mut guards: Vec<AnyNavigationGuard>,
+    ) -> Option<(NavigationTarget<Rc<dyn Any>>, bool)> {
+        for redirects in 0..MAX_REDIRECTS {
+            let mut result = GuardResult::Allow;
+            for guard in &guards {
+                result = guard(NavigationRequest {
+                    from: from.clone(),
+                    to: target.clone(),
+                    kind,
+                })
+                .await;
+                if !matches!(result, GuardResult::Allow) {
+                    break;
+                }
+            }
+
+            match result {
+                GuardResult::Allow => return Some((target, redirects > 0)),
+                GuardResult::Cancel => return None,
+                GuardResult::Redirect(new_target) => {
+                    guards = self.guards_for(&new_target);
+                    target = new_target;
+                }
+            }
+        }
+
+        tracing::error!(
+            "Navigation guards redirected more than {} times, the navigation was cancelled",
+            MAX_REDIRECTS
+        );
+        None
+    }
+
+    fn finish(
+        &self,
+        id: usize,
+        kind: NavigationKind,
+        resolved: Option<(NavigationTarget<Rc<dyn Any>>, bool)>,
+    ) -> Option<ExternalNavigationFailure> {
+        // another navigation started while the guards were running
+        if self.navigation.get() != id {
+            return None;
+        }
+
+        match resolved {
+            Some((target, redirected)) => self.commit(kind, target, redirected),
+            None => {
+                if kind == NavigationKind::Traverse {
+                    // the history already moved, so bring back the route that is still rendered
+                    let mut state = self.state_mut();
+                    let last_route = state.last_route.clone();
+                    state.history.push(last_route);
+                }
+                None
+            }
+        }
+    }
+
+    fn commit(
+        &self,
+        kind: NavigationKind,
+        target: NavigationTarget<Rc<dyn Any>>,
+        redirected: bool,
+    ) -> Option<ExternalNavigationFailure> {
+        let route = match target {
+            NavigationTarget::Internal(route) => route,
+            NavigationTarget::External(e) => return self.external(e),
+        };
+
+        {
+            let mut state = self.state_mut();
+            match (kind, redirected) {
+                (NavigationKind::Push, _)
+                | (NavigationKind::Back, true)
+                | (NavigationKind::Forward, true) => state.history.push(route),
+                (NavigationKind::Replace, _) | (NavigationKind::Traverse, true) => {
+                    state.history.replace(route)
+                }
+                (NavigationKind::Back, false) => state.history.go_back(),
+                (NavigationKind::Forward, false) => state.history.go_forward(),
+                // the history already moved
+                (NavigationKind::Traverse, false) => {}
+            }
+        }
+
+        self.change_route()
+    }
+
     fn change_route(&self) -> Option<ExternalNavigationFailure> {
         if let Some(callback) = &self.routing_callback {
             let myself = self.clone();
             if let Some(new) = callback(myself) {
-                let mut state = self.state_mut();
                 match new {
-                    NavigationTarget::Internal(p) => state.history.replace(p),
+                    NavigationTarget::Internal(p) => self.state_mut().history.replace(p),
                     NavigationTarget::External(e) => return self.external(e),
                 }
             }
         }
 
+        {
+            let mut state = self.state_mut();
+            state.last_route = state.history.current_route();
+        }
+
         self.update_subscribers();
 
         None
@@ -368,6 +745,20 @@ where
         self.inner.replace(target.into())
     }
 
+    /// Add a guard that runs before every navigation. See [`NavigationGuard`] for more details.
+    pub fn add_guard<F, Fut>(&self, guard: F) -> GuardId
+    where
+        F: Fn(NavigationRequest<R>) -> Fut + 'static,
+        Fut: Future<Output = GuardResult<R>> + 'static,
+    {
+        self.inner.add_guard(guard)
+    }
+
+    /// Remove a guard added with [`Self::add_guard`].
+    pub fn remove_guard(&self, id: GuardId) {
+        self.inner.remove_guard(id)
+    }
+
     /// The route that is currently active.
     pub fn current(&self) -> R
     where
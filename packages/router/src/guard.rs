@@ -0,0 +1,155 @@
+//! Navigation guards that can cancel or redirect a navigation before it happens.
+
+use std::{any::Any, future::Future, pin::Pin, rc::Rc};
+
+use crate::{navigation::NavigationTarget, routable::Routable};
+
+/// How a navigation was started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationKind {
+    /// A new location is pushed with [`Navigator::push`](crate::prelude::Navigator::push) or a
+    /// [`Link`](crate::prelude::Link).
+    Push,
+    /// The current location is replaced with [`Navigator::replace`](crate::prelude::Navigator::replace).
+    Replace,
+    /// The router goes back with [`Navigator::go_back`](crate::prelude::Navigator::go_back).
+    Back,
+    /// The router goes forward with [`Navigator::go_forward`](crate::prelude::Navigator::go_forward).
+    Forward,
+    /// The history changed outside of the router, for example with the back and forward buttons of
+    /// a browser. The direction is not known.
+    Traverse,
+}
+
+/// A navigation that is about to happen. Navigation guards receive this to decide if the
+/// navigation may continue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigationRequest<R> {
+    /// The route the router is navigating away from.
+    pub from: R,
+    /// The target the router is navigating to.
+    pub to: NavigationTarget<R>,
+    /// How the navigation was started.
+    pub kind: NavigationKind,
+}
+
+/// The decision of a navigation guard.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuardResult<R> {
+    /// Let the navigation continue. The next guard will be checked.
+    Allow,
+    /// Cancel the navigation. The router stays on the current route.
+    Cancel,
+    /// Navigate to another target instead. The guards are checked again for the new target.
+    Redirect(NavigationTarget<R>),
+}
+
+type GuardFuture<R> = Pin<Box<dyn Future<Output = GuardResult<R>>>>;
+
+/// A check that runs before the router navigates.
+///
+/// Guards are async, so they can wait for a server (auth) or the user (a "you have unsaved
+/// changes" dialog). If every guard returns right away, the navigation happens immediately.
+/// Otherwise the router stays on the current route until the guards are finished. If another
+/// navigation starts in the meantime, the pending one is dropped.
+///
+/// Guards can be added:
+/// - For every navigation with [`RouterConfig::guard`](crate::prelude::RouterConfig::guard),
+///   [`RouterContext::add_guard`](crate::prelude::RouterContext::add_guard) or
+///   [`use_navigation_guard`](crate::prelude::use_navigation_guard)
+/// - For navigations to a route with `#[guard(..)]` in `#[derive(Routable)]`
+/// - For navigations to any route inside a layout with `#[layout(Component, guard = ..)]`
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// # #[component]
+/// # fn Index(cx: Scope) -> Element { todo!() }
+/// # #[component]
+/// # fn Admin(cx: Scope) -> Element { todo!() }
+/// # fn is_logged_in() -> bool { false }
+/// #[derive(Clone, Routable, Debug, PartialEq)]
+/// enum Route {
+///     #[route("/")]
+///     Index {},
+///     #[route("/admin")]
+///     #[guard(require_login)]
+///     Admin {},
+/// }
+///
+/// async fn require_login(_: NavigationRequest<Route>) -> GuardResult<Route> {
+///     match is_logged_in() {
+///         true => GuardResult::Allow,
+///         false => GuardResult::Redirect(Route::Index {}.into()),
+///     }
+/// }
+/// ```
+pub struct NavigationGuard<R>(Rc<dyn Fn(NavigationRequest<R>) -> GuardFuture<R>>);
+
+impl<R> Clone for NavigationGuard<R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<R: 'static> NavigationGuard<R> {
+    /// Create a guard from an async function
+    pub fn new<F, Fut>(guard: F) -> Self
+    where
+        F: Fn(NavigationRequest<R>) -> Fut + 'static,
+        Fut: Future<Output = GuardResult<R>> + 'static,
+    {
+        Self(Rc::new(move |request| Box::pin(guard(request))))
+    }
+
+    /// Check a navigation
+    pub fn check(&self, request: NavigationRequest<R>) -> impl Future<Output = GuardResult<R>> {
+        (self.0)(request)
+    }
+}
+
+impl<R: Routable> NavigationGuard<R> {
+    /// Erase the route type so the guard can be stored in the [`RouterContext`](crate::prelude::RouterContext)
+    pub(crate) fn into_any(self) -> AnyNavigationGuard {
+        Rc::new(move |request: NavigationRequest<Rc<dyn Any>>| {
+            let request = NavigationRequest {
+                from: downcast_route::<R>(request.from),
+                to: downcast_target(request.to),
+                kind: request.kind,
+            };
+            let check = self.check(request);
+            Box::pin(async move {
+                match check.await {
+                    GuardResult::Allow => GuardResult::Allow,
+                    GuardResult::Cancel => GuardResult::Cancel,
+                    GuardResult::Redirect(target) => GuardResult::Redirect(upcast_target(target)),
+                }
+            })
+        })
+    }
+}
+
+pub(crate) type AnyNavigationGuard =
+    Rc<dyn Fn(NavigationRequest<Rc<dyn Any>>) -> GuardFuture<Rc<dyn Any>>>;
+
+/// An id for a guard added with [`RouterContext::add_guard`](crate::prelude::RouterContext::add_guard)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GuardId(pub(crate) usize);
+
+fn downcast_route<R: Routable>(route: Rc<dyn Any>) -> R {
+    route.downcast::<R>().unwrap().as_ref().clone()
+}
+
+fn downcast_target<R: Routable>(target: NavigationTarget<Rc<dyn Any>>) -> NavigationTarget<R> {
+    match target {
+        NavigationTarget::Internal(route) => NavigationTarget::Internal(downcast_route(route)),
+        NavigationTarget::External(url) => NavigationTarget::External(url),
+    }
+}
+
+fn upcast_target<R: Routable>(target: NavigationTarget<R>) -> NavigationTarget<Rc<dyn Any>> {
+    match target {
+        NavigationTarget::Internal(route) => NavigationTarget::Internal(Rc::new(route)),
+        NavigationTarget::External(url) => NavigationTarget::External(url),
+    }
+}
@@ -55,6 +55,12 @@ struct WebHistoryState<R> {
 ///
 /// Application developers are responsible for not rendering the router if the prefix is not present
 /// in the URL. Otherwise, if a router navigation is triggered, the prefix will be added.
+///
+/// # Navigation guards
+/// The back and forward buttons of the browser change the URL before the router is notified. The
+/// router checks its [`NavigationGuard`](crate::prelude::NavigationGuard)s afterwards, and if they
+/// cancel the navigation, the previous route is pushed again. The entries that were ahead of the
+/// previous route are lost in that case.
 pub struct WebHistory<R: Routable> {
     do_scroll_restoration: bool,
     history: History,
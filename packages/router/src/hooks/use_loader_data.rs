@@ -0,0 +1,60 @@
+use std::rc::Rc;
+
+use dioxus::prelude::ScopeState;
+
+use crate::{contexts::outlet::RenderedLevel, utils::use_router_internal::use_router_internal};
+
+/// A hook that provides the data a [`RouteLoader`](crate::prelude::RouteLoader) loaded for the
+/// layout or route the calling component is rendered in.
+///
+/// A layout and the routes inside of it each get the data of their own loader, even if the
+/// loaders return the same type.
+///
+/// # Return values
+/// - [`None`], when the layout or route has no loader, or its loader didn't load a `T`.
+/// - Otherwise the loaded data.
+///
+/// # Panic
+/// - When the calling component is not nested within a [`Router`](crate::prelude::Router)
+///   component.
+///
+/// # Example
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/", Index, loader = load_greeting)]
+///     Index {},
+/// }
+///
+/// async fn load_greeting() -> String {
+///     "Hello from the loader".to_string()
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     render! {
+///         Router::<Route> {}
+///     }
+/// }
+///
+/// #[component]
+/// fn Index(cx: Scope) -> Element {
+///     let greeting = use_loader_data::<String>(cx)?;
+///     render! {
+///         h1 { "{greeting}" }
+///     }
+/// }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # let _ = vdom.rebuild();
+/// # assert_eq!(dioxus_ssr::render(&vdom), "<h1>Hello from the loader</h1>")
+/// ```
+pub fn use_loader_data<T: 'static>(cx: &ScopeState) -> Option<Rc<T>> {
+    let router = use_router_internal(cx)
+        .as_ref()
+        .expect("`use_loader_data` must be called in a descendant of a Router component");
+    let level = (*cx.use_hook(|| cx.consume_context::<RenderedLevel>()))?;
+    router.loader_data(level.0)
+}
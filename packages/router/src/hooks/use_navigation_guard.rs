@@ -0,0 +1,101 @@
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+use dioxus::prelude::ScopeState;
+
+use crate::prelude::{GuardId, GuardResult, NavigationRequest, Routable, RouterContext};
+
+/// A hook that adds a navigation guard while the component is mounted. See
+/// [`NavigationGuard`](crate::prelude::NavigationGuard) for more details.
+///
+/// The guard is updated every render, so it can use the latest state of the component. This makes
+/// it a good fit for a "you have unsaved changes" check.
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Editor {},
+///     #[route("/other")]
+///     Other {},
+/// }
+///
+/// #[component]
+/// fn App(cx: Scope) -> Element {
+///     render! {
+///         Router::<Route> {}
+///     }
+/// }
+///
+/// #[component]
+/// fn Editor(cx: Scope) -> Element {
+///     let unsaved = use_state(cx, || false);
+///     let has_unsaved = **unsaved;
+///
+///     use_navigation_guard(cx, move |_: NavigationRequest<Route>| async move {
+///         match has_unsaved {
+///             true => GuardResult::Cancel,
+///             false => GuardResult::Allow,
+///         }
+///     });
+///
+///     render! {
+///         textarea {
+///             oninput: move |_| unsaved.set(true),
+///         }
+///     }
+/// }
+/// #
+/// # #[component]
+/// # fn Other(cx: Scope) -> Element { todo!() }
+/// #
+/// # let mut vdom = VirtualDom::new(App);
+/// # let _ = vdom.rebuild();
+/// ```
+///
+/// # Panics
+/// This hook panics if it is not called in a descendant of a [`Router`](crate::prelude::Router)
+/// component.
+pub fn use_navigation_guard<R, F, Fut>(cx: &ScopeState, guard: F)
+where
+    R: Routable,
+    F: Fn(NavigationRequest<R>) -> Fut + 'static,
+    Fut: Future<Output = GuardResult<R>> + 'static,
+{
+    let registration = cx.use_hook(|| {
+        let router = cx
+            .consume_context::<RouterContext>()
+            .expect("Must be called in a descendant of a Router component");
+
+        let latest: Rc<RefCell<Option<F>>> = Default::default();
+        let id = router.add_guard({
+            let latest = latest.clone();
+            move |request: NavigationRequest<R>| {
+                let check = latest.borrow().as_ref().map(|guard| guard(request));
+                async move {
+                    match check {
+                        Some(check) => check.await,
+                        None => GuardResult::Allow,
+                    }
+                }
+            }
+        });
+
+        GuardRegistration { router, id, latest }
+    });
+
+    *registration.latest.borrow_mut() = Some(guard);
+}
+
+struct GuardRegistration<F> {
+    router: RouterContext,
+    id: GuardId,
+    latest: Rc<RefCell<Option<F>>>,
+}
+
+impl<F> Drop for GuardRegistration<F> {
+    fn drop(&mut self) {
+        self.router.remove_guard(self.id);
+    }
+}
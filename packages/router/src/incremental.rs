@@ -10,7 +10,23 @@ use dioxus_ssr::incremental::{
 
 use crate::prelude::*;
 
+/// The routes that were rendered by [`export_static_site`].
+#[derive(Debug, Default)]
+pub struct StaticGenerationReport {
+    /// The routes that were rendered, and how fresh the cached pages are.
+    pub rendered: Vec<(String, RenderFreshness)>,
+    /// The routes that failed to render, and why.
+    pub failed: Vec<(String, IncrementalRendererError)>,
+}
+
 /// Pre-cache all static routes.
+///
+/// Routes with dynamic segments are rendered for every route listed with
+/// `#[static_routes(function)]` in `#[derive(Routable)]`. Routes that are already cached are not
+/// rendered again.
+///
+/// Every route is rendered even if an earlier route fails. The first error is returned once all
+/// routes have been rendered.
 pub async fn pre_cache_static_routes<Rt, R: WrapBody + Send + Sync>(
     renderer: &mut IncrementalRenderer,
     wrapper: &R,
@@ -19,54 +35,74 @@ where
     Rt: Routable,
     <Rt as FromStr>::Err: std::fmt::Display,
 {
-    for route in Rt::SITE_MAP
-        .iter()
-        .flat_map(|seg| seg.flatten().into_iter())
-    {
-        // check if this is a static segment
-        let mut is_static = true;
-        let mut full_path = String::new();
-        for segment in &route {
-            match segment {
-                SegmentType::Child => {}
-                SegmentType::Static(s) => {
-                    full_path += "/";
-                    full_path += s;
-                }
-                _ => {
-                    // skip routes with any dynamic segments
-                    is_static = false;
-                    break;
-                }
-            }
+    let report = generate_static_routes::<Rt, R>(renderer, wrapper, false).await;
+    match report.failed.into_iter().next() {
+        Some((_, err)) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Render every static route to the static directory of the renderer, including the routes
+/// listed with `#[static_routes(function)]` in `#[derive(Routable)]`.
+///
+/// Unlike [`pre_cache_static_routes`], routes that are already cached are rendered again and the
+/// outcome of every route is returned in a [`StaticGenerationReport`].
+pub async fn export_static_site<Rt, R: WrapBody + Send + Sync>(
+    renderer: &mut IncrementalRenderer,
+    wrapper: &R,
+) -> StaticGenerationReport
+where
+    Rt: Routable,
+    <Rt as FromStr>::Err: std::fmt::Display,
+{
+    generate_static_routes::<Rt, R>(renderer, wrapper, true).await
+}
+
+async fn generate_static_routes<Rt, R: WrapBody + Send + Sync>(
+    renderer: &mut IncrementalRenderer,
+    wrapper: &R,
+    invalidate: bool,
+) -> StaticGenerationReport
+where
+    Rt: Routable,
+    <Rt as FromStr>::Err: std::fmt::Display,
+{
+    let mut report = StaticGenerationReport::default();
+
+    let mut routes = Rt::static_routes();
+    routes.extend(Rt::enumerated_routes());
+
+    for route in routes {
+        let path = route.to_string();
+        if invalidate {
+            renderer.invalidate(&path);
         }
 
-        if is_static {
-            match Rt::from_str(&full_path) {
-                Ok(route) => {
-                    render_route(
-                        renderer,
-                        route,
-                        &mut tokio::io::sink(),
-                        |vdom| {
-                            Box::pin(async move {
-                                let _ = vdom.rebuild();
-                                vdom.wait_for_suspense().await;
-                            })
-                        },
-                        wrapper,
-                    )
-                    .await?;
-                }
-                Err(e) => {
-                    tracing::info!("@ route: {}", full_path);
-                    tracing::error!("Error pre-caching static route: {}", e);
-                }
+        let rendered = render_route(
+            renderer,
+            route,
+            &mut tokio::io::sink(),
+            |vdom| {
+                Box::pin(async move {
+                    let _ = vdom.rebuild();
+                    vdom.wait_for_suspense().await;
+                })
+            },
+            wrapper,
+        )
+        .await;
+
+        match rendered {
+            Ok(freshness) => report.rendered.push((path, freshness)),
+            Err(e) => {
+                tracing::info!("@ route: {}", path);
+                tracing::error!("Error pre-caching static route: {}", e);
+                report.failed.push((path, e));
             }
         }
     }
 
-    Ok(())
+    report
 }
 
 /// Render a route to a writer.
@@ -5,8 +5,11 @@
 #![deny(missing_docs)]
 #![allow(non_snake_case)]
 
+pub mod guard;
+pub mod loader;
 pub mod navigation;
 pub mod routable;
+pub mod sitemap;
 
 #[cfg(feature = "ssr")]
 pub mod incremental;
@@ -51,17 +54,26 @@ pub mod hooks {
 
     mod use_navigator;
     pub use use_navigator::*;
+
+    mod use_navigation_guard;
+    pub use use_navigation_guard::*;
+
+    mod use_loader_data;
+    pub use use_loader_data::*;
 }
 
 /// A collection of useful items most applications might need.
 pub mod prelude {
     pub use crate::components::*;
     pub use crate::contexts::*;
+    pub use crate::guard::*;
     pub use crate::history::*;
     pub use crate::hooks::*;
+    pub use crate::loader::*;
     pub use crate::navigation::*;
     pub use crate::routable::*;
     pub use crate::router_cfg::RouterConfig;
+    pub use crate::sitemap::{RouteManifest, Sitemap};
     pub use dioxus_router_macro::Routable;
 
     #[cfg(feature = "ssr")]
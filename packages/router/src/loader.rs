@@ -0,0 +1,83 @@
+//! Data loaders that run before a route is rendered.
+
+use std::{any::Any, cell::Cell, future::Future, pin::Pin, rc::Rc};
+
+use futures_util::future::{join_all, OptionFuture};
+
+/// The data of a route or layout that is loaded before the route is rendered.
+///
+/// Loaders are declared with `#[route("/path", Component, loader = function)]` or
+/// `#[layout(Component, loader = function)]` in `#[derive(Routable)]`. The function receives the
+/// same parameters as the component (in the order of the fields) and returns a future.
+///
+/// When the router navigates, the loaders of the route and all its layouts run in parallel. The
+/// previous route stays on the screen until all of them are finished. Components read the data
+/// of the route or layout they are rendered in with
+/// [`use_loader_data`](crate::prelude::use_loader_data).
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/user/:id", User, loader = load_user)]
+///     User { id: usize },
+/// }
+///
+/// #[derive(Debug)]
+/// struct UserData {
+///     name: String,
+/// }
+///
+/// async fn load_user(id: usize) -> UserData {
+///     UserData {
+///         name: format!("User {id}"),
+///     }
+/// }
+///
+/// #[component]
+/// fn User(cx: Scope, id: usize) -> Element {
+///     let user = use_loader_data::<UserData>(cx)?;
+///
+///     render! {
+///         h1 { "{user.name}" }
+///     }
+/// }
+/// ```
+pub struct RouteLoader(Pin<Box<dyn Future<Output = Rc<dyn Any>>>>);
+
+impl RouteLoader {
+    /// Create a loader from the future that loads its data
+    pub fn new<T: 'static>(data: impl Future<Output = T> + 'static) -> Self {
+        Self(Box::pin(async move { Rc::new(data.await) as Rc<dyn Any> }))
+    }
+}
+
+thread_local! {
+    static INITIAL_LOAD: Cell<bool> = Cell::new(false);
+}
+
+/// Check if the loaders that are being created are for the first route the router renders.
+///
+/// Loaders can use this to reuse data that was sent with the HTML of the page while it hydrates.
+pub fn is_initial_load() -> bool {
+    INITIAL_LOAD.with(Cell::get)
+}
+
+/// Create the loaders of a route, marking them as the initial load if needed
+pub(crate) fn create_loaders<O>(initial: bool, create: impl FnOnce() -> O) -> O {
+    let previous = INITIAL_LOAD.with(|initial_load| initial_load.replace(initial));
+    let loaders = create();
+    INITIAL_LOAD.with(|initial_load| initial_load.set(previous));
+    loaders
+}
+
+/// Run all loaders in parallel and collect their data in the order of the loaders
+pub(crate) async fn join_loaders(loaders: Vec<Option<RouteLoader>>) -> Vec<Option<Rc<dyn Any>>> {
+    join_all(
+        loaders
+            .into_iter()
+            .map(|loader| OptionFuture::from(loader.map(|loader| loader.0))),
+    )
+    .await
+}
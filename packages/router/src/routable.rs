@@ -3,6 +3,9 @@
 #![allow(non_snake_case)]
 use dioxus::prelude::*;
 
+use crate::guard::NavigationGuard;
+use crate::loader::RouteLoader;
+
 use std::iter::FlatMap;
 use std::slice::Iter;
 use std::{fmt::Display, str::FromStr};
@@ -40,6 +43,36 @@ impl<T: for<'a> From<&'a str>> FromQuery for T {
     }
 }
 
+/// Parse one named argument of a query string, like `page` in `?term=dioxus&page=2`.
+///
+/// This is used by `#[derive(Routable)]` for routes with multiple query arguments, like
+/// `#[route("/search?:term&:page")]`. The value is parsed with [`FromRouteSegment`]. If the
+/// argument is missing or fails to parse, the default value is used.
+pub fn parse_query_argument<T>(query: &str, name: &str) -> T
+where
+    T: FromRouteSegment + Default,
+    <T as FromRouteSegment>::Err: Display,
+{
+    for argument in query.split('&') {
+        let (key, value) = argument.split_once('=').unwrap_or((argument, ""));
+        let key = key.replace('+', " ");
+        if urlencoding::decode(&key).unwrap_or(key.as_str().into()) != name {
+            continue;
+        }
+
+        // forms encode spaces as `+`
+        return match T::from_route_segment(&value.replace('+', "%20")) {
+            Ok(value) => value,
+            Err(err) => {
+                tracing::error!("Failed to parse query argument {}: {}", name, err);
+                T::default()
+            }
+        };
+    }
+
+    T::default()
+}
+
 /// Something that can be created from a route segment.
 pub trait FromRouteSegment: Sized {
     /// The error that can occur when parsing a route segment.
@@ -252,6 +285,34 @@ pub trait Routable: FromStr + Display + Clone + 'static {
         Self::from_str(&new_route).ok()
     }
 
+    /// The guards that run before the router navigates to this route.
+    ///
+    /// These are the guards of the layouts the route is rendered in, from the outermost layout to
+    /// the innermost, followed by the guards of the route itself. Guards of child routers are not
+    /// included.
+    fn guards(&self) -> Vec<NavigationGuard<Self>> {
+        Vec::new()
+    }
+
+    /// The loaders that run before the router renders this route. See [`RouteLoader`] for more
+    /// details.
+    ///
+    /// There is one entry for every level the route is rendered at: the layouts the route is
+    /// rendered in, from the outermost layout to the innermost, followed by the route itself. The
+    /// entry is [`None`] if the layout or route at that level has no loader.
+    fn loaders(&self) -> Vec<Option<RouteLoader>> {
+        Vec::new()
+    }
+
+    /// The routes with dynamic segments that should be generated ahead of time.
+    ///
+    /// These are the routes returned by the functions listed with `#[static_routes(function)]`
+    /// in `#[derive(Routable)]`. Static site generation renders them together with
+    /// [`Self::static_routes`].
+    fn enumerated_routes() -> Vec<Self> {
+        Vec::new()
+    }
+
     /// Returns a flattened version of [`Self::SITE_MAP`].
     fn flatten_site_map<'a>() -> SiteMapFlattened<'a> {
         Self::SITE_MAP.iter().flat_map(SiteMapSegment::flatten)
@@ -334,6 +395,11 @@ impl SiteMapSegment {
 
 /// The type of a route segment.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum SegmentType {
     /// A static route segment.
     Static(&'static str),
@@ -1,3 +1,4 @@
+use std::future::Future;
 use std::sync::Arc;
 
 use crate::contexts::router::RoutingCallback;
@@ -28,6 +29,7 @@ pub struct RouterConfig<R: Routable> {
     pub(crate) failure_external_navigation: fn(Scope) -> Element,
     pub(crate) history: Option<Box<dyn AnyHistoryProvider>>,
     pub(crate) on_update: Option<RoutingCallback<R>>,
+    pub(crate) guards: Vec<NavigationGuard<R>>,
 }
 
 #[cfg(feature = "serde")]
@@ -41,6 +43,7 @@ where
             failure_external_navigation: FailureExternalNavigation::<R>,
             history: None,
             on_update: None,
+            guards: Vec::new(),
         }
     }
 }
@@ -72,6 +75,7 @@ where
             failure_external_navigation: FailureExternalNavigation,
             history: None,
             on_update: None,
+            guards: Vec::new(),
         }
     }
 }
@@ -120,6 +124,19 @@ where
         }
     }
 
+    /// Add a guard that runs before every navigation.
+    ///
+    /// Guards run in the order they are added, before the guards of the target route. See
+    /// [`NavigationGuard`] for more details.
+    pub fn guard<F, Fut>(mut self, guard: F) -> Self
+    where
+        F: Fn(NavigationRequest<R>) -> Fut + 'static,
+        Fut: Future<Output = GuardResult<R>> + 'static,
+    {
+        self.guards.push(NavigationGuard::new(guard));
+        self
+    }
+
     /// The [`HistoryProvider`] the router should use.
     ///
     /// Defaults to a default [`MemoryHistory`].
@@ -0,0 +1,297 @@
+//! Generate a `sitemap.xml` and a JSON route manifest from [`Routable::SITE_MAP`].
+
+use std::{
+    fmt::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::routable::{Routable, SegmentType};
+
+/// A page in a [`Sitemap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SitemapEntry {
+    /// The path of the page, relative to the base url.
+    pub path: String,
+    /// When the page was last modified.
+    pub lastmod: Option<SystemTime>,
+    /// How important the page is compared to the other pages of the site, from `0.0` to `1.0`.
+    pub priority: f32,
+}
+
+/// A `sitemap.xml` for the pages of a router.
+///
+/// The sitemap contains every page that can be generated ahead of time: the routes without
+/// dynamic segments and the routes listed with `#[static_routes(..)]` in `#[derive(Routable)]`.
+/// The priority of a page is a hint based on how deeply it is nested: `1.0` for `/`, and `0.1`
+/// less for every segment, down to `0.1`.
+///
+/// The sitemap can be served from a server route, or written next to the other files of a static
+/// site with [`Sitemap::write_to`].
+///
+/// ```rust
+/// # use dioxus::prelude::*;
+/// # use dioxus_router::prelude::*;
+/// # #[component]
+/// # fn Home(cx: Scope) -> Element { todo!() }
+/// # #[component]
+/// # fn Blog(cx: Scope) -> Element { todo!() }
+/// #[derive(Clone, Routable)]
+/// enum Route {
+///     #[route("/")]
+///     Home {},
+///     #[route("/blog")]
+///     Blog {},
+/// }
+///
+/// let sitemap = Sitemap::new::<Route>("https://example.com").priority("/blog", 0.8);
+/// assert!(sitemap.to_xml().contains("<loc>https://example.com/blog</loc>"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sitemap {
+    base_url: String,
+    entries: Vec<SitemapEntry>,
+}
+
+impl Sitemap {
+    /// Create a sitemap of all pages of the router for a site hosted at `base_url`.
+    pub fn new<R: Routable>(base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+
+        let entries = generated_pages::<R>()
+            .into_iter()
+            .map(|path| SitemapEntry {
+                priority: priority_hint(&path),
+                path,
+                lastmod: None,
+            })
+            .collect();
+
+        Self { base_url, entries }
+    }
+
+    /// Set the priority of a page.
+    pub fn priority(mut self, path: &str, priority: f32) -> Self {
+        if let Some(entry) = self.entry_mut(path) {
+            entry.priority = priority.clamp(0.0, 1.0);
+        }
+        self
+    }
+
+    /// Set when a page was last modified.
+    pub fn lastmod(mut self, path: &str, lastmod: SystemTime) -> Self {
+        if let Some(entry) = self.entry_mut(path) {
+            entry.lastmod = Some(lastmod);
+        }
+        self
+    }
+
+    /// Set when the pages were last modified from the freshness of the pages that were rendered
+    /// into the incremental cache.
+    #[cfg(feature = "ssr")]
+    pub fn lastmod_from_report(
+        mut self,
+        report: &crate::incremental::StaticGenerationReport,
+    ) -> Self {
+        let now = SystemTime::now();
+        for (path, freshness) in &report.rendered {
+            let age = std::time::Duration::from_secs(freshness.age());
+            if let Some(entry) = self.entry_mut(path) {
+                entry.lastmod = now.checked_sub(age);
+            }
+        }
+        self
+    }
+
+    /// The pages in the sitemap.
+    pub fn entries(&self) -> &[SitemapEntry] {
+        &self.entries
+    }
+
+    fn entry_mut(&mut self, path: &str) -> Option<&mut SitemapEntry> {
+        self.entries.iter_mut().find(|entry| entry.path == path)
+    }
+
+    /// Render the sitemap as XML.
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for entry in &self.entries {
+            let loc = format!("{}{}", self.base_url, entry.path);
+            xml += "  <url>\n";
+            let _ = writeln!(xml, "    <loc>{}</loc>", escape_xml(&loc));
+            if let Some(lastmod) = entry.lastmod {
+                let _ = writeln!(xml, "    <lastmod>{}</lastmod>", w3c_datetime(lastmod));
+            }
+            let _ = writeln!(xml, "    <priority>{:.1}</priority>", entry.priority);
+            xml += "  </url>\n";
+        }
+        xml += "</urlset>\n";
+        xml
+    }
+
+    /// Write the sitemap as XML to a file, creating the parent directories if needed.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        write_file(path.as_ref(), &self.to_xml())
+    }
+}
+
+/// A route in a [`RouteManifest`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ManifestRoute {
+    /// The route with its dynamic segments, like `/blog/:id`.
+    pub pattern: String,
+    /// The segments of the route.
+    pub segments: Vec<SegmentType>,
+}
+
+/// A JSON description of the routes of a router and the pages that can be generated from them.
+///
+/// Hosts and build tools can use the manifest to know which paths the app handles without running
+/// it. With the `serde` feature, the manifest can be written as JSON with this shape:
+///
+/// ```json
+/// {
+///   "routes": [
+///     {
+///       "pattern": "/blog/:id",
+///       "segments": [{ "type": "static", "value": "blog" }, { "type": "dynamic", "value": "id" }]
+///     }
+///   ],
+///   "pages": ["/blog/1", "/blog/2"]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RouteManifest {
+    /// All routes of the router.
+    pub routes: Vec<ManifestRoute>,
+    /// The pages that can be generated ahead of time. See [`Sitemap`].
+    pub pages: Vec<String>,
+}
+
+impl RouteManifest {
+    /// Create the manifest of a router.
+    pub fn new<R: Routable>() -> Self {
+        let routes = R::flatten_site_map()
+            .map(|segments| ManifestRoute {
+                pattern: pattern(&segments),
+                segments,
+            })
+            .collect();
+
+        Self {
+            routes,
+            pages: generated_pages::<R>(),
+        }
+    }
+
+    /// Render the manifest as JSON.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("the manifest only contains strings")
+    }
+
+    /// Write the manifest as JSON to a file, creating the parent directories if needed.
+    #[cfg(feature = "serde")]
+    pub fn write_to(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        write_file(path.as_ref(), &self.to_json())
+    }
+}
+
+/// The paths of the routes without dynamic segments and the enumerated routes
+fn generated_pages<R: Routable>() -> Vec<String> {
+    let mut pages = Vec::new();
+    for route in R::static_routes().into_iter().chain(R::enumerated_routes()) {
+        let path = route.to_string();
+        if !pages.contains(&path) {
+            pages.push(path);
+        }
+    }
+    pages
+}
+
+fn pattern(segments: &[SegmentType]) -> String {
+    let pattern: String = segments.iter().map(ToString::to_string).collect();
+    match pattern.is_empty() {
+        true => "/".to_string(),
+        false => pattern,
+    }
+}
+
+fn priority_hint(path: &str) -> f32 {
+    let path = path
+        .split(|c| c == '?' || c == '#')
+        .next()
+        .unwrap_or_default();
+    let depth = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .count();
+    (1.0 - depth as f32 * 0.1).max(0.1)
+}
+
+fn write_file(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped += "&amp;",
+            '<' => escaped += "&lt;",
+            '>' => escaped += "&gt;",
+            '"' => escaped += "&quot;",
+            '\'' => escaped += "&apos;",
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Format a time as a UTC W3C datetime, like `2023-10-05T14:30:00Z`
+fn w3c_datetime(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // convert the days since the epoch to a civil date (Howard Hinnant's civil_from_days)
+    let z = days as i64 + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
+
+#[test]
+fn formats_w3c_datetime() {
+    let time = UNIX_EPOCH + std::time::Duration::from_secs(1_696_516_200);
+    assert_eq!(w3c_datetime(time), "2023-10-05T14:30:00Z");
+    assert_eq!(w3c_datetime(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+}
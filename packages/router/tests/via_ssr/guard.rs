@@ -0,0 +1,148 @@
+#![allow(unused)]
+
+use std::cell::{Cell, RefCell};
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use futures_channel::oneshot;
+
+use crate::utils::{navigator, render, set_navigator};
+
+thread_local! {
+    static LOGGED_IN: Cell<bool> = Cell::new(false);
+    static CONFIRM: RefCell<Option<oneshot::Receiver<bool>>> = RefCell::new(None);
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[route("/login")]
+    Login {},
+    #[layout(Admin, guard = require_login)]
+        #[route("/admin")]
+        Dashboard {},
+    #[end_layout]
+    #[route("/blocked")]
+    #[guard(|_| async { GuardResult::Cancel })]
+    Blocked {},
+    #[route("/confirm")]
+    Confirm {},
+}
+
+async fn require_login(_: NavigationRequest<Route>) -> GuardResult<Route> {
+    match LOGGED_IN.with(Cell::get) {
+        true => GuardResult::Allow,
+        false => GuardResult::Redirect(Route::Login {}.into()),
+    }
+}
+
+// waits for the test to confirm navigations to /confirm
+async fn confirm(request: NavigationRequest<Route>) -> GuardResult<Route> {
+    if request.to != NavigationTarget::Internal(Route::Confirm {}) {
+        return GuardResult::Allow;
+    }
+    let confirmed = CONFIRM.with(|confirm| confirm.borrow_mut().take());
+    match confirmed {
+        Some(confirmed) if confirmed.await == Ok(true) => GuardResult::Allow,
+        _ => GuardResult::Cancel,
+    }
+}
+
+fn prepare() -> VirtualDom {
+    let mut vdom = VirtualDom::new(App);
+    let _ = vdom.rebuild();
+    return vdom;
+
+    #[component]
+    fn App(cx: Scope) -> Element {
+        render! {
+            Router::<Route> {
+                config: || RouterConfig::default()
+                    .history(MemoryHistory::default())
+                    .guard(confirm)
+            }
+        }
+    }
+}
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    let navigator = use_navigator(cx);
+    set_navigator(navigator);
+    render! { h1 { "Home" } }
+}
+
+#[component]
+fn Login(cx: Scope) -> Element {
+    render! { h1 { "Login" } }
+}
+
+#[component]
+fn Admin(cx: Scope) -> Element {
+    render! {
+        h1 { "Admin" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn Dashboard(cx: Scope) -> Element {
+    render! { h2 { "Dashboard" } }
+}
+
+#[component]
+fn Blocked(cx: Scope) -> Element {
+    render! { h1 { "Blocked" } }
+}
+
+#[component]
+fn Confirm(cx: Scope) -> Element {
+    render! { h1 { "Confirm" } }
+}
+
+#[test]
+fn route_guard_cancels() {
+    let mut vdom = prepare();
+
+    navigator().push(Route::Blocked {});
+    assert_eq!(render(&mut vdom), "<h1>Home</h1>");
+    assert!(!navigator().can_go_back());
+}
+
+#[test]
+fn layout_guard_redirects() {
+    let mut vdom = prepare();
+
+    navigator().push(Route::Dashboard {});
+    assert_eq!(render(&mut vdom), "<h1>Login</h1>");
+
+    LOGGED_IN.with(|logged_in| logged_in.set(true));
+    navigator().push(Route::Dashboard {});
+    assert_eq!(render(&mut vdom), "<h1>Admin</h1><h2>Dashboard</h2>");
+
+    navigator().go_back();
+    assert_eq!(render(&mut vdom), "<h1>Login</h1>");
+}
+
+#[test]
+fn async_guard_waits() {
+    let mut vdom = prepare();
+
+    let (confirm, confirmed) = oneshot::channel();
+    CONFIRM.with(|c| *c.borrow_mut() = Some(confirmed));
+    navigator().push(Route::Confirm {});
+    // the router stays on the current route until the guard is finished
+    assert_eq!(render(&mut vdom), "<h1>Home</h1>");
+
+    confirm.send(true).unwrap();
+    assert_eq!(render(&mut vdom), "<h1>Confirm</h1>");
+
+    let (confirm, confirmed) = oneshot::channel();
+    CONFIRM.with(|c| *c.borrow_mut() = Some(confirmed));
+    navigator().go_back();
+    navigator().push(Route::Confirm {});
+    confirm.send(false).unwrap();
+    assert_eq!(render(&mut vdom), "<h1>Home</h1>");
+}
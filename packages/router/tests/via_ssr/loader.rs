@@ -0,0 +1,159 @@
+#![allow(unused)]
+
+use std::cell::RefCell;
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use futures_channel::oneshot;
+
+use crate::utils::{navigator, render, set_navigator};
+
+thread_local! {
+    static USERS: RefCell<Vec<oneshot::Sender<String>>> = RefCell::new(Vec::new());
+}
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/", Home, loader = load_greeting)]
+    Home {},
+    #[route("/about", About, loader = || async { "About us".to_string() })]
+    About {},
+    #[nest("/team/:team")]
+        #[layout(Team, loader = load_team)]
+            #[route("/user/:id", User, loader = load_user)]
+            User { team: String, id: usize },
+}
+
+async fn load_greeting() -> String {
+    "Welcome".to_string()
+}
+
+// the layout loads the same type as the route
+async fn load_team(team: String) -> String {
+    team.to_uppercase()
+}
+
+// waits for the test to send the name of the user
+async fn load_user(team: String, id: usize) -> String {
+    let (user, name) = oneshot::channel();
+    USERS.with(|users| users.borrow_mut().push(user));
+    format!("{} ({id})", name.await.unwrap())
+}
+
+fn prepare(initial: Route) -> VirtualDom {
+    let mut vdom = VirtualDom::new_with_props(App, AppProps { initial });
+    let _ = vdom.rebuild();
+    return vdom;
+
+    #[derive(Props, PartialEq)]
+    struct AppProps {
+        initial: Route,
+    }
+
+    #[component]
+    fn App(cx: Scope<AppProps>) -> Element {
+        render! {
+            Router::<Route> {
+                config: {
+                    let initial = cx.props.initial.clone();
+                    move || RouterConfig::default().history(MemoryHistory::with_initial_path(initial))
+                }
+            }
+        }
+    }
+}
+
+fn send_user(name: &str) {
+    let user = USERS.with(|users| users.borrow_mut().remove(0));
+    user.send(name.to_string()).unwrap();
+}
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    let navigator = use_navigator(cx);
+    set_navigator(navigator);
+    let greeting = use_loader_data::<String>(cx)?;
+    render! { h1 { "{greeting}" } }
+}
+
+#[component]
+fn About(cx: Scope) -> Element {
+    let about = use_loader_data::<String>(cx)?;
+    render! { h1 { "{about}" } }
+}
+
+#[component]
+fn Team(cx: Scope, team: String) -> Element {
+    let name = use_loader_data::<String>(cx)?;
+    render! {
+        h1 { "{name}" }
+        Outlet::<Route> {}
+    }
+}
+
+#[component]
+fn User(cx: Scope, team: String, id: usize) -> Element {
+    let name = use_loader_data::<String>(cx)?;
+    render! { h2 { "{name}" } }
+}
+
+#[test]
+fn ready_loaders_render_immediately() {
+    let mut vdom = prepare(Route::Home {});
+
+    assert_eq!(dioxus_ssr::render(&vdom), "<h1>Welcome</h1>");
+}
+
+#[test]
+fn closures_can_be_loaders() {
+    let mut vdom = prepare(Route::About {});
+
+    assert_eq!(render(&mut vdom), "<h1>About us</h1>");
+}
+
+#[test]
+fn navigation_waits_for_loaders() {
+    let mut vdom = prepare(Route::Home {});
+
+    navigator().push(Route::User {
+        team: "core".to_string(),
+        id: 1,
+    });
+    // the previous route stays on the screen until the loaders are finished
+    assert_eq!(render(&mut vdom), "<h1>Welcome</h1>");
+
+    send_user("Ferris");
+    assert_eq!(render(&mut vdom), "<h1>CORE</h1><h2>Ferris (1)</h2>");
+}
+
+#[test]
+fn first_route_suspends() {
+    let mut vdom = prepare(Route::User {
+        team: "core".to_string(),
+        id: 2,
+    });
+    assert!(vdom.has_suspended_work());
+    assert_eq!(dioxus_ssr::render(&vdom), "");
+
+    send_user("Ferris");
+    assert_eq!(render(&mut vdom), "<h1>CORE</h1><h2>Ferris (2)</h2>");
+    assert!(!vdom.has_suspended_work());
+}
+
+#[test]
+fn navigating_back_cancels_loaders() {
+    let mut vdom = prepare(Route::Home {});
+
+    navigator().push(Route::User {
+        team: "core".to_string(),
+        id: 3,
+    });
+    assert_eq!(render(&mut vdom), "<h1>Welcome</h1>");
+
+    navigator().go_back();
+    assert_eq!(render(&mut vdom), "<h1>Welcome</h1>");
+    // the loader of the user was dropped, so it can't replace the current route later
+    let user = USERS.with(|users| users.borrow_mut().remove(0));
+    assert!(user.is_canceled());
+}
@@ -1,2 +1,36 @@
+mod guard;
 mod link;
+mod loader;
 mod outlet;
+mod query;
+mod sitemap;
+mod static_routes;
+
+/// Helpers for the tests that navigate a router and render it
+mod utils {
+    use std::cell::RefCell;
+
+    use dioxus::prelude::*;
+    use dioxus_router::prelude::*;
+
+    thread_local! {
+        static NAVIGATOR: RefCell<Option<Navigator>> = RefCell::new(None);
+    }
+
+    /// Remember the navigator of the router that is being tested
+    pub(crate) fn set_navigator(navigator: &Navigator) {
+        NAVIGATOR.with(|n| *n.borrow_mut() = Some(navigator.clone()));
+    }
+
+    /// Get the navigator of the router that is being tested
+    pub(crate) fn navigator() -> Navigator {
+        NAVIGATOR.with(|navigator| navigator.borrow().clone().unwrap())
+    }
+
+    /// Handle the pending navigations and render the router
+    pub(crate) fn render(vdom: &mut VirtualDom) -> String {
+        vdom.process_events();
+        let _ = vdom.render_immediate();
+        dioxus_ssr::render(vdom)
+    }
+}
@@ -0,0 +1,85 @@
+#![allow(unused)]
+
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+enum Route {
+    #[route("/search?:term&:page&:sort")]
+    Search {
+        term: String,
+        page: usize,
+        sort: String,
+    },
+    #[route("/raw?:query")]
+    Raw { query: String },
+}
+
+#[component]
+fn Search(cx: Scope, term: String, page: usize, sort: String) -> Element {
+    todo!()
+}
+
+#[component]
+fn Raw(cx: Scope, query: String) -> Element {
+    todo!()
+}
+
+#[test]
+fn parses_query_arguments() {
+    assert_eq!(
+        Route::from_str("/search?page=2&term=hello%20world&sort=new").unwrap(),
+        Route::Search {
+            term: "hello world".to_string(),
+            page: 2,
+            sort: "new".to_string(),
+        }
+    );
+}
+
+#[test]
+fn missing_query_arguments_use_defaults() {
+    assert_eq!(
+        Route::from_str("/search?term=dioxus+router").unwrap(),
+        Route::Search {
+            term: "dioxus router".to_string(),
+            page: 0,
+            sort: String::new(),
+        }
+    );
+    assert_eq!(
+        Route::from_str("/search?page=not-a-number").unwrap(),
+        Route::Search {
+            term: String::new(),
+            page: 0,
+            sort: String::new(),
+        }
+    );
+}
+
+#[test]
+fn displays_encoded_query_arguments() {
+    let route = Route::Search {
+        term: "a&b=c d".to_string(),
+        page: 3,
+        sort: "top".to_string(),
+    };
+
+    assert_eq!(
+        route.to_string(),
+        "/search?term=a%26b%3Dc%20d&page=3&sort=top"
+    );
+    assert_eq!(Route::from_str(&route.to_string()).unwrap(), route);
+}
+
+#[test]
+fn single_query_segment_receives_the_whole_query() {
+    assert_eq!(
+        Route::from_str("/raw?a=1&b=2").unwrap(),
+        Route::Raw {
+            query: "a=1&b=2".to_string()
+        }
+    );
+}
@@ -0,0 +1,79 @@
+#![allow(unused)]
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[nest("/blog")]
+        #[route("/about")]
+        About {},
+        #[route("/:id")]
+        #[static_routes(|| [Route::Post { id: 1 }])]
+        Post { id: usize },
+}
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    todo!()
+}
+
+#[component]
+fn About(cx: Scope) -> Element {
+    todo!()
+}
+
+#[component]
+fn Post(cx: Scope, id: usize) -> Element {
+    todo!()
+}
+
+#[test]
+fn sitemap_lists_generated_pages() {
+    let sitemap = Sitemap::new::<Route>("https://example.com/")
+        .priority("/blog/about", 0.3)
+        .lastmod("/", UNIX_EPOCH + Duration::from_secs(1_696_516_200));
+
+    assert_eq!(
+        sitemap.to_xml(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <url>
+    <loc>https://example.com/</loc>
+    <lastmod>2023-10-05T14:30:00Z</lastmod>
+    <priority>1.0</priority>
+  </url>
+  <url>
+    <loc>https://example.com/blog/about</loc>
+    <priority>0.3</priority>
+  </url>
+  <url>
+    <loc>https://example.com/blog/1</loc>
+    <priority>0.8</priority>
+  </url>
+</urlset>
+"#
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn manifest_describes_routes() {
+    let manifest = RouteManifest::new::<Route>();
+
+    assert_eq!(
+        manifest.to_json(),
+        concat!(
+            r#"{"routes":["#,
+            r#"{"pattern":"/","segments":[{"type":"static","value":""}]},"#,
+            r#"{"pattern":"/blog/about","segments":[{"type":"static","value":"blog"},{"type":"static","value":"about"}]},"#,
+            r#"{"pattern":"/blog/:id","segments":[{"type":"static","value":"blog"},{"type":"dynamic","value":"id"}]}"#,
+            r#"],"pages":["/","/blog/about","/blog/1"]}"#
+        )
+    );
+}
@@ -0,0 +1,110 @@
+#![allow(unused)]
+
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+
+#[derive(Routable, Clone, PartialEq, Debug)]
+#[rustfmt::skip]
+enum Route {
+    #[route("/")]
+    Home {},
+    #[nest("/blog")]
+        #[route("/:id")]
+        #[static_routes(|| (0..3).map(|id| Route::Post { id }))]
+        Post { id: usize },
+        #[route("/:id/:page")]
+        #[static_routes(post_pages)]
+        #[static_routes(|| [Route::PostPage { id: 9, page: "draft".to_string() }])]
+        PostPage { id: usize, page: String },
+}
+
+fn post_pages() -> Vec<Route> {
+    vec![Route::PostPage {
+        id: 0,
+        page: "comments".to_string(),
+    }]
+}
+
+#[component]
+fn Home(cx: Scope) -> Element {
+    render! { h1 { "Home" } }
+}
+
+#[component]
+fn Post(cx: Scope, id: usize) -> Element {
+    render! { h1 { "Post {id}" } }
+}
+
+#[component]
+fn PostPage(cx: Scope, id: usize, page: String) -> Element {
+    render! { h1 { "Post {id}: {page}" } }
+}
+
+#[test]
+fn enumerates_dynamic_routes() {
+    let routes: Vec<_> = Route::enumerated_routes()
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+
+    assert_eq!(
+        routes,
+        [
+            "/blog/0",
+            "/blog/1",
+            "/blog/2",
+            "/blog/0/comments",
+            "/blog/9/draft",
+        ]
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::test]
+async fn generates_enumerated_routes() {
+    use dioxus_ssr::incremental::{DefaultRenderer, IncrementalRendererConfig};
+
+    let dir = std::env::temp_dir().join(format!("dioxus-router-static-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    // A file where the directory of a page should be makes that page fail to render
+    std::fs::create_dir_all(dir.join("blog")).unwrap();
+    std::fs::write(dir.join("blog").join("2"), "").unwrap();
+
+    let mut renderer = IncrementalRendererConfig::new().static_dir(&dir).build();
+    let report = export_static_site::<Route, _>(&mut renderer, &DefaultRenderer::default()).await;
+
+    let rendered: Vec<_> = report
+        .rendered
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect();
+    assert_eq!(
+        rendered,
+        [
+            "/",
+            "/blog/0",
+            "/blog/1",
+            "/blog/0/comments",
+            "/blog/9/draft"
+        ]
+    );
+    let failed: Vec<_> = report
+        .failed
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect();
+    assert_eq!(failed, ["/blog/2"]);
+
+    let page = std::fs::read_to_string(dir.join("blog").join("9").join("draft").join("index.html"))
+        .unwrap();
+    assert!(page.contains("<h1>Post 9: draft</h1>"));
+
+    // Pre-caching renders the same routes again from the cache and returns the failure
+    assert!(
+        pre_cache_static_routes::<Route, _>(&mut renderer, &DefaultRenderer::default())
+            .await
+            .is_err()
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
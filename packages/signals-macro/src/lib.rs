@@ -0,0 +1,101 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields};
+
+/// Derives an extension trait for `Store<T>` with a method that returns a store for each field of a struct.
+///
+/// For a struct named `Todo`, the trait is named `TodoStoreExt` and has the same visibility as the struct. Fields with
+/// the same name as a method of `Store` must be accessed with the trait method syntax (`TodoStoreExt::read(&store)`).
+///
+/// ```rust, ignore
+/// #[derive(Store)]
+/// struct Todo {
+///     title: String,
+///     done: bool,
+/// }
+///
+/// let todo = Store::new(Todo { title: "Write docs".into(), done: false });
+/// let title: Store<String> = todo.title();
+/// ```
+#[proc_macro_derive(Store)]
+pub fn derive_store(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match store_ext(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn store_ext(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "Store can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Store can only be derived for structs",
+            ))
+        }
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let ext = format_ident!("{}StoreExt", name);
+    let names: Vec<_> = fields.iter().map(|field| &field.ident).collect();
+    let types: Vec<_> = fields.iter().map(|field| &field.ty).collect();
+
+    // Every value in a store must be 'static
+    let mut generics = input.generics.clone();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let where_clause = generics.make_where_clause();
+    where_clause
+        .predicates
+        .push(parse_quote!(#name #ty_generics: 'static));
+    for ty in &types {
+        where_clause.predicates.push(parse_quote!(#ty: 'static));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let doc = format!("Get a store for each field of [`{name}`]");
+    let field_docs = names.iter().map(|field| {
+        let field = field.as_ref().unwrap();
+        format!("Get a store for the `{field}` field")
+    });
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis trait #ext #impl_generics #where_clause {
+            #(
+                #[doc = #field_docs]
+                fn #names(&self) -> ::dioxus_signals::Store<#types>;
+            )*
+        }
+
+        impl #impl_generics #ext #ty_generics for ::dioxus_signals::Store<#name #ty_generics> #where_clause {
+            #(
+                fn #names(&self) -> ::dioxus_signals::Store<#types> {
+                    self.field(
+                        ::core::stringify!(#names),
+                        |value| &value.#names,
+                        |value| &mut value.#names,
+                    )
+                }
+            )*
+        }
+    })
+}
@@ -0,0 +1,162 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use dioxus_core::prelude::*;
+use futures_channel::mpsc::unbounded;
+use futures_util::future::{select, Either};
+use futures_util::StreamExt;
+
+use crate::{Effect, ReadOnlySignal, Signal};
+
+type BoxedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>>>>;
+
+/// The state of an async selector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsyncState<T, E> {
+    /// The selector has not resolved to a value yet
+    Pending,
+    /// The last future the selector ran resolved to a value
+    Ready(T),
+    /// The last future the selector ran failed
+    Error(E),
+}
+
+impl<T, E> AsyncState<T, E> {
+    /// Returns true if the selector has not resolved to a value yet
+    pub fn is_pending(&self) -> bool {
+        matches!(self, AsyncState::Pending)
+    }
+
+    /// Get the value the selector resolved to, if it resolved successfully
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            AsyncState::Ready(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get the error the selector failed with, if it failed
+    pub fn error(&self) -> Option<&E> {
+        match self {
+            AsyncState::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl<T, E> From<Result<T, E>> for AsyncState<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => AsyncState::Ready(value),
+            Err(error) => AsyncState::Error(error),
+        }
+    }
+}
+
+/// Creates a new async Selector. The closure will be run immediately and whenever any signal it reads changes. The future it returns is run in the background and the selector is updated with the output once it resolves.
+///
+/// Only signals read by the closure are tracked, signals read inside the future are not. If the closure is rerun while the last future is still running, the last future is cancelled. While a new future is running, the selector keeps the last value it resolved to.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// async fn fetch_user(id: usize) -> Result<String, String> {
+///     Ok(format!("user {id}"))
+/// }
+///
+/// fn App(cx: Scope) -> Element {
+///     let id = use_signal(cx, || 0);
+///     let user = use_async_selector(cx, move || {
+///         let id = id.value();
+///         async move { fetch_user(id).await }
+///     });
+///
+///     match &*user.read() {
+///         AsyncState::Pending => render! { "Loading..." },
+///         AsyncState::Ready(user) => render! { "{user}" },
+///         AsyncState::Error(error) => render! { "Failed to load the user: {error}" },
+///     }
+/// }
+/// ```
+pub fn use_async_selector<T: 'static, E: 'static, F: Future<Output = Result<T, E>> + 'static>(
+    cx: &ScopeState,
+    f: impl FnMut() -> F + 'static,
+) -> ReadOnlySignal<AsyncState<T, E>> {
+    *cx.use_hook(|| async_selector(f))
+}
+
+/// Creates a new async Selector and suspends the component until the selector resolves for the first time. See [`use_async_selector`] for more information.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// async fn fetch_user(id: usize) -> Result<String, String> {
+///     Ok(format!("user {id}"))
+/// }
+///
+/// fn Profile(cx: Scope) -> Element {
+///     let id = use_signal(cx, || 0);
+///     let user = use_async_selector_suspense(cx, move || {
+///         let id = id.value();
+///         async move { fetch_user(id).await }
+///     })?;
+///
+///     render! { "{user:?}" }
+/// }
+/// ```
+pub fn use_async_selector_suspense<
+    T: 'static,
+    E: 'static,
+    F: Future<Output = Result<T, E>> + 'static,
+>(
+    cx: &ScopeState,
+    f: impl FnMut() -> F + 'static,
+) -> Option<ReadOnlySignal<AsyncState<T, E>>> {
+    let selector = use_async_selector(cx, f);
+    if selector.read().is_pending() {
+        cx.suspend();
+        None
+    } else {
+        Some(selector)
+    }
+}
+
+/// Creates a new async Selector. The closure will be run immediately and whenever any signal it reads changes. The future it returns is run in the background and the selector is updated with the output once it resolves.
+///
+/// The future is polled in the current component and is cancelled when the component is dropped. See [`use_async_selector`] for more information.
+pub fn async_selector<T: 'static, E: 'static, F: Future<Output = Result<T, E>> + 'static>(
+    mut f: impl FnMut() -> F + 'static,
+) -> ReadOnlySignal<AsyncState<T, E>> {
+    let state = Signal::new(AsyncState::Pending);
+    let (tx, mut rx) = unbounded::<BoxedFuture<T, E>>();
+
+    spawn(async move {
+        let mut running: Option<BoxedFuture<T, E>> = None;
+        loop {
+            let next = match &mut running {
+                Some(future) => match select(rx.next(), future).await {
+                    Either::Left((next, _)) => Either::Left(next),
+                    Either::Right((output, _)) => Either::Right(output),
+                },
+                None => Either::Left(rx.next().await),
+            };
+            match next {
+                // Dropping the running future cancels it
+                Either::Left(Some(future)) => running = Some(future),
+                Either::Left(None) => break,
+                Either::Right(output) => {
+                    running = None;
+                    state.set(output.into());
+                }
+            }
+        }
+    });
+
+    Effect::new(move || {
+        let _ = tx.unbounded_send(Box::pin(f()));
+    });
+
+    ReadOnlySignal::new(state)
+}
@@ -1,37 +1,233 @@
 use core::{self, fmt::Debug};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{self, Formatter};
+use std::panic::Location;
 use std::rc::Rc;
+use std::thread::ThreadId;
 //
 use dioxus_core::prelude::*;
+use futures_channel::mpsc::{unbounded, UnboundedSender};
+use futures_util::StreamExt;
+use generational_box::BorrowMutError;
 
+use crate::graph::{self, Graph, NodeGuard, ReactiveNodeKind};
 use crate::use_signal;
 use crate::{dependency::Dependency, CopyValue};
 
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub(crate) struct EffectStack {
     pub(crate) effects: Rc<RefCell<Vec<Effect>>>,
+    // Effects that were triggered from other threads are sent here to run on the thread of the VirtualDom
+    rerun: UnboundedSender<usize>,
 }
 
 pub(crate) fn get_effect_stack() -> EffectStack {
     match consume_context() {
         Some(rt) => rt,
         None => {
-            let store = EffectStack::default();
+            let (rerun, mut rx) = unbounded();
+            spawn_forever(async move {
+                while let Some(id) = rx.next().await {
+                    queue_effect(id);
+                }
+            });
+            let store = EffectStack {
+                effects: Default::default(),
+                rerun,
+            };
             provide_root_context(store.clone());
             store
         }
     }
 }
 
+struct RegisteredEffect {
+    effect: Effect,
+    rerun: UnboundedSender<usize>,
+    // Effects run after every effect with a lower height. An effect is always higher than the effects that write to
+    // the signals it reads, so each effect in a diamond dependency graph only runs once.
+    height: usize,
+}
+
+#[derive(Default)]
+struct EffectQueue {
+    // The number of batches that are currently running
+    batches: usize,
+    flushing: bool,
+    pending: Vec<usize>,
+}
+
+thread_local! {
+    // Every effect that is alive on this thread with the channel that reruns it
+    static EFFECTS: RefCell<HashMap<usize, RegisteredEffect>> = RefCell::new(HashMap::new());
+    // The effects that are waiting to be rerun on this thread
+    static QUEUE: RefCell<EffectQueue> = RefCell::new(EffectQueue::default());
+}
+
+/// Unregisters an effect when the owner of the effect drops it
+struct EffectGuard(usize);
+
+impl Drop for EffectGuard {
+    fn drop(&mut self) {
+        // The effects may already be gone if the owner is dropped while the thread is shutting down
+        let _ = EFFECTS.try_with(|effects| effects.borrow_mut().remove(&self.0));
+    }
+}
+
+fn registered_effect(id: usize) -> Option<Effect> {
+    EFFECTS.with(|effects| {
+        effects
+            .borrow()
+            .get(&id)
+            .map(|registered| registered.effect)
+    })
+}
+
+/// Queue an effect to rerun once the current batch ends
+fn queue_effect(id: usize) {
+    let writer = Effect::current();
+    // Effects that write to a signal they read don't rerun themselves
+    if writer.map(|writer| writer.id) == Some(id) {
+        return;
+    }
+
+    EFFECTS.with(|effects| {
+        let mut effects = effects.borrow_mut();
+        let writer_height = writer.and_then(|writer| effects.get(&writer.id).map(|e| e.height));
+        if let (Some(writer_height), Some(effect)) = (writer_height, effects.get_mut(&id)) {
+            effect.height = effect.height.max(writer_height + 1);
+        }
+    });
+
+    let flush = QUEUE.with(|queue| {
+        let mut queue = queue.borrow_mut();
+        if !queue.pending.contains(&id) {
+            queue.pending.push(id);
+        }
+        queue.batches == 0 && !queue.flushing
+    });
+    if flush {
+        flush_effects();
+    }
+}
+
+/// Run every queued effect, lowest effects first
+fn flush_effects() {
+    QUEUE.with(|queue| queue.borrow_mut().flushing = true);
+    while let Some(id) = next_queued_effect() {
+        if let Some(effect) = registered_effect(id) {
+            effect.try_run();
+        }
+    }
+    QUEUE.with(|queue| queue.borrow_mut().flushing = false);
+}
+
+fn next_queued_effect() -> Option<usize> {
+    QUEUE.with(|queue| {
+        let mut queue = queue.borrow_mut();
+        let (index, _) = EFFECTS.with(|effects| {
+            let effects = effects.borrow();
+            queue
+                .pending
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, id)| effects.get(id).map(|e| e.height))
+        })?;
+        Some(queue.pending.remove(index))
+    })
+}
+
+/// Run a closure and defer rerunning the effects and selectors that depend on the signals it writes to until the
+/// closure returns. Every effect runs at most once per batch, after every value it depends on is up to date.
+///
+/// Components are only marked as dirty when a signal is written to, so they always rerender once after the batch.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// fn App(cx: Scope) -> Element {
+///     let first = use_signal(cx, || "Jane".to_string());
+///     let last = use_signal(cx, || "Doe".to_string());
+///     use_effect(cx, move || println!("{first} {last}"));
+///
+///     render! {
+///         button {
+///             // The effect only runs once with both names updated
+///             onclick: move |_| batch(|| {
+///                 first.set("John".to_string());
+///                 last.set("Smith".to_string());
+///             }),
+///             "Rename"
+///         }
+///     }
+/// }
+/// ```
+pub fn batch<O>(f: impl FnOnce() -> O) -> O {
+    struct Batch;
+
+    impl Drop for Batch {
+        fn drop(&mut self) {
+            let flush = QUEUE.with(|queue| {
+                let mut queue = queue.borrow_mut();
+                queue.batches -= 1;
+                queue.batches == 0 && !queue.flushing
+            });
+            if flush {
+                flush_effects();
+            }
+        }
+    }
+
+    QUEUE.with(|queue| queue.borrow_mut().batches += 1);
+    let _batch = Batch;
+    f()
+}
+
+/// A handle to an effect that can be sent to other threads. The effect itself always runs on the thread it was
+/// created on.
+#[derive(Clone)]
+pub(crate) struct EffectSubscriber {
+    pub(crate) id: usize,
+    thread: ThreadId,
+    rerun: UnboundedSender<usize>,
+}
+
+impl PartialEq for EffectSubscriber {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Debug for EffectSubscriber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_fmt(format_args!("Effect({})", self.id))
+    }
+}
+
+impl EffectSubscriber {
+    /// Rerun the effect. Writes from other threads queue the effect to run on the thread of the VirtualDom.
+    pub(crate) fn rerun(&self) {
+        if std::thread::current().id() == self.thread {
+            queue_effect(self.id);
+        } else {
+            _ = self.rerun.unbounded_send(self.id);
+        }
+    }
+}
+
 /// Create a new effect. The effect will be run immediately and whenever any signal it reads changes.
 /// The signal will be owned by the current component and will be dropped when the component is dropped.
+#[track_caller]
 pub fn use_effect(cx: &ScopeState, callback: impl FnMut() + 'static) {
-    cx.use_hook(|| Effect::new(callback));
+    let caller = Location::caller();
+    cx.use_hook(|| Effect::new_with_caller(callback, caller));
 }
 
 /// Create a new effect. The effect will be run immediately and whenever any signal it reads changes.
 /// The signal will be owned by the current component and will be dropped when the component is dropped.
+#[track_caller]
 pub fn use_effect_with_dependencies<D: Dependency>(
     cx: &ScopeState,
     dependencies: D,
@@ -39,12 +235,16 @@ pub fn use_effect_with_dependencies<D: Dependency>(
 ) where
     D::Out: 'static,
 {
+    let caller = Location::caller();
     let dependencies_signal = use_signal(cx, || dependencies.out());
     cx.use_hook(|| {
-        Effect::new(move || {
-            let deref = &*dependencies_signal.read();
-            callback(deref.clone());
-        });
+        Effect::new_with_caller(
+            move || {
+                let deref = &*dependencies_signal.read();
+                callback(deref.clone());
+            },
+            caller,
+        );
     });
     let changed = { dependencies.changed(&*dependencies_signal.read()) };
     if changed {
@@ -55,6 +255,7 @@ pub fn use_effect_with_dependencies<D: Dependency>(
 /// Effects allow you to run code when a signal changes. Effects are run immediately and whenever any signal it reads changes.
 #[derive(Copy, Clone, PartialEq)]
 pub struct Effect {
+    pub(crate) id: usize,
     pub(crate) source: ScopeId,
     pub(crate) callback: CopyValue<Box<dyn FnMut()>>,
 }
@@ -73,27 +274,91 @@ impl Effect {
     /// Create a new effect. The effect will be run immediately and whenever any signal it reads changes.
     ///
     /// The signal will be owned by the current component and will be dropped when the component is dropped.
+    #[track_caller]
     pub fn new(callback: impl FnMut() + 'static) -> Self {
+        Self::new_with_caller(callback, Location::caller())
+    }
+
+    /// Create a new effect that was created by the code at `caller`
+    pub(crate) fn new_with_caller(
+        callback: impl FnMut() + 'static,
+        caller: &'static Location<'static>,
+    ) -> Self {
+        let myself = Self::with_callback(CopyValue::new(Box::new(callback)));
+        if let Some(graph) = Graph::current() {
+            graph.register(
+                myself.id,
+                ReactiveNodeKind::Effect,
+                myself.source,
+                caller,
+                None,
+            );
+            // Remove the effect from the graph when the owner of the callback drops it
+            CopyValue::new(NodeGuard {
+                graph,
+                id: myself.id,
+            });
+        }
+
+        myself.try_run();
+
+        myself
+    }
+
+    /// Create an effect and register it so writes from any thread can rerun it
+    pub(crate) fn with_callback(callback: CopyValue<Box<dyn FnMut()>>) -> Self {
         let myself = Self {
+            id: graph::next_node_id(),
             source: current_scope_id().expect("in a virtual dom"),
-            callback: CopyValue::new(Box::new(callback)),
+            callback,
         };
-
-        myself.try_run();
+        let rerun = get_effect_stack().rerun;
+        EFFECTS.with(|effects| {
+            effects.borrow_mut().insert(
+                myself.id,
+                RegisteredEffect {
+                    effect: myself,
+                    rerun,
+                    height: 0,
+                },
+            )
+        });
+        CopyValue::new(EffectGuard(myself.id));
 
         myself
     }
 
+    /// Get a handle to this effect that signals can store
+    pub(crate) fn subscriber(&self) -> Option<EffectSubscriber> {
+        EFFECTS.with(|effects| {
+            let effects = effects.borrow();
+            let registered = effects.get(&self.id)?;
+            Some(EffectSubscriber {
+                id: self.id,
+                thread: std::thread::current().id(),
+                rerun: registered.rerun.clone(),
+            })
+        })
+    }
+
     /// Run the effect callback immediately. Returns `true` if the effect was run. Returns `false` is the effect is dead.
     pub fn try_run(&self) {
-        if let Some(mut callback) = self.callback.try_write() {
-            {
-                get_effect_stack().effects.borrow_mut().push(*self);
+        match self.callback.try_write() {
+            Ok(mut callback) => {
+                {
+                    get_effect_stack().effects.borrow_mut().push(*self);
+                }
+                callback();
+                {
+                    get_effect_stack().effects.borrow_mut().pop();
+                }
             }
-            callback();
-            {
-                get_effect_stack().effects.borrow_mut().pop();
+            Err(BorrowMutError::Dropped(_)) => {
+                // The owner of the effect was dropped
+                EFFECTS.with(|effects| effects.borrow_mut().remove(&self.id));
             }
+            // The effect is already running
+            Err(_) => {}
         }
     }
 }
@@ -0,0 +1,314 @@
+//! Introspection of the reactive graph. Every signal, selector and effect registers itself in the graph of its
+//! VirtualDom in debug builds so tools can find out which write caused a component or effect to rerun.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write as _};
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use dioxus_core::prelude::{consume_context, current_scope_id, provide_root_context};
+use dioxus_core::{ScopeId, VirtualDom};
+use parking_lot::Mutex;
+
+use crate::signal::{EffectSubscribers, Subscribers};
+use crate::Effect;
+
+/// The number of writes that are remembered for each signal
+const WRITE_HISTORY: usize = 16;
+
+static NEXT_NODE: AtomicUsize = AtomicUsize::new(0);
+static NEXT_WRITE: AtomicU64 = AtomicU64::new(0);
+
+/// Get a unique id for a node in the reactive graph
+pub(crate) fn next_node_id() -> usize {
+    NEXT_NODE.fetch_add(1, Ordering::Relaxed)
+}
+
+struct NodeEntry {
+    kind: ReactiveNodeKind,
+    origin_scope: ScopeId,
+    location: &'static Location<'static>,
+    subscribers: Option<(Subscribers, EffectSubscribers)>,
+    writes: VecDeque<WriteRecord>,
+}
+
+/// The reactive graph of a VirtualDom. Signals in sync storage can be written to from other threads, so the graph is
+/// behind a mutex.
+#[derive(Clone, Default)]
+pub(crate) struct Graph(Arc<Mutex<HashMap<usize, NodeEntry>>>);
+
+impl Graph {
+    /// Get the graph of the current VirtualDom, creating it if needed. The graph is only tracked in debug builds, this
+    /// returns `None` in release builds.
+    pub(crate) fn current() -> Option<Self> {
+        if !cfg!(debug_assertions) {
+            return None;
+        }
+        match consume_context() {
+            Some(graph) => Some(graph),
+            None => provide_root_context(Graph::default()),
+        }
+    }
+
+    /// Add a node to the graph
+    pub(crate) fn register(
+        &self,
+        id: usize,
+        kind: ReactiveNodeKind,
+        origin_scope: ScopeId,
+        location: &'static Location<'static>,
+        subscribers: Option<(Subscribers, EffectSubscribers)>,
+    ) {
+        self.0.lock().insert(
+            id,
+            NodeEntry {
+                kind,
+                origin_scope,
+                location,
+                subscribers,
+                writes: VecDeque::new(),
+            },
+        );
+    }
+
+    /// Remove a node that was dropped from the graph
+    pub(crate) fn unregister(&self, id: usize) {
+        self.0.lock().remove(&id);
+    }
+
+    /// Remember a write to a node
+    pub(crate) fn record_write(&self, id: usize, location: &'static Location<'static>) {
+        let record = WriteRecord {
+            sequence: NEXT_WRITE.fetch_add(1, Ordering::Relaxed),
+            location,
+            scope: current_scope_id(),
+            effect: Effect::current().map(|effect| effect.id),
+        };
+        if let Some(node) = self.0.lock().get_mut(&id) {
+            if node.writes.len() == WRITE_HISTORY {
+                node.writes.pop_front();
+            }
+            node.writes.push_back(record);
+        }
+    }
+
+    fn snapshot(&self) -> ReactiveGraph {
+        let graph = self.0.lock();
+        let mut nodes: Vec<_> = graph
+            .iter()
+            .map(|(id, node)| {
+                let (subscribers, effect_subscribers) = match &node.subscribers {
+                    Some((subscribers, effect_subscribers)) => (
+                        subscribers.read().clone(),
+                        effect_subscribers
+                            .read()
+                            .iter()
+                            .map(|effect| effect.id)
+                            .collect(),
+                    ),
+                    None => Default::default(),
+                };
+                ReactiveNode {
+                    id: *id,
+                    kind: node.kind,
+                    origin_scope: node.origin_scope,
+                    location: node.location,
+                    subscribers,
+                    effect_subscribers,
+                    writes: node.writes.iter().cloned().collect(),
+                }
+            })
+            .collect();
+        nodes.sort_by_key(|node| node.id);
+
+        ReactiveGraph { nodes }
+    }
+}
+
+/// Removes a node from the graph when it is dropped
+pub(crate) struct NodeGuard {
+    pub(crate) graph: Graph,
+    pub(crate) id: usize,
+}
+
+impl Drop for NodeGuard {
+    fn drop(&mut self) {
+        self.graph.unregister(self.id);
+    }
+}
+
+/// Get a snapshot of every signal, selector and effect that is currently alive in the VirtualDom that is running.
+/// Every VirtualDom has its own graph. Use [`reactive_graph_of`] to get the graph of a VirtualDom from outside of it.
+///
+/// The graph is only tracked in debug builds. In release builds the graph is always empty.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// fn App(cx: Scope) -> Element {
+///     let mut count = use_signal(cx, || 0);
+///
+///     render! {
+///         button {
+///             onclick: move |_| {
+///                 count += 1;
+///                 println!("{}", reactive_graph().to_dot());
+///             },
+///             "{count}"
+///         }
+///     }
+/// }
+/// ```
+pub fn reactive_graph() -> ReactiveGraph {
+    consume_context::<Graph>()
+        .map(|graph| graph.snapshot())
+        .unwrap_or_default()
+}
+
+/// Get a snapshot of every signal, selector and effect that is currently alive in a VirtualDom. See [`reactive_graph`].
+pub fn reactive_graph_of(dom: &VirtualDom) -> ReactiveGraph {
+    dom.base_scope()
+        .has_context::<Graph>()
+        .map(|graph| graph.snapshot())
+        .unwrap_or_default()
+}
+
+/// What kind of value a node in the reactive graph is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum ReactiveNodeKind {
+    /// A [`crate::Signal`]
+    Signal,
+    /// A selector. Selectors are signals that are written to by an effect with the same id.
+    Selector,
+    /// An [`Effect`]
+    Effect,
+}
+
+impl fmt::Display for ReactiveNodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReactiveNodeKind::Signal => f.write_str("signal"),
+            ReactiveNodeKind::Selector => f.write_str("selector"),
+            ReactiveNodeKind::Effect => f.write_str("effect"),
+        }
+    }
+}
+
+/// A write to a signal
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct WriteRecord {
+    /// Writes are numbered in the order they happened across every signal
+    pub sequence: u64,
+    /// The location of the code that wrote to the signal
+    #[cfg_attr(feature = "serialize", serde(serialize_with = "serialize_location"))]
+    pub location: &'static Location<'static>,
+    /// The scope that was running when the signal was written to
+    pub scope: Option<ScopeId>,
+    /// The effect or selector that was running when the signal was written to
+    pub effect: Option<usize>,
+}
+
+/// A signal, selector or effect in the reactive graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ReactiveNode {
+    /// The id of the node. Effect subscribers and writes refer to effects and selectors by this id
+    pub id: usize,
+    /// What kind of value the node is
+    pub kind: ReactiveNodeKind,
+    /// The scope that owns the node
+    pub origin_scope: ScopeId,
+    /// The location of the code that created the node
+    #[cfg_attr(feature = "serialize", serde(serialize_with = "serialize_location"))]
+    pub location: &'static Location<'static>,
+    /// The components that rerender when the node is written to
+    pub subscribers: Vec<ScopeId>,
+    /// The ids of the effects and selectors that rerun when the node is written to
+    pub effect_subscribers: Vec<usize>,
+    /// The most recent writes to the node, oldest first
+    pub writes: Vec<WriteRecord>,
+}
+
+/// A snapshot of the reactive graph. See [`reactive_graph`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+pub struct ReactiveGraph {
+    /// Every node in the graph sorted by id
+    pub nodes: Vec<ReactiveNode>,
+}
+
+impl ReactiveGraph {
+    /// Get the node with the given id
+    pub fn node(&self, id: usize) -> Option<&ReactiveNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Get every node that reruns the given scope when it is written to
+    pub fn subscriptions_of(&self, scope: ScopeId) -> impl Iterator<Item = &ReactiveNode> {
+        self.nodes
+            .iter()
+            .filter(move |node| node.subscribers.contains(&scope))
+    }
+
+    /// Render the graph in the [DOT](https://graphviz.org/doc/info/lang.html) format. Components are drawn as boxes, signals as ellipses, selectors as diamonds and effects as hexagons.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph signals {\n");
+        let mut scopes = Vec::new();
+        for node in &self.nodes {
+            let shape = match node.kind {
+                ReactiveNodeKind::Signal => "ellipse",
+                ReactiveNodeKind::Selector => "diamond",
+                ReactiveNodeKind::Effect => "hexagon",
+            };
+            let _ = writeln!(
+                dot,
+                "    node{} [label={}, shape={}];",
+                node.id,
+                dot_string(&format!("{} {}\n{}", node.kind, node.id, node.location)),
+                shape
+            );
+            for effect in &node.effect_subscribers {
+                let _ = writeln!(dot, "    node{} -> node{};", node.id, effect);
+            }
+            for scope in &node.subscribers {
+                if !scopes.contains(scope) {
+                    scopes.push(*scope);
+                }
+                let _ = writeln!(dot, "    node{} -> scope{};", node.id, scope.0);
+            }
+        }
+        for scope in scopes {
+            let _ = writeln!(
+                dot,
+                "    scope{} [label={}, shape=box];",
+                scope.0,
+                dot_string(&format!("{:?}", scope))
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Serialize the graph to JSON
+    #[cfg(feature = "serialize")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("the graph only contains values that can be serialized")
+    }
+}
+
+fn dot_string(value: &str) -> String {
+    format!("{:?}", value)
+}
+
+#[cfg(feature = "serialize")]
+fn serialize_location<S: serde::Serializer>(
+    location: &&'static Location<'static>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(location)
+}
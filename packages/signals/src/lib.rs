@@ -10,7 +10,22 @@ pub use effect::*;
 mod impls;
 mod selector;
 pub use selector::*;
+mod async_selector;
+pub use async_selector::*;
 pub(crate) mod signal;
 pub use signal::*;
 mod dependency;
 pub use dependency::*;
+mod graph;
+pub use graph::{
+    reactive_graph, reactive_graph_of, ReactiveGraph, ReactiveNode, ReactiveNodeKind, WriteRecord,
+};
+mod store;
+pub use store::*;
+
+/// Derive a store for every field of a struct. See [`Store`].
+pub use dioxus_signals_macro::Store;
+
+pub use generational_box::{
+    AnyStorage, BorrowError, BorrowMutError, Storage, SyncStorage, UnsyncStorage,
+};
@@ -1,25 +1,25 @@
-use std::cell::{Ref, RefMut};
-
 use std::rc::Rc;
 
 use dioxus_core::prelude::*;
 use dioxus_core::ScopeId;
 
-use generational_box::{GenerationalBox, Owner, Store};
+use generational_box::{
+    AnyStorage, BorrowError, BorrowMutError, GenerationalBox, Owner, Storage, Store, UnsyncStorage,
+};
 
 use crate::Effect;
 
-fn current_store() -> Store {
+fn current_store<S: AnyStorage>() -> Store<S> {
     match consume_context() {
         Some(rt) => rt,
         None => {
-            let store = Store::default();
+            let store = Store::<S>::new();
             provide_root_context(store).expect("in a virtual dom")
         }
     }
 }
 
-fn current_owner() -> Rc<Owner> {
+fn current_owner<S: AnyStorage>() -> Rc<Owner<S>> {
     match Effect::current() {
         // If we are inside of an effect, we should use the owner of the effect as the owner of the value.
         Some(effect) => {
@@ -37,7 +37,7 @@ fn current_owner() -> Rc<Owner> {
     }
 }
 
-fn owner_in_scope(scope: ScopeId) -> Rc<Owner> {
+fn owner_in_scope<S: AnyStorage>(scope: ScopeId) -> Rc<Owner<S>> {
     match consume_context_from_scope(scope) {
         Some(rt) => rt,
         None => {
@@ -49,31 +49,33 @@ fn owner_in_scope(scope: ScopeId) -> Rc<Owner> {
 
 /// CopyValue is a wrapper around a value to make the value mutable and Copy.
 ///
-/// It is internally backed by [`generational_box::GenerationalBox`].
-pub struct CopyValue<T: 'static> {
-    pub(crate) value: GenerationalBox<T>,
+/// It is internally backed by [`generational_box::GenerationalBox`]. The storage decides which threads can access
+/// the value: the default [`generational_box::UnsyncStorage`] is only accessible from the thread the value was created
+/// on, [`generational_box::SyncStorage`] can be read and written from any thread.
+pub struct CopyValue<T: 'static, S: Storage<T> = UnsyncStorage> {
+    pub(crate) value: GenerationalBox<T, S>,
     origin_scope: ScopeId,
 }
 
 #[cfg(feature = "serde")]
-impl<T: 'static> serde::Serialize for CopyValue<T>
+impl<T: 'static, S: Storage<T>> serde::Serialize for CopyValue<T, S>
 where
     T: serde::Serialize,
 {
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
         self.value.read().serialize(serializer)
     }
 }
 
 #[cfg(feature = "serde")]
-impl<'de, T: 'static> serde::Deserialize<'de> for CopyValue<T>
+impl<'de, T: 'static, S: Storage<T>> serde::Deserialize<'de> for CopyValue<T, S>
 where
     T: serde::Deserialize<'de>,
 {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let value = T::deserialize(deserializer)?;
 
-        Ok(Self::new(value))
+        Ok(Self::new_maybe_sync(value))
     }
 }
 
@@ -82,6 +84,20 @@ impl<T: 'static> CopyValue<T> {
     ///
     /// Once the component this value is created in is dropped, the value will be dropped.
     pub fn new(value: T) -> Self {
+        Self::new_maybe_sync(value)
+    }
+
+    /// Create a new CopyValue. The value will be stored in the given scope. When the specified scope is dropped, the value will be dropped.
+    pub fn new_in_scope(value: T, scope: ScopeId) -> Self {
+        Self::new_maybe_sync_in_scope(value, scope)
+    }
+}
+
+impl<T: 'static, S: Storage<T>> CopyValue<T, S> {
+    /// Create a new CopyValue in any storage. The value will be stored in the current component.
+    ///
+    /// Use `CopyValue::<T, SyncStorage>::new_maybe_sync` to create a value that can be used from other threads.
+    pub fn new_maybe_sync(value: T) -> Self {
         let owner = current_owner();
 
         Self {
@@ -90,8 +106,8 @@ impl<T: 'static> CopyValue<T> {
         }
     }
 
-    /// Create a new CopyValue. The value will be stored in the given scope. When the specified scope is dropped, the value will be dropped.
-    pub fn new_in_scope(value: T, scope: ScopeId) -> Self {
+    /// Create a new CopyValue in any storage. The value will be stored in the given scope. When the specified scope is dropped, the value will be dropped.
+    pub fn new_maybe_sync_in_scope(value: T, scope: ScopeId) -> Self {
         let owner = owner_in_scope(scope);
 
         Self {
@@ -114,52 +130,60 @@ impl<T: 'static> CopyValue<T> {
         self.origin_scope
     }
 
-    /// Try to read the value. If the value has been dropped, this will return None.
-    pub fn try_read(&self) -> Option<Ref<'_, T>> {
+    /// Try to read the value. If the value has been dropped or is borrowed mutably, this will return an error.
+    #[track_caller]
+    pub fn try_read(&self) -> Result<S::Ref<T>, BorrowError> {
         self.value.try_read()
     }
 
     /// Read the value. If the value has been dropped, this will panic.
-    pub fn read(&self) -> Ref<'_, T> {
+    #[track_caller]
+    pub fn read(&self) -> S::Ref<T> {
         self.value.read()
     }
 
-    /// Try to write the value. If the value has been dropped, this will return None.
-    pub fn try_write(&self) -> Option<RefMut<'_, T>> {
+    /// Try to write the value. If the value has been dropped or is already borrowed, this will return an error.
+    #[track_caller]
+    pub fn try_write(&self) -> Result<S::Mut<T>, BorrowMutError> {
         self.value.try_write()
     }
 
     /// Write the value. If the value has been dropped, this will panic.
-    pub fn write(&self) -> RefMut<'_, T> {
+    #[track_caller]
+    pub fn write(&self) -> S::Mut<T> {
         self.value.write()
     }
 
     /// Set the value. If the value has been dropped, this will panic.
+    #[track_caller]
     pub fn set(&mut self, value: T) {
         *self.write() = value;
     }
 
     /// Run a function with a reference to the value. If the value has been dropped, this will panic.
+    #[track_caller]
     pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
         let write = self.read();
         f(&*write)
     }
 
     /// Run a function with a mutable reference to the value. If the value has been dropped, this will panic.
+    #[track_caller]
     pub fn with_mut<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
         let mut write = self.write();
         f(&mut *write)
     }
 }
 
-impl<T: Clone + 'static> CopyValue<T> {
+impl<T: Clone + 'static, S: Storage<T>> CopyValue<T, S> {
     /// Get the value. If the value has been dropped, this will panic.
+    #[track_caller]
     pub fn value(&self) -> T {
         self.read().clone()
     }
 }
 
-impl<T: 'static> PartialEq for CopyValue<T> {
+impl<T: 'static, S: Storage<T>> PartialEq for CopyValue<T, S> {
     fn eq(&self, other: &Self) -> bool {
         self.value.ptr_eq(&other.value)
     }
@@ -1,6 +1,9 @@
+use std::panic::Location;
+
 use dioxus_core::prelude::*;
 
 use crate::dependency::Dependency;
+use crate::graph::ReactiveNodeKind;
 use crate::use_signal;
 use crate::{get_effect_stack, signal::SignalData, CopyValue, Effect, ReadOnlySignal, Signal};
 
@@ -21,11 +24,13 @@ use crate::{get_effect_stack, signal::SignalData, CopyValue, Effect, ReadOnlySig
 ///     render! { "{double}" }
 /// }
 /// ```
+#[track_caller]
 pub fn use_selector<R: PartialEq>(
     cx: &ScopeState,
     f: impl FnMut() -> R + 'static,
 ) -> ReadOnlySignal<R> {
-    *cx.use_hook(|| selector(f))
+    let caller = Location::caller();
+    *cx.use_hook(|| selector_with_caller(f, caller))
 }
 
 /// Creates a new Selector with some local dependencies. The selector will be run immediately and whenever any signal it reads or any dependencies it tracks changes
@@ -44,6 +49,7 @@ pub fn use_selector<R: PartialEq>(
 ///     render! { "{double}" }
 /// }
 /// ```
+#[track_caller]
 pub fn use_selector_with_dependencies<R: PartialEq, D: Dependency>(
     cx: &ScopeState,
     dependencies: D,
@@ -52,12 +58,16 @@ pub fn use_selector_with_dependencies<R: PartialEq, D: Dependency>(
 where
     D::Out: 'static,
 {
+    let caller = Location::caller();
     let dependencies_signal = use_signal(cx, || dependencies.out());
     let selector = *cx.use_hook(|| {
-        selector(move || {
-            let deref = &*dependencies_signal.read();
-            f(deref.clone())
-        })
+        selector_with_caller(
+            move || {
+                let deref = &*dependencies_signal.read();
+                f(deref.clone())
+            },
+            caller,
+        )
     });
     let changed = { dependencies.changed(&*dependencies_signal.read()) };
     if changed {
@@ -69,27 +79,35 @@ where
 /// Creates a new Selector. The selector will be run immediately and whenever any signal it reads changes.
 ///
 /// Selectors can be used to efficiently compute derived data from signals.
-pub fn selector<R: PartialEq>(mut f: impl FnMut() -> R + 'static) -> ReadOnlySignal<R> {
+#[track_caller]
+pub fn selector<R: PartialEq>(f: impl FnMut() -> R + 'static) -> ReadOnlySignal<R> {
+    selector_with_caller(f, Location::caller())
+}
+
+fn selector_with_caller<R: PartialEq>(
+    mut f: impl FnMut() -> R + 'static,
+    caller: &'static Location<'static>,
+) -> ReadOnlySignal<R> {
     let state = Signal::<R> {
         inner: CopyValue::invalid(),
     };
-    let effect = Effect {
-        source: current_scope_id().expect("in a virtual dom"),
-        callback: CopyValue::invalid(),
-    };
+    let effect = Effect::with_callback(CopyValue::invalid());
 
     {
         get_effect_stack().effects.borrow_mut().push(effect);
     }
-    state.inner.value.set(SignalData {
-        subscribers: Default::default(),
-        effect_subscribers: Default::default(),
-        update_any: schedule_update_any().expect("in a virtual dom"),
-        value: f(),
-    });
+    let value = f();
     {
         get_effect_stack().effects.borrow_mut().pop();
     }
+    // The selector shares its id with the effect that writes to it
+    state.inner.value.set(SignalData::with_node(
+        value,
+        effect.id,
+        ReactiveNodeKind::Selector,
+        effect.source,
+        caller,
+    ));
 
     effect.callback.value.set(Box::new(move || {
         let value = f();
@@ -1,6 +1,8 @@
 use std::{
-    cell::{Ref, RefCell, RefMut},
+    cell::RefCell,
+    fmt::Debug,
     ops::{Deref, DerefMut},
+    panic::Location,
     rc::Rc,
     sync::Arc,
 };
@@ -9,8 +11,11 @@ use dioxus_core::{
     prelude::{current_scope_id, has_context, provide_context, schedule_update_any},
     ScopeId, ScopeState,
 };
+use generational_box::{Storage, SyncStorage, UnsyncStorage};
+use parking_lot::RwLock;
 
-use crate::{CopyValue, Effect};
+use crate::graph::{self, Graph, ReactiveNodeKind};
+use crate::{CopyValue, Effect, EffectSubscriber};
 
 /// Creates a new Signal. Signals are a Copy state management solution with automatic dependency tracking.
 ///
@@ -43,22 +48,61 @@ use crate::{CopyValue, Effect};
 ///     }
 /// }
 /// ```
+#[track_caller]
 pub fn use_signal<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> T) -> Signal<T> {
-    *cx.use_hook(|| Signal::new(f()))
+    let caller = Location::caller();
+    *cx.use_hook(|| Signal::new_with_caller(f(), caller))
 }
 
+/// Creates a new Signal that can be read and written from any thread.
+///
+/// Writes from other threads mark the subscribed components as dirty through the VirtualDom's scheduler. Effects that
+/// read the signal are rerun on the thread of the VirtualDom.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// fn App(cx: Scope) -> Element {
+///     let count = use_signal_sync(cx, || 0);
+///
+///     cx.use_hook(|| {
+///         std::thread::spawn(move || loop {
+///             std::thread::sleep(std::time::Duration::from_secs(1));
+///             *count.write() += 1;
+///         })
+///     });
+///
+///     render! { "{count}" }
+/// }
+/// ```
+#[track_caller]
+pub fn use_signal_sync<T: Send + Sync + 'static>(
+    cx: &ScopeState,
+    f: impl FnOnce() -> T,
+) -> Signal<T, SyncStorage> {
+    let caller = Location::caller();
+    *cx.use_hook(|| Signal::new_with_caller(f(), caller))
+}
+
+/// The components subscribed to a signal
+pub(crate) type Subscribers = Arc<RwLock<Vec<ScopeId>>>;
+
+/// The effects subscribed to a signal
+pub(crate) type EffectSubscribers = Arc<RwLock<Vec<EffectSubscriber>>>;
+
 #[derive(Clone)]
 struct Unsubscriber {
     scope: ScopeId,
     subscribers: UnsubscriberArray,
 }
 
-type UnsubscriberArray = Rc<RefCell<Vec<Rc<RefCell<Vec<ScopeId>>>>>>;
+type UnsubscriberArray = Rc<RefCell<Vec<Subscribers>>>;
 
 impl Drop for Unsubscriber {
     fn drop(&mut self) {
         for subscribers in self.subscribers.borrow().iter() {
-            subscribers.borrow_mut().retain(|s| *s != self.scope);
+            subscribers.write().retain(|s| *s != self.scope);
         }
     }
 }
@@ -76,13 +120,116 @@ fn current_unsubscriber() -> Unsubscriber {
     }
 }
 
-pub(crate) struct SignalData<T> {
-    pub(crate) subscribers: Rc<RefCell<Vec<ScopeId>>>,
-    pub(crate) effect_subscribers: Rc<RefCell<Vec<Effect>>>,
-    pub(crate) update_any: Arc<dyn Fn(ScopeId)>,
+/// Subscribe the effect or component that is currently running to a value
+pub(crate) fn subscribe_current(
+    subscribers: &Subscribers,
+    effect_subscribers: &EffectSubscribers,
+    source: &dyn Debug,
+) {
+    if let Some(effect) = Effect::current() {
+        if let Some(effect) = effect.subscriber() {
+            let mut effect_subscribers = effect_subscribers.write();
+            if !effect_subscribers.contains(&effect) {
+                effect_subscribers.push(effect);
+            }
+        }
+    } else if let Some(current_scope_id) = current_scope_id() {
+        // only subscribe if the vdom is rendering
+        if dioxus_core::vdom_is_rendering() {
+            tracing::trace!("{:?} subscribed to {:?}", source, current_scope_id);
+            let mut subscribers_mut = subscribers.write();
+            if !subscribers_mut.contains(&current_scope_id) {
+                subscribers_mut.push(current_scope_id);
+                drop(subscribers_mut);
+                let unsubscriber = current_unsubscriber();
+                unsubscriber
+                    .subscribers
+                    .borrow_mut()
+                    .push(subscribers.clone());
+            }
+        }
+    }
+}
+
+/// Mark every component subscribed to a value as dirty and rerun every effect subscribed to it
+pub(crate) fn update_subscribers(
+    subscribers: &Subscribers,
+    effect_subscribers: &EffectSubscribers,
+    update_any: &dyn Fn(ScopeId),
+    source: &dyn Debug,
+) {
+    for &scope_id in &*subscribers.read() {
+        tracing::trace!("Write on {:?} triggered update on {:?}", source, scope_id);
+        update_any(scope_id);
+    }
+
+    let effects = std::mem::take(&mut *effect_subscribers.write());
+    for effect in effects {
+        tracing::trace!("Write on {:?} triggered effect {:?}", source, effect);
+        effect.rerun();
+    }
+}
+
+/// The data stored for tracking in a signal.
+pub struct SignalData<T> {
+    // The id of the signal in the reactive graph
+    pub(crate) id: usize,
+    pub(crate) subscribers: Subscribers,
+    pub(crate) effect_subscribers: EffectSubscribers,
+    pub(crate) update_any: Arc<dyn Fn(ScopeId) + Send + Sync>,
+    // The reactive graph the signal is registered in. This is only set in debug builds.
+    pub(crate) graph: Option<Graph>,
     pub(crate) value: T,
 }
 
+impl<T> SignalData<T> {
+    fn new(value: T, origin_scope: ScopeId, caller: &'static Location<'static>) -> Self {
+        Self::with_node(
+            value,
+            graph::next_node_id(),
+            ReactiveNodeKind::Signal,
+            origin_scope,
+            caller,
+        )
+    }
+
+    /// Create the data for a signal and add it to the reactive graph
+    pub(crate) fn with_node(
+        value: T,
+        id: usize,
+        kind: ReactiveNodeKind,
+        origin_scope: ScopeId,
+        caller: &'static Location<'static>,
+    ) -> Self {
+        let data = Self {
+            id,
+            subscribers: Default::default(),
+            effect_subscribers: Default::default(),
+            update_any: schedule_update_any().expect("in a virtual dom"),
+            graph: Graph::current(),
+            value,
+        };
+        if let Some(graph) = &data.graph {
+            graph.register(
+                id,
+                kind,
+                origin_scope,
+                caller,
+                Some((data.subscribers.clone(), data.effect_subscribers.clone())),
+            );
+        }
+        data
+    }
+}
+
+impl<T> Drop for SignalData<T> {
+    fn drop(&mut self) {
+        if let Some(graph) = &self.graph {
+            graph.unregister(self.id);
+        }
+    }
+}
+
 /// Creates a new Signal. Signals are a Copy state management solution with automatic dependency tracking.
 ///
 /// ```rust
@@ -115,49 +262,67 @@ pub(crate) struct SignalData<T> {
 ///     }
 /// }
 /// ```
-pub struct Signal<T: 'static> {
-    pub(crate) inner: CopyValue<SignalData<T>>,
+///
+/// Signals are stored in [`UnsyncStorage`] by default and can only be used on the thread they were created on. Use
+/// [`use_signal_sync`] or [`Signal::new_maybe_sync`] to create a signal in [`SyncStorage`] that other threads can
+/// write to.
+pub struct Signal<T: 'static, S: Storage<SignalData<T>> = UnsyncStorage> {
+    pub(crate) inner: CopyValue<SignalData<T>, S>,
 }
 
 #[cfg(feature = "serde")]
-impl<T: serde::Serialize + 'static> serde::Serialize for Signal<T> {
-    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+impl<T: serde::Serialize + 'static, S: Storage<SignalData<T>>> serde::Serialize for Signal<T, S> {
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
         self.read().serialize(serializer)
     }
 }
 
 #[cfg(feature = "serde")]
-impl<'de, T: serde::Deserialize<'de> + 'static> serde::Deserialize<'de> for Signal<T> {
+impl<'de, T: serde::Deserialize<'de> + 'static, S: Storage<SignalData<T>>> serde::Deserialize<'de>
+    for Signal<T, S>
+{
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        Ok(Self::new(T::deserialize(deserializer)?))
+        Ok(Self::new_maybe_sync(T::deserialize(deserializer)?))
     }
 }
 
 impl<T: 'static> Signal<T> {
     /// Creates a new Signal. Signals are a Copy state management solution with automatic dependency tracking.
+    #[track_caller]
     pub fn new(value: T) -> Self {
-        Self {
-            inner: CopyValue::new(SignalData {
-                subscribers: Default::default(),
-                effect_subscribers: Default::default(),
-                update_any: schedule_update_any().expect("in a virtual dom"),
-                value,
-            }),
-        }
+        Self::new_maybe_sync(value)
     }
 
     /// Create a new signal with a custom owner scope. The signal will be dropped when the owner scope is dropped instead of the current scope.
+    #[track_caller]
     pub fn new_in_scope(value: T, owner: ScopeId) -> Self {
+        Self::new_maybe_sync_in_scope(value, owner)
+    }
+}
+
+impl<T: 'static, S: Storage<SignalData<T>>> Signal<T, S> {
+    /// Creates a new Signal in any storage.
+    ///
+    /// Use `Signal::<T, SyncStorage>::new_maybe_sync` to create a signal that can be written from other threads.
+    #[track_caller]
+    pub fn new_maybe_sync(value: T) -> Self {
+        Self::new_with_caller(value, Location::caller())
+    }
+
+    /// Create a new signal in the current scope that was created by the code at `caller`
+    pub(crate) fn new_with_caller(value: T, caller: &'static Location<'static>) -> Self {
+        let origin_scope = current_scope_id().expect("in a virtual dom");
+        Self {
+            inner: CopyValue::new_maybe_sync(SignalData::new(value, origin_scope, caller)),
+        }
+    }
+
+    /// Create a new signal in any storage with a custom owner scope. The signal will be dropped when the owner scope is dropped instead of the current scope.
+    #[track_caller]
+    pub fn new_maybe_sync_in_scope(value: T, owner: ScopeId) -> Self {
+        let data = SignalData::new(value, owner, Location::caller());
         Self {
-            inner: CopyValue::new_in_scope(
-                SignalData {
-                    subscribers: Default::default(),
-                    effect_subscribers: Default::default(),
-                    update_any: schedule_update_any().expect("in a virtual dom"),
-                    value,
-                },
-                owner,
-            ),
+            inner: CopyValue::new_maybe_sync_in_scope(data, owner),
         }
     }
 
@@ -168,38 +333,26 @@ impl<T: 'static> Signal<T> {
 
     /// Get the current value of the signal. This will subscribe the current scope to the signal.
     /// If the signal has been dropped, this will panic.
-    pub fn read(&self) -> Ref<T> {
+    #[track_caller]
+    pub fn read(&self) -> S::Ref<T> {
         let inner = self.inner.read();
-        if let Some(effect) = Effect::current() {
-            let mut effect_subscribers = inner.effect_subscribers.borrow_mut();
-            if !effect_subscribers.contains(&effect) {
-                effect_subscribers.push(effect);
-            }
-        } else if let Some(current_scope_id) = current_scope_id() {
-            // only subscribe if the vdom is rendering
-            if dioxus_core::vdom_is_rendering() {
-                tracing::trace!(
-                    "{:?} subscribed to {:?}",
-                    self.inner.value,
-                    current_scope_id
-                );
-                let mut subscribers = inner.subscribers.borrow_mut();
-                if !subscribers.contains(&current_scope_id) {
-                    subscribers.push(current_scope_id);
-                    drop(subscribers);
-                    let unsubscriber = current_unsubscriber();
-                    inner.subscribers.borrow_mut().push(unsubscriber.scope);
-                }
-            }
-        }
-        Ref::map(inner, |v| &v.value)
+        subscribe_current(
+            &inner.subscribers,
+            &inner.effect_subscribers,
+            &self.inner.value,
+        );
+        S::map(inner, |v| &v.value)
     }
 
     /// Get a mutable reference to the signal's value.
     /// If the signal has been dropped, this will panic.
-    pub fn write(&self) -> Write<'_, T> {
+    #[track_caller]
+    pub fn write(&self) -> Write<T, T, S> {
         let inner = self.inner.write();
-        let borrow = RefMut::map(inner, |v| &mut v.value);
+        if let Some(graph) = &inner.graph {
+            graph.record_write(inner.id, Location::caller());
+        }
+        let borrow = S::map_mut(inner, |v| &mut v.value);
         Write {
             write: borrow,
             signal: SignalSubscriberDrop { signal: *self },
@@ -207,40 +360,31 @@ impl<T: 'static> Signal<T> {
     }
 
     fn update_subscribers(&self) {
-        {
+        let (subscribers, effect_subscribers, update_any) = {
             let inner = self.inner.read();
-            for &scope_id in &*inner.subscribers.borrow() {
-                tracing::trace!(
-                    "Write on {:?} triggered update on {:?}",
-                    self.inner.value,
-                    scope_id
-                );
-                (inner.update_any)(scope_id);
-            }
-        }
-
-        let subscribers = {
-            let self_read = self.inner.read();
-            let mut effects = self_read.effect_subscribers.borrow_mut();
-            std::mem::take(&mut *effects)
+            (
+                inner.subscribers.clone(),
+                inner.effect_subscribers.clone(),
+                inner.update_any.clone(),
+            )
         };
-        for effect in subscribers {
-            tracing::trace!(
-                "Write on {:?} triggered effect {:?}",
-                self.inner.value,
-                effect
-            );
-            effect.try_run();
-        }
+        update_subscribers(
+            &subscribers,
+            &effect_subscribers,
+            &*update_any,
+            &self.inner.value,
+        );
     }
 
     /// Set the value of the signal. This will trigger an update on all subscribers.
+    #[track_caller]
     pub fn set(&self, value: T) {
         *self.write() = value;
     }
 
     /// Run a closure with a reference to the signal's value.
     /// If the signal has been dropped, this will panic.
+    #[track_caller]
     pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
         let write = self.read();
         f(&*write)
@@ -248,48 +392,50 @@ impl<T: 'static> Signal<T> {
 
     /// Run a closure with a mutable reference to the signal's value.
     /// If the signal has been dropped, this will panic.
+    #[track_caller]
     pub fn with_mut<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
         let mut write = self.write();
         f(&mut *write)
     }
 }
 
-impl<T: Clone + 'static> Signal<T> {
+impl<T: Clone + 'static, S: Storage<SignalData<T>>> Signal<T, S> {
     /// Get the current value of the signal. This will subscribe the current scope to the signal.
     /// If the signal has been dropped, this will panic.
+    #[track_caller]
     pub fn value(&self) -> T {
         self.read().clone()
     }
 }
 
-impl<T: 'static> PartialEq for Signal<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> PartialEq for Signal<T, S> {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
 }
 
-struct SignalSubscriberDrop<T: 'static> {
-    signal: Signal<T>,
+struct SignalSubscriberDrop<T: 'static, S: Storage<SignalData<T>>> {
+    signal: Signal<T, S>,
 }
 
-impl<T: 'static> Drop for SignalSubscriberDrop<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> Drop for SignalSubscriberDrop<T, S> {
     fn drop(&mut self) {
         self.signal.update_subscribers();
     }
 }
 
 /// A mutable reference to a signal's value.
-pub struct Write<'a, T: 'static, I: 'static = T> {
-    write: RefMut<'a, T>,
-    signal: SignalSubscriberDrop<I>,
+pub struct Write<T: 'static, I: 'static = T, S: Storage<SignalData<I>> = UnsyncStorage> {
+    write: S::Mut<T>,
+    signal: SignalSubscriberDrop<I, S>,
 }
 
-impl<'a, T: 'static, I: 'static> Write<'a, T, I> {
+impl<T: 'static, I: 'static, S: Storage<SignalData<I>>> Write<T, I, S> {
     /// Map the mutable reference to the signal's value to a new type.
-    pub fn map<O>(myself: Self, f: impl FnOnce(&mut T) -> &mut O) -> Write<'a, O, I> {
+    pub fn map<O>(myself: Self, f: impl FnOnce(&mut T) -> &mut O) -> Write<O, I, S> {
         let Self { write, signal } = myself;
         Write {
-            write: RefMut::map(write, f),
+            write: S::map_mut(write, f),
             signal,
         }
     }
@@ -298,14 +444,14 @@ impl<'a, T: 'static, I: 'static> Write<'a, T, I> {
     pub fn filter_map<O>(
         myself: Self,
         f: impl FnOnce(&mut T) -> Option<&mut O>,
-    ) -> Option<Write<'a, O, I>> {
+    ) -> Option<Write<O, I, S>> {
         let Self { write, signal } = myself;
-        let write = RefMut::filter_map(write, f).ok();
+        let write = S::try_map_mut(write, f);
         write.map(|write| Write { write, signal })
     }
 }
 
-impl<'a, T: 'static, I: 'static> Deref for Write<'a, T, I> {
+impl<T: 'static, I: 'static, S: Storage<SignalData<I>>> Deref for Write<T, I, S> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -313,20 +459,20 @@ impl<'a, T: 'static, I: 'static> Deref for Write<'a, T, I> {
     }
 }
 
-impl<T, I> DerefMut for Write<'_, T, I> {
+impl<T: 'static, I: 'static, S: Storage<SignalData<I>>> DerefMut for Write<T, I, S> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.write
     }
 }
 
 /// A signal that can only be read from.
-pub struct ReadOnlySignal<T: 'static> {
-    inner: Signal<T>,
+pub struct ReadOnlySignal<T: 'static, S: Storage<SignalData<T>> = UnsyncStorage> {
+    inner: Signal<T, S>,
 }
 
-impl<T: 'static> ReadOnlySignal<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> ReadOnlySignal<T, S> {
     /// Create a new read-only signal.
-    pub fn new(signal: Signal<T>) -> Self {
+    pub fn new(signal: Signal<T, S>) -> Self {
         Self { inner: signal }
     }
 
@@ -336,24 +482,27 @@ impl<T: 'static> ReadOnlySignal<T> {
     }
 
     /// Get the current value of the signal. This will subscribe the current scope to the signal.
-    pub fn read(&self) -> Ref<T> {
+    #[track_caller]
+    pub fn read(&self) -> S::Ref<T> {
         self.inner.read()
     }
 
     /// Run a closure with a reference to the signal's value.
+    #[track_caller]
     pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
         self.inner.with(f)
     }
 }
 
-impl<T: Clone + 'static> ReadOnlySignal<T> {
+impl<T: Clone + 'static, S: Storage<SignalData<T>>> ReadOnlySignal<T, S> {
     /// Get the current value of the signal. This will subscribe the current scope to the signal.
+    #[track_caller]
     pub fn value(&self) -> T {
         self.read().clone()
     }
 }
 
-impl<T: 'static> PartialEq for ReadOnlySignal<T> {
+impl<T: 'static, S: Storage<SignalData<T>>> PartialEq for ReadOnlySignal<T, S> {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
@@ -0,0 +1,492 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Display},
+    hash::Hash,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    sync::Arc,
+};
+
+use dioxus_core::{prelude::schedule_update_any, ScopeId, ScopeState};
+use generational_box::{AnyStorage, UnsyncStorage};
+
+use crate::{
+    signal::{subscribe_current, update_subscribers, EffectSubscribers, Subscribers},
+    CopyValue,
+};
+
+/// Creates a new Store. Stores are like signals, but every field, index and key of the value can be subscribed to
+/// separately.
+///
+/// ```rust
+/// use dioxus::prelude::*;
+/// use dioxus_signals::*;
+///
+/// #[derive(Store)]
+/// struct Todo {
+///     title: String,
+///     done: bool,
+/// }
+///
+/// fn App(cx: Scope) -> Element {
+///     let todos = use_store(cx, || vec![Todo { title: "Write docs".into(), done: false }]);
+///
+///     render! {
+///         for todo in todos.iter() {
+///             TodoItem { todo: todo }
+///         }
+///     }
+/// }
+///
+/// #[component]
+/// fn TodoItem(cx: Scope, todo: Store<Todo>) -> Element {
+///     // This component only reruns when the title of this todo changes
+///     let title = todo.title();
+///
+///     render! { "{title}" }
+/// }
+/// ```
+pub fn use_store<T: 'static>(cx: &ScopeState, f: impl FnOnce() -> T) -> Store<T> {
+    *cx.use_hook(|| Store::new(f()))
+}
+
+/// Get a child value from a parent value, or `None` if the child doesn't exist
+type Projection = Rc<dyn Fn(&(dyn Any + 'static)) -> Option<&(dyn Any + 'static)>>;
+type ProjectionMut = Rc<dyn Fn(&mut (dyn Any + 'static)) -> Option<&mut (dyn Any + 'static)>>;
+
+fn projection(
+    f: impl Fn(&(dyn Any + 'static)) -> Option<&(dyn Any + 'static)> + 'static,
+) -> Projection {
+    Rc::new(f)
+}
+
+fn projection_mut(
+    f: impl Fn(&mut (dyn Any + 'static)) -> Option<&mut (dyn Any + 'static)> + 'static,
+) -> ProjectionMut {
+    Rc::new(f)
+}
+
+pub(crate) struct StoreData {
+    value: Box<dyn Any>,
+    // The paths into the value that have been projected. The first node is the root value.
+    nodes: RefCell<Vec<StoreNode>>,
+    update_any: Arc<dyn Fn(ScopeId)>,
+}
+
+struct StoreNode {
+    parent: Option<usize>,
+    children: Vec<(PathSegment, usize)>,
+    project: Projection,
+    project_mut: ProjectionMut,
+    subscribers: Subscribers,
+    effect_subscribers: EffectSubscribers,
+}
+
+impl StoreNode {
+    fn new(parent: Option<usize>, project: Projection, project_mut: ProjectionMut) -> Self {
+        Self {
+            parent,
+            children: Vec::new(),
+            project,
+            project_mut,
+            subscribers: Default::default(),
+            effect_subscribers: Default::default(),
+        }
+    }
+}
+
+/// One step of the path from the root of a store to a child
+enum PathSegment {
+    Field(&'static str),
+    Index(usize),
+    Key(Box<dyn StoreKey>),
+}
+
+impl PathSegment {
+    fn matches(&self, other: &PathSegment) -> bool {
+        match (self, other) {
+            (PathSegment::Field(a), PathSegment::Field(b)) => a == b,
+            (PathSegment::Index(a), PathSegment::Index(b)) => a == b,
+            (PathSegment::Key(a), PathSegment::Key(b)) => a.eq_key(b.as_any()),
+            _ => false,
+        }
+    }
+}
+
+trait StoreKey {
+    fn as_any(&self) -> &dyn Any;
+    fn eq_key(&self, other: &dyn Any) -> bool;
+}
+
+impl<K: PartialEq + 'static> StoreKey for K {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_key(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<K>() == Some(self)
+    }
+}
+
+/// A Copy state management solution that tracks every path into its value separately.
+///
+/// Reading a store subscribes to that path. Writing to a store reruns the subscribers of that path, every path it
+/// is a part of and every path inside of it. Writing to `todos[2].title` reruns components that read `todos[2].title`,
+/// `todos[2]` or `todos`, but not components that only read `todos[3].title`.
+///
+/// Use `#[derive(Store)]` on a struct to get a store for each of its fields.
+pub struct Store<T: 'static> {
+    root: CopyValue<StoreData>,
+    node: usize,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: 'static> Store<T> {
+    /// Creates a new Store. The store will be dropped when the current component is dropped.
+    pub fn new(value: T) -> Self {
+        Self::from_root(CopyValue::new(Self::data(value)))
+    }
+
+    /// Create a new store with a custom owner scope. The store will be dropped when the owner scope is dropped instead of the current scope.
+    pub fn new_in_scope(value: T, owner: ScopeId) -> Self {
+        Self::from_root(CopyValue::new_in_scope(Self::data(value), owner))
+    }
+
+    fn data(value: T) -> StoreData {
+        let root = StoreNode::new(
+            None,
+            projection(|value| Some(value)),
+            projection_mut(|value| Some(value)),
+        );
+        StoreData {
+            value: Box::new(value),
+            nodes: RefCell::new(vec![root]),
+            update_any: schedule_update_any().expect("in a virtual dom"),
+        }
+    }
+
+    fn from_root(root: CopyValue<StoreData>) -> Self {
+        Self {
+            root,
+            node: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Get the scope the store was created in.
+    pub fn origin_scope(&self) -> ScopeId {
+        self.root.origin_scope()
+    }
+
+    /// Get a store for one field of this value. This is used by `#[derive(Store)]`.
+    pub fn field<F: 'static>(
+        &self,
+        name: &'static str,
+        get: fn(&T) -> &F,
+        get_mut: fn(&mut T) -> &mut F,
+    ) -> Store<F> {
+        self.child(
+            PathSegment::Field(name),
+            move |value: &T| Some(get(value)),
+            move |value: &mut T| Some(get_mut(value)),
+        )
+    }
+
+    fn child<C: 'static>(
+        &self,
+        segment: PathSegment,
+        get: impl Fn(&T) -> Option<&C> + 'static,
+        get_mut: impl Fn(&mut T) -> Option<&mut C> + 'static,
+    ) -> Store<C> {
+        let root = self.root.read();
+        let mut nodes = root.nodes.borrow_mut();
+        let existing = nodes[self.node]
+            .children
+            .iter()
+            .find(|(other, _)| other.matches(&segment))
+            .map(|(_, node)| *node);
+
+        let node = match existing {
+            Some(node) => node,
+            None => {
+                let project = projection(move |value| {
+                    get(value.downcast_ref()?).map(|child| child as &(dyn Any + 'static))
+                });
+                let project_mut = projection_mut(move |value| {
+                    get_mut(value.downcast_mut()?).map(|child| child as &mut (dyn Any + 'static))
+                });
+                let node = nodes.len();
+                nodes.push(StoreNode::new(Some(self.node), project, project_mut));
+                nodes[self.node].children.push((segment, node));
+                node
+            }
+        };
+
+        Store {
+            root: self.root,
+            node,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The projections from the root value to this path, starting at the root
+    fn path(&self) -> Vec<(Projection, ProjectionMut)> {
+        let root = self.root.read();
+        let nodes = root.nodes.borrow();
+        let mut path = Vec::new();
+        let mut node = Some(self.node);
+        while let Some(idx) = node {
+            path.push((nodes[idx].project.clone(), nodes[idx].project_mut.clone()));
+            node = nodes[idx].parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Get the current value of the store if this path still exists. This will subscribe the current scope to the path.
+    /// If the store has been dropped, this will panic.
+    #[track_caller]
+    pub fn try_read(&self) -> Option<<UnsyncStorage as AnyStorage>::Ref<T>> {
+        let path = self.path();
+        let root = self.root.read();
+        {
+            let nodes = root.nodes.borrow();
+            let node = &nodes[self.node];
+            subscribe_current(
+                &node.subscribers,
+                &node.effect_subscribers,
+                &self.root.value,
+            );
+        }
+        UnsyncStorage::try_map(root, |root| {
+            let mut value = &*root.value;
+            for (project, _) in &path {
+                value = project(value)?;
+            }
+            value.downcast_ref()
+        })
+    }
+
+    /// Get the current value of the store. This will subscribe the current scope to the path.
+    /// If the store has been dropped or the path no longer exists, this will panic.
+    #[track_caller]
+    pub fn read(&self) -> <UnsyncStorage as AnyStorage>::Ref<T> {
+        self.try_read()
+            .expect("the path of the store no longer exists")
+    }
+
+    /// Get a mutable reference to the value of the store if this path still exists.
+    /// If the store has been dropped, this will panic.
+    #[track_caller]
+    pub fn try_write(&self) -> Option<StoreWrite<T>> {
+        let path = self.path();
+        let write = UnsyncStorage::try_map_mut(self.root.write(), |root| {
+            let mut value = &mut *root.value;
+            for (_, project_mut) in &path {
+                value = project_mut(value)?;
+            }
+            value.downcast_mut()
+        })?;
+        Some(StoreWrite {
+            write,
+            store: StoreSubscriberDrop { store: *self },
+        })
+    }
+
+    /// Get a mutable reference to the value of the store.
+    /// If the store has been dropped or the path no longer exists, this will panic.
+    #[track_caller]
+    pub fn write(&self) -> StoreWrite<T> {
+        self.try_write()
+            .expect("the path of the store no longer exists")
+    }
+
+    /// Rerun everything subscribed to this path, a path it is part of, or a path inside of it
+    fn update_subscribers(&self) {
+        let (targets, update_any) = {
+            let root = self.root.read();
+            let nodes = root.nodes.borrow();
+
+            let mut targets = Vec::new();
+            let mut ancestor = nodes[self.node].parent;
+            while let Some(node) = ancestor {
+                targets.push(node);
+                ancestor = nodes[node].parent;
+            }
+            let mut descendants = vec![self.node];
+            while let Some(node) = descendants.pop() {
+                targets.push(node);
+                descendants.extend(nodes[node].children.iter().map(|(_, child)| *child));
+            }
+
+            let targets: Vec<_> = targets
+                .into_iter()
+                .map(|node| {
+                    (
+                        nodes[node].subscribers.clone(),
+                        nodes[node].effect_subscribers.clone(),
+                    )
+                })
+                .collect();
+            (targets, root.update_any.clone())
+        };
+
+        for (subscribers, effect_subscribers) in targets {
+            update_subscribers(
+                &subscribers,
+                &effect_subscribers,
+                &*update_any,
+                &self.root.value,
+            );
+        }
+    }
+
+    /// Set the value of the store. This will trigger an update on all subscribers of this path.
+    pub fn set(&self, value: T) {
+        *self.write() = value;
+    }
+
+    /// Run a closure with a reference to the store's value.
+    /// If the store has been dropped, this will panic.
+    pub fn with<O>(&self, f: impl FnOnce(&T) -> O) -> O {
+        let read = self.read();
+        f(&*read)
+    }
+
+    /// Run a closure with a mutable reference to the store's value.
+    /// If the store has been dropped, this will panic.
+    pub fn with_mut<O>(&self, f: impl FnOnce(&mut T) -> O) -> O {
+        let mut write = self.write();
+        f(&mut *write)
+    }
+}
+
+impl<T: Clone + 'static> Store<T> {
+    /// Get the current value of the store. This will subscribe the current scope to the path.
+    /// If the store has been dropped, this will panic.
+    pub fn value(&self) -> T {
+        self.read().clone()
+    }
+}
+
+impl<T: 'static> Store<Vec<T>> {
+    /// Get a store for the element at an index. Reading it after the vector shrinks past the index will panic.
+    pub fn index(&self, index: usize) -> Store<T> {
+        self.child(
+            PathSegment::Index(index),
+            move |value: &Vec<T>| value.get(index),
+            move |value: &mut Vec<T>| value.get_mut(index),
+        )
+    }
+
+    /// Get the length of the vector. This will subscribe the current scope to the whole vector.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns true if the vector is empty. This will subscribe the current scope to the whole vector.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Iterate over a store for every element of the vector. This will subscribe the current scope to the whole vector.
+    pub fn iter(&self) -> impl Iterator<Item = Store<T>> {
+        let store = *self;
+        (0..self.len()).map(move |index| store.index(index))
+    }
+
+    /// Pushes a new value to the end of the vector.
+    pub fn push(&self, value: T) {
+        self.with_mut(|v| v.push(value))
+    }
+
+    /// Removes the value at the given index.
+    pub fn remove(&self, index: usize) -> T {
+        self.with_mut(|v| v.remove(index))
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, V: 'static> Store<HashMap<K, V>> {
+    /// Get a store for the value of a key. Reading it while the key is missing will panic.
+    pub fn get(&self, key: K) -> Store<V> {
+        let get_key = key.clone();
+        let get_mut_key = key.clone();
+        self.child(
+            PathSegment::Key(Box::new(key)),
+            move |value: &HashMap<K, V>| value.get(&get_key),
+            move |value: &mut HashMap<K, V>| value.get_mut(&get_mut_key),
+        )
+    }
+}
+
+impl<K: Ord + Clone + 'static, V: 'static> Store<BTreeMap<K, V>> {
+    /// Get a store for the value of a key. Reading it while the key is missing will panic.
+    pub fn get(&self, key: K) -> Store<V> {
+        let get_key = key.clone();
+        let get_mut_key = key.clone();
+        self.child(
+            PathSegment::Key(Box::new(key)),
+            move |value: &BTreeMap<K, V>| value.get(&get_key),
+            move |value: &mut BTreeMap<K, V>| value.get_mut(&get_mut_key),
+        )
+    }
+}
+
+impl<T: 'static> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Store<T> {}
+
+impl<T: 'static> PartialEq for Store<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && self.node == other.node
+    }
+}
+
+impl<T: Display + 'static> Display for Store<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|v| Display::fmt(v, f))
+    }
+}
+
+impl<T: Debug + 'static> Debug for Store<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.with(|v| Debug::fmt(v, f))
+    }
+}
+
+struct StoreSubscriberDrop<T: 'static> {
+    store: Store<T>,
+}
+
+impl<T: 'static> Drop for StoreSubscriberDrop<T> {
+    fn drop(&mut self) {
+        self.store.update_subscribers();
+    }
+}
+
+/// A mutable reference to the value of a store.
+pub struct StoreWrite<T: 'static> {
+    write: <UnsyncStorage as AnyStorage>::Mut<T>,
+    store: StoreSubscriberDrop<T>,
+}
+
+impl<T: 'static> Deref for StoreWrite<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.write
+    }
+}
+
+impl<T> DerefMut for StoreWrite<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.write
+    }
+}
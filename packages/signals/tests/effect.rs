@@ -45,3 +45,66 @@ fn effects_rerun() {
     assert_eq!(current_counter.component, 1);
     assert_eq!(current_counter.effect, 2);
 }
+
+#[test]
+fn batched_writes_rerun_effects_once() {
+    let _ = simple_logger::SimpleLogger::new().init();
+
+    let runs = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let first = use_signal(cx, || 0);
+            let second = use_signal(cx, || 0);
+            let runs = cx.props.clone();
+            use_effect(cx, move || {
+                runs.borrow_mut().push((first.value(), second.value()))
+            });
+
+            if cx.generation() == 0 {
+                batch(|| {
+                    first.set(1);
+                    second.set(1);
+                    // Effects don't rerun until the batch ends
+                    assert_eq!(cx.props.borrow().len(), 1);
+                });
+            }
+
+            render! { div {} }
+        },
+        runs.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+
+    assert_eq!(*runs.borrow(), [(0, 0), (1, 1)]);
+}
+
+#[test]
+fn diamonds_rerun_effects_once() {
+    let _ = simple_logger::SimpleLogger::new().init();
+
+    let runs = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let source = use_signal(cx, || 0);
+            let left = use_selector(cx, move || source.value() + 1);
+            let right = use_selector(cx, move || source.value() * 2);
+            let runs = cx.props.clone();
+            use_effect(cx, move || {
+                runs.borrow_mut().push((left.value(), right.value()))
+            });
+
+            if cx.generation() == 0 {
+                source.set(1);
+            }
+
+            render! { div {} }
+        },
+        runs.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+
+    // The effect never sees the new value of one selector together with the old value of the other
+    assert_eq!(*runs.borrow(), [(1, 0), (2, 2)]);
+}
@@ -0,0 +1,82 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+// The graph is only tracked in debug builds
+#[cfg(debug_assertions)]
+#[test]
+fn graph_tracks_signals_selectors_and_effects() {
+    let _ = simple_logger::SimpleLogger::new().init();
+
+    let mut dom = VirtualDom::new(|cx| {
+        let signal = use_signal(cx, || 0);
+        let doubled = use_selector(cx, move || signal.value() * 2);
+        use_effect(cx, move || println!("{doubled}"));
+
+        if cx.generation() == 0 {
+            signal.set(1);
+        }
+
+        render! { "{signal}" }
+    });
+
+    let _ = dom.rebuild().santize();
+
+    let graph = reactive_graph_of(&dom);
+    assert_eq!(graph.nodes.len(), 3);
+    for node in &graph.nodes {
+        assert_eq!(node.origin_scope, ScopeId::ROOT);
+        assert!(node.location.file().ends_with("graph.rs"));
+    }
+
+    let signal = &graph.nodes[0];
+    let selector = &graph.nodes[1];
+    let effect = &graph.nodes[2];
+    assert_eq!(signal.kind, ReactiveNodeKind::Signal);
+    assert_eq!(selector.kind, ReactiveNodeKind::Selector);
+    assert_eq!(effect.kind, ReactiveNodeKind::Effect);
+
+    assert_eq!(signal.subscribers, [ScopeId::ROOT]);
+    assert_eq!(signal.effect_subscribers, [selector.id]);
+    assert_eq!(selector.effect_subscribers, [effect.id]);
+
+    assert_eq!(signal.writes.len(), 1);
+    assert!(signal.writes[0].location.file().ends_with("graph.rs"));
+    assert_eq!(signal.writes[0].scope, Some(ScopeId::ROOT));
+    // The selector was rerun by the write to the signal
+    assert_eq!(selector.writes.len(), 1);
+    assert_eq!(selector.writes[0].effect, Some(selector.id));
+
+    let dot = graph.to_dot();
+    assert!(dot.contains(&format!("node{} -> node{};", signal.id, selector.id)));
+    assert!(dot.contains(&format!("node{} -> scope0;", signal.id)));
+
+    #[cfg(feature = "serialize")]
+    {
+        let json = graph.to_json();
+        assert!(json.starts_with("{\"nodes\":[{\"id\":"));
+        assert!(json.contains("\"kind\":\"selector\""));
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn every_virtual_dom_has_its_own_graph() {
+    fn app(cx: Scope) -> Element {
+        let signal = use_signal(cx, || 0);
+        render! { "{signal}" }
+    }
+
+    let mut first = VirtualDom::new(app);
+    let _ = first.rebuild().santize();
+    let mut second = VirtualDom::new(app);
+    let _ = second.rebuild().santize();
+
+    assert_eq!(reactive_graph_of(&first).nodes.len(), 1);
+    assert_eq!(reactive_graph_of(&second).nodes.len(), 1);
+
+    drop(first);
+    assert_eq!(reactive_graph_of(&second).nodes.len(), 1);
+}
@@ -143,3 +143,42 @@ fn memos_prevents_component_rerun() {
         assert_eq!(current_counter.effect, 3);
     }
 }
+
+#[test]
+fn async_selectors_rerun_when_signals_change() {
+    let _ = simple_logger::SimpleLogger::new().init();
+
+    let states = Rc::new(RefCell::new(Vec::new()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let signal = use_signal(cx, || 0);
+            let doubled = use_async_selector(cx, move || {
+                let value = signal.value();
+                async move { Ok::<_, ()>(value * 2) }
+            });
+
+            cx.props.borrow_mut().push(doubled.value());
+            if cx.generation() == 1 {
+                signal.set(1);
+            }
+
+            render! { div {} }
+        },
+        states.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+    dom.process_events();
+    let _ = dom.render_immediate();
+    dom.process_events();
+    let _ = dom.render_immediate();
+
+    assert_eq!(
+        *states.borrow(),
+        [
+            AsyncState::Pending,
+            AsyncState::Ready(0),
+            AsyncState::Ready(2)
+        ]
+    );
+}
@@ -0,0 +1,152 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_core::ElementId;
+use dioxus_signals::*;
+
+#[derive(Store)]
+struct Todo {
+    title: String,
+    done: bool,
+}
+
+#[test]
+fn writes_only_rerun_subscribers_of_the_path() {
+    let _ = simple_logger::SimpleLogger::new().init();
+
+    #[derive(Default)]
+    struct RunCounter {
+        titles: HashMap<usize, usize>,
+        lists: usize,
+    }
+
+    let counter = Rc::new(RefCell::new(RunCounter::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let todos = use_store(cx, || {
+                (0..3)
+                    .map(|i| Todo {
+                        title: format!("todo {i}"),
+                        done: false,
+                    })
+                    .collect::<Vec<_>>()
+            });
+
+            if cx.generation() == 1 {
+                todos.index(1).title().set("changed".to_string());
+            }
+
+            render! {
+                List {
+                    todos: todos,
+                    counter: cx.props.clone()
+                }
+                for idx in 0..3 {
+                    Title {
+                        todo: todos.index(idx),
+                        idx: idx,
+                        counter: cx.props.clone()
+                    }
+                }
+            }
+        },
+        counter.clone(),
+    );
+
+    #[derive(Props, Clone)]
+    struct ListProps {
+        todos: Store<Vec<Todo>>,
+        counter: Rc<RefCell<RunCounter>>,
+    }
+
+    impl PartialEq for ListProps {
+        fn eq(&self, other: &Self) -> bool {
+            self.todos == other.todos
+        }
+    }
+
+    fn List(cx: Scope<ListProps>) -> Element {
+        cx.props.counter.borrow_mut().lists += 1;
+        let len = cx.props.todos.len();
+
+        render! { "{len}" }
+    }
+
+    #[derive(Props, Clone)]
+    struct TitleProps {
+        todo: Store<Todo>,
+        idx: usize,
+        counter: Rc<RefCell<RunCounter>>,
+    }
+
+    impl PartialEq for TitleProps {
+        fn eq(&self, other: &Self) -> bool {
+            self.todo == other.todo && self.idx == other.idx
+        }
+    }
+
+    fn Title(cx: Scope<TitleProps>) -> Element {
+        *cx.props
+            .counter
+            .borrow_mut()
+            .titles
+            .entry(cx.props.idx)
+            .or_default() += 1;
+        let title = cx.props.todo.title();
+
+        render! { "{title}" }
+    }
+
+    let _ = dom.rebuild().santize();
+
+    {
+        let current_counter = counter.borrow();
+        assert_eq!(current_counter.lists, 1);
+        for idx in 0..3 {
+            assert_eq!(current_counter.titles[&idx], 1);
+        }
+    }
+
+    dom.mark_dirty(ScopeId::ROOT);
+    dom.render_immediate();
+    dom.process_events();
+    dom.render_immediate();
+
+    {
+        let current_counter = counter.borrow();
+        // The list read the whole vector, so it reruns when any todo changes
+        assert_eq!(current_counter.lists, 2);
+        assert_eq!(current_counter.titles[&0], 1);
+        assert_eq!(current_counter.titles[&1], 2);
+        assert_eq!(current_counter.titles[&2], 1);
+    }
+}
+
+#[test]
+fn stores_share_their_root() {
+    let _ = simple_logger::SimpleLogger::new().init();
+
+    let mut dom = VirtualDom::new(|cx| {
+        let todo = use_store(cx, || Todo {
+            title: "todo".to_string(),
+            done: false,
+        });
+
+        assert!(todo.title() == todo.title());
+        todo.done().set(true);
+        assert!(todo.read().done);
+
+        todo.write().title = "changed".to_string();
+        assert_eq!(todo.title().value(), "changed");
+
+        let map = use_store(cx, || HashMap::from([(1, "one")]));
+        assert_eq!(*map.get(1).read(), "one");
+        assert!(map.get(2).try_read().is_none());
+
+        render! { div {} }
+    });
+
+    let _ = dom.rebuild().santize();
+}
@@ -0,0 +1,46 @@
+#![allow(unused, non_upper_case_globals, non_snake_case)]
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use dioxus_signals::*;
+
+#[test]
+fn writes_from_other_threads_mark_subscribers_dirty() {
+    let _ = simple_logger::SimpleLogger::new().init();
+
+    #[derive(Default)]
+    struct RunCounter {
+        runs: usize,
+        signal: Option<Signal<usize, SyncStorage>>,
+    }
+
+    let counter = Rc::new(RefCell::new(RunCounter::default()));
+    let mut dom = VirtualDom::new_with_props(
+        |cx| {
+            let signal = use_signal_sync(cx, || 0);
+
+            let mut counter = cx.props.borrow_mut();
+            counter.runs += 1;
+            counter.signal = Some(signal);
+
+            render! { "{signal}" }
+        },
+        counter.clone(),
+    );
+
+    let _ = dom.rebuild().santize();
+    assert_eq!(counter.borrow().runs, 1);
+
+    let signal = counter.borrow().signal.unwrap();
+    std::thread::spawn(move || {
+        *signal.write() += 1;
+    })
+    .join()
+    .unwrap();
+
+    dom.process_events();
+    let _ = dom.render_immediate();
+
+    assert_eq!(counter.borrow().runs, 2);
+    assert_eq!(*signal.read(), 1);
+}
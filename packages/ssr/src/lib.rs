@@ -9,8 +9,10 @@ mod fs_cache;
 pub mod incremental;
 #[cfg(feature = "incremental")]
 mod incremental_cfg;
+mod pretty;
 
 pub mod renderer;
+pub mod streaming;
 pub mod template;
 
 use dioxus_core::{Element, LazyNodes, Scope, VirtualDom};
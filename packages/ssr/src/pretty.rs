@@ -0,0 +1,125 @@
+//! Reformat rendered html into an indented, human readable tree
+
+/// A piece of rendered html
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    SelfClosing(&'a str),
+    Text(&'a str),
+    Comment(&'a str),
+}
+
+/// Indent rendered html so every element that contains other elements is written on its own line.
+///
+/// Elements that only contain text are kept on a single line.
+pub(crate) fn prettify(html: &str) -> String {
+    let tokens = tokenize(html);
+
+    let mut lines = Vec::new();
+    let mut depth = 0;
+    let mut idx = 0;
+
+    while idx < tokens.len() {
+        match tokens[idx] {
+            Token::Open(open) => {
+                // Keep elements that only contain text or comments on one line
+                let mut end = idx + 1;
+                while matches!(tokens.get(end), Some(Token::Text(_) | Token::Comment(_))) {
+                    end += 1;
+                }
+
+                if let Some(Token::Close(close)) = tokens.get(end) {
+                    let mut line = open.to_string();
+                    for token in &tokens[idx + 1..end] {
+                        if let Token::Text(text) | Token::Comment(text) = token {
+                            line.push_str(text);
+                        }
+                    }
+                    line.push_str(close);
+                    lines.push(indent(depth, &line));
+                    idx = end;
+                } else {
+                    lines.push(indent(depth, open));
+                    depth += 1;
+                }
+            }
+            Token::Close(close) => {
+                depth = depth.saturating_sub(1);
+                lines.push(indent(depth, close));
+            }
+            Token::SelfClosing(tag) | Token::Comment(tag) => lines.push(indent(depth, tag)),
+            Token::Text(text) => lines.push(indent(depth, text)),
+        }
+
+        idx += 1;
+    }
+
+    lines.join("\n")
+}
+
+fn indent(depth: usize, line: &str) -> String {
+    format!("{}{}", "    ".repeat(depth), line)
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+            tokens.push(Token::Comment(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with("</") {
+            let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+            tokens.push(Token::Close(&rest[..end]));
+            rest = &rest[end..];
+        } else if rest.starts_with('<') {
+            let end = tag_end(rest);
+            let tag = &rest[..end];
+            if tag.ends_with("/>") {
+                tokens.push(Token::SelfClosing(tag));
+            } else {
+                tokens.push(Token::Open(tag));
+            }
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            tokens.push(Token::Text(&rest[..end]));
+            rest = &rest[end..];
+        }
+    }
+
+    tokens
+}
+
+/// Find the end of an opening tag, skipping over any `>` inside of quoted attribute values
+fn tag_end(tag: &str) -> usize {
+    let mut in_quotes = false;
+    for (idx, c) in tag.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '>' if !in_quotes => return idx + 1,
+            _ => {}
+        }
+    }
+    tag.len()
+}
+
+#[test]
+fn prettify_nested_elements() {
+    assert_eq!(
+        prettify(
+            r#"<div class="a>b"><h1>Title</h1><ul><li>one</li><li><!--#-->two<!--#--></li></ul><input/></div>"#
+        ),
+        r#"<div class="a>b">
+    <h1>Title</h1>
+    <ul>
+        <li>one</li>
+        <li><!--#-->two<!--#--></li>
+    </ul>
+    <input/>
+</div>"#
+    );
+}
@@ -2,7 +2,8 @@ use super::cache::Segment;
 use crate::cache::StringCache;
 
 use dioxus_core::Attribute;
-use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn};
+use dioxus_core::{prelude::*, AttributeValue, DynamicNode, RenderReturn, VComponent};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::fmt::Write;
 use std::sync::Arc;
@@ -11,6 +12,8 @@ use std::sync::Arc;
 #[derive(Default)]
 pub struct Renderer {
     /// should we do our best to prettify the output?
+    ///
+    /// Elements that contain other elements are written on their own lines and indented
     pub pretty: bool,
 
     /// Control if elements are written onto a new line
@@ -22,19 +25,46 @@ pub struct Renderer {
     /// Choose to write ElementIDs into elements so the page can be re-hydrated later on
     pub pre_render: bool,
 
-    // Currently not implemented
-    // Don't proceed onto new components. Instead, put the name of the component.
+    /// Don't proceed onto new components. Instead, write a stub element with the name of the component.
+    ///
+    /// Props of components registered with [`Renderer::serialize_props`] are written into the stub.
     pub skip_components: bool,
 
     /// A cache of templates that have been rendered
     template_cache: HashMap<&'static str, Arc<StringCache>>,
+
+    /// Functions that serialize the props of a component if the scope renders that component
+    props_serializers: Vec<Box<PropsSerializer>>,
+
+    /// Suspended scopes that were replaced with a placeholder while streaming
+    pub(crate) streaming_placeholders: Option<Vec<ScopeId>>,
 }
 
+type PropsSerializer = dyn Fn(&ScopeState) -> Option<String> + Send + Sync;
+
 impl Renderer {
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Serialize the props of a component into its stub element when [`Renderer::skip_components`] is set.
+    ///
+    /// ```rust, ignore
+    /// let mut renderer = Renderer::new();
+    /// renderer.skip_components = true;
+    /// renderer.serialize_props(Counter);
+    /// ```
+    pub fn serialize_props<P: Properties + Serialize + 'static>(
+        &mut self,
+        component: fn(Scope<P>) -> Element,
+    ) {
+        self.props_serializers.push(Box::new(move |scope| {
+            scope
+                .props_of(component)
+                .and_then(|props| serde_json::to_string(props).ok())
+        }));
+    }
+
     pub fn render(&mut self, dom: &VirtualDom) -> String {
         let mut buf = String::new();
         self.render_to(&mut buf, dom).unwrap();
@@ -42,6 +72,12 @@ impl Renderer {
     }
 
     pub fn render_to(&mut self, buf: &mut impl Write, dom: &VirtualDom) -> std::fmt::Result {
+        if self.pretty {
+            let mut flat = String::new();
+            self.render_scope(&mut flat, dom, ScopeId::ROOT)?;
+            return write!(buf, "{}", crate::pretty::prettify(&flat));
+        }
+
         self.render_scope(buf, dom, ScopeId::ROOT)
     }
 
@@ -51,15 +87,63 @@ impl Renderer {
         dom: &VirtualDom,
         scope: ScopeId,
     ) -> std::fmt::Result {
-        // We should never ever run into async or errored nodes in SSR
-        // Error boundaries and suspense boundaries will convert these to sync
-        if let RenderReturn::Ready(node) = dom.get_scope(scope).unwrap().root_node() {
-            self.render_template(buf, dom, node)?
-        };
+        match dom.get_scope(scope).unwrap().root_node() {
+            RenderReturn::Ready(node) => self.render_template(buf, dom, node),
+            RenderReturn::Aborted(_) => self.render_suspended(buf, scope),
+        }
+    }
+
+    /// Render a scope that has not finished rendering yet
+    ///
+    /// While streaming, this writes a placeholder that the resolved content will be swapped into later on.
+    fn render_suspended(&mut self, buf: &mut impl Write, scope: ScopeId) -> std::fmt::Result {
+        match &mut self.streaming_placeholders {
+            Some(placeholders) => {
+                write!(
+                    buf,
+                    "<template id=\"{}\"></template>",
+                    crate::streaming::placeholder_id(scope)
+                )?;
+                placeholders.push(scope);
+            }
+            None => {
+                if self.pre_render {
+                    write!(buf, "<pre></pre>")?;
+                }
+            }
+        }
 
         Ok(())
     }
 
+    /// Write a stub element in place of a component
+    fn render_component_stub(
+        &self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        component: &VComponent,
+    ) -> std::fmt::Result {
+        write!(buf, "<dioxus-component data-name=\"{}\"", component.name)?;
+
+        let props = component
+            .mounted_scope()
+            .and_then(|id| dom.get_scope(id))
+            .and_then(|scope| {
+                self.props_serializers
+                    .iter()
+                    .find_map(|serialize| serialize(scope))
+            });
+        if let Some(props) = props {
+            write!(
+                buf,
+                " data-props=\"{}\"",
+                askama_escape::escape(&props, askama_escape::Html)
+            )?;
+        }
+
+        write!(buf, "></dioxus-component>")
+    }
+
     fn render_template(
         &mut self,
         buf: &mut impl Write,
@@ -97,19 +181,10 @@ impl Renderer {
                 Segment::Node(idx) => match &template.dynamic_nodes[*idx] {
                     DynamicNode::Component(node) => {
                         if self.skip_components {
-                            write!(buf, "<{}><{}/>", node.name, node.name)?;
+                            self.render_component_stub(buf, dom, node)?;
                         } else {
                             let id = node.mounted_scope().unwrap();
-                            let scope = dom.get_scope(id).unwrap();
-                            let node = scope.root_node();
-                            match node {
-                                RenderReturn::Ready(node) => {
-                                    self.render_template(buf, dom, node)?
-                                }
-                                _ => todo!(
-                                    "generally, scopes should be sync, only if being traversed"
-                                ),
-                            }
+                            self.render_scope(buf, dom, id)?;
                         }
                     }
                     DynamicNode::Text(text) => {
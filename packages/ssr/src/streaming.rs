@@ -0,0 +1,162 @@
+//! Out of order streaming of suspended content
+//!
+//! The shell of the page is rendered as soon as the VirtualDom is built. Every suspended scope is replaced with an
+//! empty `<template>` placeholder. As each scope resolves, its html is streamed in a second `<template>` followed by a
+//! small script that swaps the resolved content into the placeholder.
+
+use crate::Renderer;
+use dioxus_core::{RenderReturn, ScopeId, VirtualDom};
+use std::fmt::Write;
+
+/// The script that moves resolved content into the placeholder it was rendered for.
+///
+/// The script removes itself, the content template, and the calling script so the final document matches a non-streamed
+/// render and can be hydrated.
+const SWAP_SCRIPT: &str = r#"<script>window.__dioxus_swap=function(i){var p=document.getElementById("ds-"+i),t=document.getElementById("dsc-"+i);if(p&&t){p.replaceWith(t.content);t.remove()}var s=document.currentScript;if(s)s.remove()};document.currentScript.remove()</script>"#;
+
+/// The id of the placeholder element written in place of a suspended scope
+pub(crate) fn placeholder_id(scope: ScopeId) -> String {
+    format!("ds-{}", scope.0)
+}
+
+impl Renderer {
+    /// Render a VirtualDom, streaming out chunks of html as suspended scopes resolve.
+    ///
+    /// The VirtualDom must already be rebuilt. The first chunk is always the shell of the page, with placeholders
+    /// for every suspended scope. Every following chunk contains the html for one or more scopes that finished
+    /// suspending and the script to swap them into the page.
+    ///
+    /// ```rust, ignore
+    /// let mut dom = VirtualDom::new(app);
+    /// _ = dom.rebuild();
+    ///
+    /// let mut renderer = Renderer::new();
+    /// renderer
+    ///     .render_streaming(&mut dom, |chunk| {
+    ///         send_to_client(chunk);
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn render_streaming(
+        &mut self,
+        dom: &mut VirtualDom,
+        mut on_chunk: impl FnMut(String) -> std::fmt::Result,
+    ) -> std::fmt::Result {
+        let mut shell = String::new();
+        let mut pending = self.render_with_placeholders(&mut shell, dom, ScopeId::ROOT)?;
+        on_chunk(shell)?;
+
+        let mut wrote_swap_script = false;
+
+        while !pending.is_empty() {
+            let waiting = dom.has_suspended_work();
+            if waiting {
+                dom.wait_for_work().await;
+                _ = dom.render_immediate();
+            }
+
+            let mut chunk = String::new();
+            let mut still_pending = Vec::new();
+
+            for scope in std::mem::take(&mut pending) {
+                match dom.get_scope(scope).and_then(|s| s.try_root_node()) {
+                    Some(RenderReturn::Ready(_)) => {
+                        if !wrote_swap_script {
+                            chunk.push_str(SWAP_SCRIPT);
+                            wrote_swap_script = true;
+                        }
+                        write!(chunk, "<template id=\"dsc-{}\">", scope.0)?;
+                        let nested = self.render_with_placeholders(&mut chunk, dom, scope)?;
+                        write!(
+                            chunk,
+                            "</template><script>__dioxus_swap({})</script>",
+                            scope.0
+                        )?;
+                        still_pending.extend(nested);
+                    }
+                    // Keep waiting as long as the VirtualDom is still making progress on suspense
+                    Some(RenderReturn::Aborted(_)) if waiting => still_pending.push(scope),
+                    // The scope was removed or will never resolve. Leave the placeholder in place
+                    _ => {}
+                }
+            }
+
+            pending = still_pending;
+
+            if !chunk.is_empty() {
+                on_chunk(chunk)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render a scope, returning all of the suspended scopes that were replaced with placeholders
+    fn render_with_placeholders(
+        &mut self,
+        buf: &mut impl Write,
+        dom: &VirtualDom,
+        scope: ScopeId,
+    ) -> Result<Vec<ScopeId>, std::fmt::Error> {
+        self.streaming_placeholders = Some(Vec::new());
+        let result = self.render_scope(buf, dom, scope);
+        let placeholders = self.streaming_placeholders.take().unwrap_or_default();
+        result.map(|_| placeholders)
+    }
+}
+
+#[test]
+fn streams_suspended_scopes() {
+    use dioxus::prelude::*;
+
+    fn app(cx: Scope) -> Element {
+        render! {
+            div {
+                "Waiting for... "
+                suspended_child {}
+            }
+        }
+    }
+
+    fn suspended_child(cx: Scope) -> Element {
+        let val = use_state(cx, || 0);
+
+        if **val < 3 {
+            let mut val = val.clone();
+            cx.spawn(async move {
+                val += 1;
+            });
+            cx.suspend()?;
+        }
+
+        render!("child")
+    }
+
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(async {
+            let mut dom = VirtualDom::new(app);
+            _ = dom.rebuild();
+
+            let mut chunks = Vec::new();
+            Renderer::new()
+                .render_streaming(&mut dom, |chunk| {
+                    chunks.push(chunk);
+                    Ok(())
+                })
+                .await
+                .unwrap();
+
+            assert_eq!(chunks.len(), 2);
+            assert_eq!(
+                chunks[0],
+                "<div>Waiting for... <template id=\"ds-1\"></template></div>"
+            );
+            assert!(chunks[1].starts_with(SWAP_SCRIPT));
+            assert!(chunks[1].ends_with(
+                "<template id=\"dsc-1\">child</template><script>__dioxus_swap(1)</script>"
+            ));
+        });
+}
@@ -0,0 +1,56 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use serde::Serialize;
+
+#[derive(Props, PartialEq, Serialize)]
+struct CounterProps {
+    count: i32,
+    label: &'static str,
+}
+
+fn Counter(cx: Scope<CounterProps>) -> Element {
+    render! { span { "{cx.props.label}: {cx.props.count}" } }
+}
+
+fn Footer(cx: Scope) -> Element {
+    render! { footer { "footer" } }
+}
+
+fn app(cx: Scope) -> Element {
+    render! {
+        div {
+            Counter { count: 3, label: "clicks" }
+            Footer {}
+        }
+    }
+}
+
+#[test]
+fn skip_components() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.skip_components = true;
+
+    assert_eq!(
+        renderer.render(&dom),
+        r#"<div><dioxus-component data-name="Counter"></dioxus-component><dioxus-component data-name="Footer"></dioxus-component></div>"#
+    );
+}
+
+#[test]
+fn skip_components_with_props() {
+    let mut dom = VirtualDom::new(app);
+    _ = dom.rebuild();
+
+    let mut renderer = dioxus_ssr::Renderer::new();
+    renderer.skip_components = true;
+    renderer.serialize_props(Counter);
+
+    assert_eq!(
+        renderer.render(&dom),
+        r#"<div><dioxus-component data-name="Counter" data-props="{&quot;count&quot;:3,&quot;label&quot;:&quot;clicks&quot;}"></dioxus-component><dioxus-component data-name="Footer"></dioxus-component></div>"#
+    );
+}
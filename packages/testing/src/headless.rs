@@ -0,0 +1,327 @@
+//! A headless renderer for testing components the way a user interacts with them
+//!
+//! [`HeadlessDom`] applies the mutations of a VirtualDom to a [`RealDom`] so tests can find nodes by their tag, id,
+//! class or text, fire events at them, and assert on the resulting document.
+
+use dioxus_core::{Element, ElementId, Scope, VirtualDom};
+use dioxus_html::{
+    input_data::keyboard_types::{Code, Key, Location, Modifiers},
+    FormData, KeyboardData, MouseData,
+};
+use dioxus_native_core::{
+    node::{OwnedAttributeDiscription, OwnedAttributeValue},
+    prelude::*,
+    real_dom::NodeTypeMut,
+};
+use std::{any::Any, fmt::Write, future::Future, rc::Rc, task::Context, time::Duration};
+
+/// A way to find nodes in a [`HeadlessDom`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum By<'a> {
+    /// Elements with this tag name
+    Tag(&'a str),
+    /// Elements with this `id` attribute
+    Id(&'a str),
+    /// Elements with this class in their `class` attribute
+    Class(&'a str),
+    /// Elements whose own text content, ignoring leading and trailing whitespace, is exactly this text
+    Text(&'a str),
+}
+
+/// A VirtualDom rendered into a [`RealDom`] without a window or browser
+///
+/// Every method that sends an event or advances time renders the VirtualDom afterwards, so the [`RealDom`] is always
+/// up to date when the test inspects it.
+///
+/// ```rust, ignore
+/// let mut dom = HeadlessDom::new(app);
+///
+/// let button = dom.get(By::Text("Increment"));
+/// dom.click(button);
+///
+/// assert!(dom.find(By::Text("Count: 1")).is_some());
+/// ```
+pub struct HeadlessDom {
+    vdom: VirtualDom,
+    rdom: RealDom,
+    state: DioxusState,
+}
+
+impl HeadlessDom {
+    /// Create and render a new HeadlessDom for a component without props
+    pub fn new(app: fn(Scope) -> Element) -> Self {
+        Self::new_with_props(app, ())
+    }
+
+    /// Create and render a new HeadlessDom for a component with props
+    pub fn new_with_props<P: 'static>(app: fn(Scope<P>) -> Element, props: P) -> Self {
+        Self::from_dom(VirtualDom::new_with_props(app, props))
+    }
+
+    /// Create a HeadlessDom from an existing VirtualDom and render it
+    pub fn from_dom(mut vdom: VirtualDom) -> Self {
+        let tracked_states: [TypeErasedState<()>; 0] = [];
+        let mut rdom = RealDom::new(tracked_states);
+        let mut state = DioxusState::create(&mut rdom);
+        state.apply_mutations(&mut rdom, vdom.rebuild());
+        rdom.update_state(SendAnyMap::new());
+
+        let mut dom = Self { vdom, rdom, state };
+        dom.flush();
+        dom
+    }
+
+    /// Get the VirtualDom being tested
+    pub fn vdom(&self) -> &VirtualDom {
+        &self.vdom
+    }
+
+    /// Get the VirtualDom being tested mutably
+    pub fn vdom_mut(&mut self) -> &mut VirtualDom {
+        &mut self.vdom
+    }
+
+    /// Get the RealDom the VirtualDom is rendered into
+    pub fn rdom(&self) -> &RealDom {
+        &self.rdom
+    }
+
+    /// Run every task and render every scope that is ready without waiting
+    ///
+    /// This never blocks on futures that are pending. Use [`HeadlessDom::advance`] to progress timers.
+    pub fn flush(&mut self) {
+        let waker = futures_util::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            let ready = {
+                let work = self.vdom.wait_for_work();
+                futures_util::pin_mut!(work);
+                work.poll(&mut cx).is_ready()
+            };
+            if !ready {
+                break;
+            }
+
+            let mutations = self.vdom.render_immediate();
+            let changed = !mutations.edits.is_empty() || !mutations.templates.is_empty();
+            self.state.apply_mutations(&mut self.rdom, mutations);
+            self.rdom.update_state(SendAnyMap::new());
+
+            // Suspended scopes always report work, so stop once they stop making progress
+            if !changed && self.vdom.has_suspended_work() {
+                break;
+            }
+        }
+    }
+
+    /// Advance the tokio clock and render any work that became ready
+    ///
+    /// The clock must be paused for this to be deterministic. Use `#[tokio::test(start_paused = true)]` or call
+    /// `tokio::time::pause` before creating the dom.
+    pub async fn advance(&mut self, duration: Duration) {
+        tokio::time::advance(duration).await;
+        self.flush();
+    }
+
+    /// Find every node that matches in document order
+    pub fn find_all(&self, by: By) -> Vec<NodeId> {
+        let mut found = Vec::new();
+        self.rdom.traverse_depth_first(|node| {
+            if node.id() != self.rdom.root_id() && self.matches(&node, by) {
+                found.push(node.id());
+            }
+        });
+        found
+    }
+
+    /// Find the first node that matches
+    pub fn find(&self, by: By) -> Option<NodeId> {
+        self.find_all(by).into_iter().next()
+    }
+
+    /// Find the first node that matches
+    ///
+    /// # Panics
+    ///
+    /// Panics if no node matches
+    #[track_caller]
+    pub fn get(&self, by: By) -> NodeId {
+        self.find(by)
+            .unwrap_or_else(|| panic!("No node matches {by:?} in\n{}", self.to_html()))
+    }
+
+    fn matches(&self, node: &NodeRef, by: By) -> bool {
+        let node_type = node.node_type();
+        let NodeType::Element(element) = &*node_type else {
+            return false;
+        };
+
+        match by {
+            By::Tag(tag) => element.tag == tag,
+            By::Id(id) => attribute(element, "id").as_deref() == Some(id),
+            By::Class(class) => attribute(element, "class").map_or(false, |classes| {
+                classes.split_whitespace().any(|c| c == class)
+            }),
+            By::Text(text) => {
+                let mut own_text = String::new();
+                for child in node.children() {
+                    if let NodeType::Text(child) = &*child.node_type() {
+                        own_text.push_str(&child.text);
+                    }
+                }
+                own_text.trim() == text
+            }
+        }
+    }
+
+    /// Get the value of an attribute on an element
+    pub fn attribute(&self, id: NodeId, name: &str) -> Option<String> {
+        let node = self.rdom.get(id)?;
+        let node_type = node.node_type();
+        match &*node_type {
+            NodeType::Element(element) => attribute(element, name),
+            _ => None,
+        }
+    }
+
+    /// Get the text content of a node and all of its children
+    pub fn text_content(&self, id: NodeId) -> String {
+        let mut text = String::new();
+        if let Some(node) = self.rdom.get(id) {
+            collect_text(&node, &mut text);
+        }
+        text
+    }
+
+    /// Click an element
+    pub fn click(&mut self, id: NodeId) {
+        self.dispatch(id, "click", Rc::new(MouseData::default()), true);
+    }
+
+    /// Type a new value into an input element, updating its `value` attribute and sending an input event
+    pub fn input(&mut self, id: NodeId, value: &str) {
+        if let Some(mut node) = self.rdom.get_mut(id) {
+            if let NodeTypeMut::Element(mut element) = node.node_type_mut() {
+                element.set_attribute(
+                    OwnedAttributeDiscription::from("value".to_string()),
+                    OwnedAttributeValue::Text(value.to_string()),
+                );
+            }
+        }
+
+        let data = FormData {
+            value: value.to_string(),
+            values: Default::default(),
+            files: None,
+        };
+        self.dispatch(id, "input", Rc::new(data), true);
+    }
+
+    /// Press a key while an element is focused
+    pub fn keydown(&mut self, id: NodeId, key: Key) {
+        let data = KeyboardData::new(
+            key,
+            Code::Unidentified,
+            Location::Standard,
+            false,
+            Modifiers::empty(),
+        );
+        self.dispatch(id, "keydown", Rc::new(data), true);
+    }
+
+    /// Send any event to a node, then render the result
+    ///
+    /// Nodes without an [`ElementId`] send the event to their closest ancestor with one. Nodes without an id never
+    /// have listeners, so this reaches the same handlers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not mounted
+    pub fn dispatch(&mut self, id: NodeId, name: &str, data: Rc<dyn Any>, bubbles: bool) {
+        let element = self
+            .mounted_id(id)
+            .unwrap_or_else(|| panic!("Node {id:?} is not mounted in the VirtualDom"));
+
+        self.vdom.handle_event(name, data, element, bubbles);
+        self.flush();
+    }
+
+    fn mounted_id(&self, id: NodeId) -> Option<ElementId> {
+        let mut node = self.rdom.get(id)?;
+        loop {
+            if let Some(id) = node.mounted_id() {
+                return Some(id);
+            }
+            node = node.parent()?;
+        }
+    }
+
+    /// Serialize the current document to html for assertions. Attributes are sorted by name.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        if let Some(root) = self.rdom.get(self.rdom.root_id()) {
+            for child in root.children() {
+                write_html(&child, &mut html);
+            }
+        }
+        html
+    }
+}
+
+fn attribute(element: &ElementNode, name: &str) -> Option<String> {
+    element
+        .attributes
+        .iter()
+        .find(|(attr, _)| attr.name == name && attr.namespace.is_none())
+        .map(|(_, value)| value.to_string())
+}
+
+fn collect_text(node: &NodeRef, text: &mut String) {
+    match &*node.node_type() {
+        NodeType::Text(node) => text.push_str(&node.text),
+        NodeType::Element(_) => {
+            for child in node.children() {
+                collect_text(&child, text);
+            }
+        }
+        NodeType::Placeholder => {}
+    }
+}
+
+fn write_html(node: &NodeRef, html: &mut String) {
+    match &*node.node_type() {
+        NodeType::Text(text) => html.push_str(&text.text),
+        NodeType::Element(element) => {
+            let mut attributes: Vec<_> = element
+                .attributes
+                .iter()
+                .filter(|(attr, _)| attr.namespace.is_none())
+                .map(|(attr, value)| (attr.name.as_str(), value.to_string()))
+                .collect();
+            attributes.sort();
+
+            let mut styles: Vec<_> = element
+                .attributes
+                .iter()
+                .filter(|(attr, _)| attr.namespace.as_deref() == Some("style"))
+                .map(|(attr, value)| format!("{}:{};", attr.name, value))
+                .collect();
+            styles.sort();
+
+            let _ = write!(html, "<{}", element.tag);
+            for (name, value) in attributes {
+                let _ = write!(html, " {name}=\"{value}\"");
+            }
+            if !styles.is_empty() {
+                let _ = write!(html, " style=\"{}\"", styles.concat());
+            }
+            html.push('>');
+            for child in node.children() {
+                write_html(&child, html);
+            }
+            let _ = write!(html, "</{}>", element.tag);
+        }
+        NodeType::Placeholder => {}
+    }
+}
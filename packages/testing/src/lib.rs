@@ -0,0 +1,216 @@
+#![doc = include_str!("../README.md")]
+#![doc(html_logo_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![doc(html_favicon_url = "https://avatars.githubusercontent.com/u/79236386")]
+#![warn(missing_docs)]
+
+mod headless;
+mod query;
+mod snapshot;
+
+pub use headless::{By, HeadlessDom};
+pub use query::{Selector, SelectorError};
+pub use snapshot::{
+    compare_snapshot, default_snapshot_dir, update_mode, SnapshotResult, UPDATE_SNAPSHOTS_ENV,
+};
+
+use dioxus_core::{Element, ElementId, Mutations, Scope, VirtualDom};
+use std::{any::Any, fmt::Write, path::PathBuf, rc::Rc};
+
+/// A VirtualDom driven by a test that records every batch of mutations it produces
+///
+/// ```rust, ignore
+/// let mut dom = TestDom::new(app);
+///
+/// dom.handle_event("button#increment", "click", Rc::new(MouseData::default()), true);
+///
+/// dom.assert_snapshot("counter_increments");
+/// ```
+pub struct TestDom {
+    dom: VirtualDom,
+    renderer: dioxus_ssr::Renderer,
+    steps: Vec<Step>,
+    snapshot_dir: PathBuf,
+}
+
+/// A single recorded action and the mutations it produced
+struct Step {
+    label: String,
+    mutations: Vec<String>,
+}
+
+impl TestDom {
+    /// Create and rebuild a new VirtualDom for a component without props
+    pub fn new(app: fn(Scope) -> Element) -> Self {
+        Self::new_with_props(app, ())
+    }
+
+    /// Create and rebuild a new VirtualDom for a component with props
+    pub fn new_with_props<P: 'static>(app: fn(Scope<P>) -> Element, props: P) -> Self {
+        Self::from_dom(VirtualDom::new_with_props(app, props))
+    }
+
+    /// Rebuild an existing VirtualDom and record the mutations it produces
+    pub fn from_dom(mut dom: VirtualDom) -> Self {
+        let mutations = record(dom.rebuild());
+
+        let mut renderer = dioxus_ssr::Renderer::new();
+        renderer.pretty = true;
+
+        Self {
+            dom,
+            renderer,
+            steps: vec![Step {
+                label: "rebuild".to_string(),
+                mutations,
+            }],
+            snapshot_dir: default_snapshot_dir(),
+        }
+    }
+
+    /// Store snapshots in a different directory (defaults to `tests/snapshots` in the crate being tested)
+    pub fn with_snapshot_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.snapshot_dir = dir.into();
+        self
+    }
+
+    /// Get the VirtualDom being tested
+    pub fn dom(&self) -> &VirtualDom {
+        &self.dom
+    }
+
+    /// Get the VirtualDom being tested mutably
+    pub fn dom_mut(&mut self) -> &mut VirtualDom {
+        &mut self.dom
+    }
+
+    /// Render any dirty scopes and record the mutations they produce
+    pub fn render(&mut self) {
+        self.render_step("render");
+    }
+
+    /// Wait for the VirtualDom to have work, then render it
+    pub async fn wait_for_work(&mut self) {
+        self.dom.wait_for_work().await;
+        self.render_step("work");
+    }
+
+    /// Wait for every suspended scope to resolve, recording the mutations produced along the way
+    pub async fn wait_for_suspense(&mut self) {
+        while self.dom.has_suspended_work() {
+            self.dom.wait_for_work().await;
+            self.render_step("suspense");
+        }
+    }
+
+    /// Find the first element that matches a selector
+    ///
+    /// Elements that were not assigned an [`ElementId`] resolve to their closest ancestor with one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector is invalid
+    pub fn find(&self, selector: &str) -> Option<ElementId> {
+        self.find_all(selector).into_iter().next()
+    }
+
+    /// Find every element that matches a selector in document order
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector is invalid
+    pub fn find_all(&self, selector: &str) -> Vec<ElementId> {
+        let selector: Selector = selector.parse().unwrap_or_else(|err| panic!("{err}"));
+        query::ElementTree::new(&self.dom).query(&selector)
+    }
+
+    /// Send an event to the first element that matches a selector, then render and record the result
+    ///
+    /// The name of the event is the name of the listener without the `on` prefix. For example, `"click"` for `onclick`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the selector is invalid or no element matches it
+    pub fn handle_event(&mut self, selector: &str, name: &str, data: Rc<dyn Any>, bubbles: bool) {
+        let id = self
+            .find(selector)
+            .unwrap_or_else(|| panic!("No element matches the selector `{selector}`"));
+
+        self.dom.handle_event(name, data, id, bubbles);
+
+        self.render_step(&format!("{name} on `{selector}`"));
+    }
+
+    /// Render the current state of the VirtualDom to indented html
+    pub fn html(&mut self) -> String {
+        self.renderer.render(&self.dom)
+    }
+
+    /// Get the snapshot of every step recorded so far, followed by the current html
+    pub fn snapshot(&mut self) -> String {
+        let mut out = String::new();
+        for step in &self.steps {
+            let _ = writeln!(out, "-- {} --", step.label);
+            for mutation in &step.mutations {
+                let _ = writeln!(out, "{mutation}");
+            }
+        }
+        let _ = writeln!(out, "-- html --");
+        let _ = writeln!(out, "{}", self.html());
+        out
+    }
+
+    /// Compare the snapshot with the file `{name}.snap` in the snapshot directory
+    ///
+    /// Set the `DIOXUS_UPDATE_SNAPSHOTS` environment variable to create missing snapshots and overwrite snapshots that
+    /// are out of date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot does not match the stored file, or if there is no stored file outside of update mode
+    #[track_caller]
+    pub fn assert_snapshot(&mut self, name: &str) {
+        let actual = self.snapshot();
+        match compare_snapshot(&self.snapshot_dir, name, &actual, update_mode()) {
+            Ok(SnapshotResult::Matched)
+            | Ok(SnapshotResult::Created(_))
+            | Ok(SnapshotResult::Updated(_)) => {}
+            Ok(SnapshotResult::Missing { path, actual }) => {
+                panic!(
+                    "Snapshot {} does not exist. Rerun with {UPDATE_SNAPSHOTS_ENV}=1 to create it.\n{}",
+                    path.display(),
+                    actual
+                );
+            }
+            Ok(SnapshotResult::Mismatched {
+                path,
+                expected,
+                actual,
+            }) => {
+                panic!(
+                    "Snapshot {} does not match. Rerun with {UPDATE_SNAPSHOTS_ENV}=1 to update it.\n{}",
+                    path.display(),
+                    snapshot::diff(&expected, &actual)
+                );
+            }
+            Err(err) => panic!("Failed to read snapshot `{name}`: {err}"),
+        }
+    }
+
+    fn render_step(&mut self, label: &str) {
+        let mutations = record(self.dom.render_immediate());
+        self.steps.push(Step {
+            label: label.to_string(),
+            mutations,
+        });
+    }
+}
+
+/// Format mutations with stable template names
+fn record(mutations: Mutations) -> Vec<String> {
+    mutations
+        .santize()
+        .edits
+        .iter()
+        .map(|edit| format!("{edit:?}"))
+        .collect()
+}
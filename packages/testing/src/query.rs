@@ -0,0 +1,358 @@
+//! Find elements in a VirtualDom with css-like selectors
+//!
+//! Selectors support tag names, `#id`, `.class`, `[attribute]` and `[attribute="value"]`, combined into compound
+//! selectors like `button.primary[disabled]` and chained with the descendant combinator (`ul li`).
+
+use dioxus_core::{
+    AttributeValue, DynamicNode, ElementId, RenderReturn, ScopeId, TemplateAttribute, TemplateNode,
+    VNode, VirtualDom,
+};
+
+/// A parsed selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector {
+    /// Each compound selector, from the outermost ancestor to the target element
+    parts: Vec<Compound>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Compound {
+    tag: Option<String>,
+    id: Option<String>,
+    classes: Vec<String>,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+/// An error that occurred while parsing a selector
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectorError {
+    /// The selector that failed to parse
+    pub selector: String,
+}
+
+impl std::fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid selector `{}`", self.selector)
+    }
+}
+
+impl std::error::Error for SelectorError {}
+
+impl std::str::FromStr for Selector {
+    type Err = SelectorError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        let error = || SelectorError {
+            selector: selector.to_string(),
+        };
+
+        let mut parts = Vec::new();
+        let mut chars = selector.trim().chars().peekable();
+        let mut current = Compound::default();
+        let mut empty = true;
+
+        while let Some(c) = chars.next() {
+            match c {
+                ' ' => {
+                    if !empty {
+                        parts.push(std::mem::take(&mut current));
+                        empty = true;
+                    }
+                }
+                '#' => {
+                    current.id = Some(take_ident(&mut chars).ok_or_else(error)?);
+                    empty = false;
+                }
+                '.' => {
+                    current
+                        .classes
+                        .push(take_ident(&mut chars).ok_or_else(error)?);
+                    empty = false;
+                }
+                '[' => {
+                    let mut inner = String::new();
+                    let mut in_quotes = false;
+                    loop {
+                        match chars.next().ok_or_else(error)? {
+                            '"' => in_quotes = !in_quotes,
+                            ']' if !in_quotes => break,
+                            c => inner.push(c),
+                        }
+                    }
+                    let attribute = match inner.split_once('=') {
+                        Some((name, value)) => {
+                            (name.trim().to_string(), Some(value.trim().to_string()))
+                        }
+                        None => (inner.trim().to_string(), None),
+                    };
+                    if attribute.0.is_empty() {
+                        return Err(error());
+                    }
+                    current.attributes.push(attribute);
+                    empty = false;
+                }
+                c if is_ident_char(c) && empty => {
+                    let mut tag = c.to_string();
+                    tag.push_str(&take_ident(&mut chars).unwrap_or_default());
+                    current.tag = Some(tag);
+                    empty = false;
+                }
+                _ => return Err(error()),
+            }
+        }
+
+        if !empty {
+            parts.push(current);
+        }
+
+        if parts.is_empty() {
+            return Err(error());
+        }
+
+        Ok(Self { parts })
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '-' || c == '_'
+}
+
+fn take_ident(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<String> {
+    let mut ident = String::new();
+    while let Some(c) = chars.peek().copied().filter(|c| is_ident_char(*c)) {
+        ident.push(c);
+        chars.next();
+    }
+    (!ident.is_empty()).then_some(ident)
+}
+
+impl Compound {
+    fn matches(&self, element: &ElementInfo) -> bool {
+        if let Some(tag) = &self.tag {
+            if tag != &element.tag {
+                return false;
+            }
+        }
+
+        if let Some(id) = &self.id {
+            if element.attribute("id") != Some(id) {
+                return false;
+            }
+        }
+
+        let classes = element.attribute("class").unwrap_or_default();
+        if !self
+            .classes
+            .iter()
+            .all(|class| classes.split_whitespace().any(|c| c == class))
+        {
+            return false;
+        }
+
+        self.attributes
+            .iter()
+            .all(|(name, value)| match (element.attribute(name), value) {
+                (Some(found), Some(value)) => found == value,
+                (Some(_), None) => true,
+                (None, _) => false,
+            })
+    }
+}
+
+/// An element found while walking the VirtualDom
+#[derive(Debug)]
+pub(crate) struct ElementInfo {
+    pub(crate) tag: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    /// The id of this element if it was assigned one by the VirtualDom
+    pub(crate) id: Option<ElementId>,
+    /// The index of the parent element
+    pub(crate) parent: Option<usize>,
+}
+
+impl ElementInfo {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attr, _)| attr == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A flattened view of all the elements in a VirtualDom
+pub(crate) struct ElementTree {
+    elements: Vec<ElementInfo>,
+}
+
+impl ElementTree {
+    pub(crate) fn new(dom: &VirtualDom) -> Self {
+        let mut tree = Self {
+            elements: Vec::new(),
+        };
+        tree.walk_scope(dom, ScopeId::ROOT, None);
+        tree
+    }
+
+    /// Find the ids of every element that matches the selector in document order
+    ///
+    /// Elements without an id of their own resolve to their closest ancestor with an id. Elements that are not assigned
+    /// an id never have listeners, so events sent to that ancestor reach the same handlers.
+    pub(crate) fn query(&self, selector: &Selector) -> Vec<ElementId> {
+        let mut ids = Vec::new();
+        for idx in 0..self.elements.len() {
+            if self.matches(idx, &selector.parts) {
+                if let Some(id) = self.closest_id(idx) {
+                    if !ids.contains(&id) {
+                        ids.push(id);
+                    }
+                }
+            }
+        }
+        ids
+    }
+
+    fn matches(&self, idx: usize, parts: &[Compound]) -> bool {
+        let Some((last, ancestors)) = parts.split_last() else {
+            return true;
+        };
+
+        if !last.matches(&self.elements[idx]) {
+            return false;
+        }
+
+        // Find any ancestor that matches the rest of the selector
+        let mut parent = self.elements[idx].parent;
+        while let Some(parent_idx) = parent {
+            if self.matches(parent_idx, ancestors) {
+                return true;
+            }
+            parent = self.elements[parent_idx].parent;
+        }
+
+        ancestors.is_empty()
+    }
+
+    fn closest_id(&self, mut idx: usize) -> Option<ElementId> {
+        loop {
+            let element = &self.elements[idx];
+            if element.id.is_some() {
+                return element.id;
+            }
+            idx = element.parent?;
+        }
+    }
+
+    fn walk_scope(&mut self, dom: &VirtualDom, scope: ScopeId, parent: Option<usize>) {
+        if let Some(RenderReturn::Ready(node)) =
+            dom.get_scope(scope).and_then(|s| s.try_root_node())
+        {
+            self.walk_vnode(dom, node, parent);
+        }
+    }
+
+    fn walk_vnode(&mut self, dom: &VirtualDom, node: &VNode, parent: Option<usize>) {
+        for (root_idx, root) in node.template.get().roots.iter().enumerate() {
+            let root_id = node.root_ids.borrow().get(root_idx).copied();
+            self.walk_template_node(dom, node, root, root_id, parent);
+        }
+    }
+
+    fn walk_template_node(
+        &mut self,
+        dom: &VirtualDom,
+        node: &VNode,
+        template_node: &TemplateNode,
+        root_id: Option<ElementId>,
+        parent: Option<usize>,
+    ) {
+        match template_node {
+            TemplateNode::Element {
+                tag,
+                attrs,
+                children,
+                ..
+            } => {
+                let mut id = root_id;
+                let mut attributes = Vec::new();
+                for attr in attrs.iter() {
+                    match attr {
+                        TemplateAttribute::Static {
+                            name,
+                            value,
+                            namespace: None,
+                        } => attributes.push((name.to_string(), value.to_string())),
+                        TemplateAttribute::Static { .. } => {}
+                        TemplateAttribute::Dynamic { id: idx } => {
+                            let attr = &node.dynamic_attrs[*idx];
+                            let mounted = attr.mounted_element();
+                            if id.is_none() && mounted != ElementId::default() {
+                                id = Some(mounted);
+                            }
+                            if attr.namespace.is_some() {
+                                continue;
+                            }
+                            let value = match &attr.value {
+                                AttributeValue::Text(value) => value.to_string(),
+                                AttributeValue::Float(value) => value.to_string(),
+                                AttributeValue::Int(value) => value.to_string(),
+                                AttributeValue::Bool(value) => value.to_string(),
+                                _ => continue,
+                            };
+                            attributes.push((attr.name.to_string(), value));
+                        }
+                    }
+                }
+
+                let idx = self.elements.len();
+                self.elements.push(ElementInfo {
+                    tag: tag.to_string(),
+                    attributes,
+                    id,
+                    parent,
+                });
+
+                for child in children.iter() {
+                    self.walk_template_node(dom, node, child, None, Some(idx));
+                }
+            }
+            TemplateNode::Dynamic { id } => match &node.dynamic_nodes[*id] {
+                DynamicNode::Component(component) => {
+                    if let Some(scope) = component.mounted_scope() {
+                        self.walk_scope(dom, scope, parent);
+                    }
+                }
+                DynamicNode::Fragment(nodes) => {
+                    for node in nodes.iter() {
+                        self.walk_vnode(dom, node, parent);
+                    }
+                }
+                DynamicNode::Text(_) | DynamicNode::Placeholder(_) => {}
+            },
+            TemplateNode::Text { .. } | TemplateNode::DynamicText { .. } => {}
+        }
+    }
+}
+
+#[test]
+fn parse_selectors() {
+    let selector: Selector = "ul.list li#first[data-active=\"true\"]".parse().unwrap();
+    assert_eq!(
+        selector.parts,
+        vec![
+            Compound {
+                tag: Some("ul".to_string()),
+                classes: vec!["list".to_string()],
+                ..Default::default()
+            },
+            Compound {
+                tag: Some("li".to_string()),
+                id: Some("first".to_string()),
+                attributes: vec![("data-active".to_string(), Some("true".to_string()))],
+                ..Default::default()
+            },
+        ]
+    );
+
+    assert!("".parse::<Selector>().is_err());
+    assert!("div > span".parse::<Selector>().is_err());
+}
@@ -0,0 +1,131 @@
+//! Compare rendered output against snapshot files on disk
+
+use std::path::{Path, PathBuf};
+
+/// The environment variable that switches snapshot assertions into update mode
+pub const UPDATE_SNAPSHOTS_ENV: &str = "DIOXUS_UPDATE_SNAPSHOTS";
+
+/// The result of comparing a snapshot against the file on disk
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotResult {
+    /// The snapshot matched the stored file
+    Matched,
+    /// There was no stored file and update mode is disabled, so nothing was written
+    Missing {
+        /// The path the snapshot would be stored at
+        path: PathBuf,
+        /// The snapshot that was just recorded
+        actual: String,
+    },
+    /// There was no stored file, so the snapshot was written because update mode is enabled
+    Created(PathBuf),
+    /// The stored file was out of date and was overwritten because update mode is enabled
+    Updated(PathBuf),
+    /// The snapshot did not match the stored file
+    Mismatched {
+        /// The path of the stored snapshot
+        path: PathBuf,
+        /// The contents of the stored snapshot
+        expected: String,
+        /// The snapshot that was just recorded
+        actual: String,
+    },
+}
+
+/// Check if snapshots should be overwritten instead of compared
+pub fn update_mode() -> bool {
+    std::env::var(UPDATE_SNAPSHOTS_ENV).map_or(false, |value| value != "0" && !value.is_empty())
+}
+
+/// The default directory snapshots are stored in: `tests/snapshots` in the crate being tested
+pub fn default_snapshot_dir() -> PathBuf {
+    std::env::var("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("tests")
+        .join("snapshots")
+}
+
+/// Compare a snapshot against the file `{name}.snap` in the directory
+///
+/// Missing snapshots are only written and outdated snapshots are only overwritten in update mode.
+pub fn compare_snapshot(
+    dir: &Path,
+    name: &str,
+    actual: &str,
+    update: bool,
+) -> std::io::Result<SnapshotResult> {
+    let path = dir.join(format!("{name}.snap"));
+
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            if !update {
+                return Ok(SnapshotResult::Missing {
+                    path,
+                    actual: actual.to_string(),
+                });
+            }
+            std::fs::create_dir_all(dir)?;
+            std::fs::write(&path, actual)?;
+            return Ok(SnapshotResult::Created(path));
+        }
+        Err(err) => return Err(err),
+    };
+
+    // Ignore differences in line endings from checking the snapshots out on different platforms
+    if expected.replace("\r\n", "\n") == actual {
+        return Ok(SnapshotResult::Matched);
+    }
+
+    if update {
+        std::fs::write(&path, actual)?;
+        return Ok(SnapshotResult::Updated(path));
+    }
+
+    Ok(SnapshotResult::Mismatched {
+        path,
+        expected,
+        actual: actual.to_string(),
+    })
+}
+
+/// Write a line based diff of two snapshots, marking removed lines with `-` and added lines with `+`
+pub(crate) fn diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<_> = expected.lines().collect();
+    let actual: Vec<_> = actual.lines().collect();
+
+    // Find the longest common subsequence of lines
+    let mut lcs = vec![vec![0; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            out += &format!("  {}\n", expected[i]);
+            i += 1;
+            j += 1;
+        } else if j < actual.len() && (i == expected.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out += &format!("+ {}\n", actual[j]);
+            j += 1;
+        } else {
+            out += &format!("- {}\n", expected[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+#[test]
+fn diff_marks_changed_lines() {
+    assert_eq!(diff("a\nb\nc", "a\nd\nc"), "  a\n+ d\n- b\n  c\n");
+}
@@ -0,0 +1,122 @@
+use dioxus::prelude::*;
+use dioxus_testing::TestDom;
+use std::rc::Rc;
+
+fn counter(cx: Scope) -> Element {
+    let count = use_state(cx, || 0);
+
+    render! {
+        div { id: "counter",
+            h1 { "Count: {count}" }
+            button { class: "increment", onclick: move |_| count.set(count + 1), "+" }
+            button { class: "decrement", onclick: move |_| count.set(count - 1), "-" }
+        }
+    }
+}
+
+#[test]
+fn find_elements_by_selector() {
+    let dom = TestDom::new(counter);
+
+    assert_eq!(dom.find_all("button").len(), 2);
+    assert_eq!(dom.find_all("#counter button").len(), 2);
+    assert!(dom.find("button.increment").is_some());
+    assert_ne!(dom.find("button.increment"), dom.find("button.decrement"));
+    assert!(dom.find("span").is_none());
+}
+
+#[test]
+fn events_by_selector() {
+    let mut dom = TestDom::new(counter);
+
+    dom.handle_event(
+        "button.increment",
+        "click",
+        Rc::new(MouseData::default()),
+        true,
+    );
+    dom.handle_event(
+        "button.increment",
+        "click",
+        Rc::new(MouseData::default()),
+        true,
+    );
+    dom.handle_event(
+        "button.decrement",
+        "click",
+        Rc::new(MouseData::default()),
+        true,
+    );
+
+    assert_eq!(
+        dom.html(),
+        r#"<div id="counter">
+    <h1>Count: 1</h1>
+    <button class="increment">+</button>
+    <button class="decrement">-</button>
+</div>"#
+    );
+}
+
+#[test]
+fn snapshots() {
+    let dir = std::env::temp_dir().join(format!("dioxus-testing-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    // Missing snapshots are only created in update mode
+    let mut dom = TestDom::new(counter).with_snapshot_dir(&dir);
+    dom.handle_event(
+        "button.increment",
+        "click",
+        Rc::new(MouseData::default()),
+        true,
+    );
+    let result = dioxus_testing::compare_snapshot(&dir, "counter", &dom.snapshot(), false).unwrap();
+    assert!(matches!(
+        result,
+        dioxus_testing::SnapshotResult::Missing { .. }
+    ));
+    assert!(!dir.join("counter.snap").exists());
+    let result = dioxus_testing::compare_snapshot(&dir, "counter", &dom.snapshot(), true).unwrap();
+    assert!(matches!(result, dioxus_testing::SnapshotResult::Created(_)));
+    let snapshot = std::fs::read_to_string(dir.join("counter.snap")).unwrap();
+    assert_eq!(snapshot, dom.snapshot());
+    assert!(snapshot.contains("-- click on `button.increment` --"));
+
+    // The same steps match the snapshot
+    let mut dom = TestDom::new(counter).with_snapshot_dir(&dir);
+    dom.handle_event(
+        "button.increment",
+        "click",
+        Rc::new(MouseData::default()),
+        true,
+    );
+    dom.assert_snapshot("counter");
+
+    // Different steps do not
+    let mut dom = TestDom::new(counter).with_snapshot_dir(&dir);
+    dom.handle_event(
+        "button.decrement",
+        "click",
+        Rc::new(MouseData::default()),
+        true,
+    );
+    let result = dioxus_testing::compare_snapshot(&dir, "counter", &dom.snapshot(), false).unwrap();
+    assert!(matches!(
+        result,
+        dioxus_testing::SnapshotResult::Mismatched { .. }
+    ));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+#[should_panic(expected = "does not exist")]
+fn missing_snapshots_fail() {
+    let dir = std::env::temp_dir().join(format!("dioxus-testing-missing-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    TestDom::new(counter)
+        .with_snapshot_dir(&dir)
+        .assert_snapshot("counter");
+}
@@ -0,0 +1,93 @@
+use dioxus::prelude::*;
+use dioxus_html::input_data::keyboard_types::Key;
+use dioxus_testing::{By, HeadlessDom};
+use std::time::Duration;
+
+fn counter(cx: Scope) -> Element {
+    let count = use_state(cx, || 0);
+
+    render! {
+        div { id: "counter",
+            h1 { "Count: {count}" }
+            button { class: "increment", onclick: move |_| count.set(count + 1), "Increment" }
+        }
+    }
+}
+
+#[test]
+fn click_by_text() {
+    let mut dom = HeadlessDom::new(counter);
+
+    assert_eq!(dom.find_all(By::Tag("button")).len(), 1);
+    assert!(dom.find(By::Class("increment")).is_some());
+
+    let button = dom.get(By::Text("Increment"));
+    dom.click(button);
+    dom.click(button);
+
+    let title = dom.get(By::Tag("h1"));
+    assert_eq!(dom.text_content(title), "Count: 2");
+    assert_eq!(
+        dom.to_html(),
+        r#"<div id="counter"><h1>Count: 2</h1><button class="increment">Increment</button></div>"#
+    );
+}
+
+#[test]
+fn input_and_keydown() {
+    fn app(cx: Scope) -> Element {
+        let text = use_state(cx, String::new);
+        let submitted = use_state(cx, || false);
+
+        render! {
+            input {
+                id: "name",
+                oninput: move |evt| text.set(evt.value.clone()),
+                onkeydown: move |evt| if evt.key() == Key::Enter { submitted.set(true) },
+            }
+            p { "Hello {text}" }
+            if **submitted {
+                rsx! { span { "Submitted" } }
+            }
+        }
+    }
+
+    let mut dom = HeadlessDom::new(app);
+
+    let input = dom.get(By::Id("name"));
+    dom.input(input, "Dioxus");
+    assert_eq!(dom.attribute(input, "value").as_deref(), Some("Dioxus"));
+    assert!(dom.find(By::Text("Hello Dioxus")).is_some());
+
+    assert!(dom.find(By::Text("Submitted")).is_none());
+    dom.keydown(input, Key::Enter);
+    assert!(dom.find(By::Text("Submitted")).is_some());
+}
+
+#[tokio::test(start_paused = true)]
+async fn advance_timers() {
+    fn app(cx: Scope) -> Element {
+        let ready = use_state(cx, || false);
+
+        use_effect(cx, (), |_| {
+            to_owned![ready];
+            async move {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                ready.set(true);
+            }
+        });
+
+        let status = if **ready { "Ready" } else { "Loading" };
+
+        render! { "{status}" }
+    }
+
+    let mut dom = HeadlessDom::new(app);
+    assert_eq!(dom.to_html(), "Loading");
+
+    dom.advance(Duration::from_secs(1)).await;
+    assert_eq!(dom.to_html(), "Loading");
+
+    dom.advance(Duration::from_secs(5)).await;
+    assert_eq!(dom.to_html(), "Ready");
+}